@@ -77,6 +77,23 @@ pub struct Cli {
     )]
     developer:      bool,
 
+    /// Record Input
+    /// 
+    /// Record resolved input actions with their update ticks, writing them to the given file when the game exits.
+    #[arg(
+        long,
+        conflicts_with = "replay_input"
+    )]
+    record_input:   Option<PathBuf>,
+
+    /// Replay Input
+    /// 
+    /// Replay input actions recorded with `--record-input` from the given file, in place of live input.
+    #[arg(
+        long
+    )]
+    replay_input:   Option<PathBuf>,
+
     #[command(subcommand)]
     _subcommand:    Option<Subcommands>
 
@@ -123,6 +140,14 @@ impl Cli {
         self.vsync
     }
 
+    pub fn record_input(&self) -> Option<&PathBuf> {
+        self.record_input.as_ref()
+    }
+
+    pub fn replay_input(&self) -> Option<&PathBuf> {
+        self.replay_input.as_ref()
+    }
+
 }
 
 #[derive(Subcommand, Debug)]
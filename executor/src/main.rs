@@ -19,8 +19,8 @@ const UPDATE_RATE:      f32     = 30.0;
 #[instrument]
 fn main() {
 
-    // // Read command line arguments and env.
-    // let cmds = cmd::Cli::parse();
+    // Read command line arguments and env.
+    let cmds = cmd::Cli::parse();
 
     let _ = tracing::subscriber::set_global_default(
         tracing_subscriber::registry()
@@ -35,6 +35,13 @@ fn main() {
     // executor.set_desired_update_rate(cmds.rate());
     // executor.set_headless(cmds.headless());
     // game.developer_mode(cmds.developer());
+    if let Some(path) = cmds.record_input() {
+        game.record_input(path.clone());
+    }
+    if let Some(path) = cmds.replay_input() {
+        let _ = game.replay_input(path)
+            .inspect_err(|error| error!("Unable to replay input from {}: {}", path.display(), error));
+    }
     
     executor.set_desired_update_rate(UPDATE_RATE);
     let _ = executor.set_frame_size((960, 540))
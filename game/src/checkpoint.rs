@@ -0,0 +1,99 @@
+//!
+//! Checkpoints.
+//!
+//! A `Checkpoint` script on a node marks a place for the player to respawn at. The player activates it by coming
+//! within its radius, after which they respawn there until the next one is activated. Checkpoints activate once, so
+//! walking back past an older one doesn't move the respawn point back. Activation is saved along with the scene.
+//!
+
+use fyrox:: {
+    core:: { algebra::Vector3, visitor::prelude::*, reflect::prelude::*, type_traits::prelude::* },
+    script:: { ScriptContext, ScriptTrait },
+};
+use tracing:: { info, instrument };
+use crate::player::Player;
+
+
+
+#[test]
+fn test_checkpoint_reach() {
+
+    let checkpoint = Checkpoint::default();
+    let at = Vector3::new(4.0, 0.0, 4.0);
+
+    // The player reaches a checkpoint anywhere within its radius, above and below included.
+    assert!(checkpoint.reaches(at, at));
+    assert!(checkpoint.reaches(at, at + Vector3::new(0.0, checkpoint.radius, 0.0)));
+    assert!(!checkpoint.reaches(at, at + Vector3::new(checkpoint.radius, 0.1, 0.0)));
+
+    // Activated checkpoints are never reached again.
+    let activated = Checkpoint { activated: true, ..Default::default() };
+    assert!(!activated.reaches(at, at));
+
+}
+
+
+#[derive(Visit, Reflect, Debug, Clone, TypeUuidProvider, ComponentProvider)]
+#[type_uuid(id = "7e2a9c41-5d3b-4f60-8a1e-9b4c6d2f0e73")]
+#[visit(optional)]
+pub struct Checkpoint {
+
+    /// Distance from the checkpoint within which the player activates it, in metres.
+    pub radius:     f32,
+
+    /// Activated by the player.
+    #[reflect(hidden)]
+    pub activated:  bool,
+
+}
+
+impl Checkpoint {
+
+    /// Returns `true` if a player at `player` activates this checkpoint at `checkpoint` now.
+    pub fn reaches(&self, checkpoint: Vector3<f32>, player: Vector3<f32>) -> bool {
+        !self.activated && (player - checkpoint).norm() <= self.radius
+    }
+
+}
+
+impl Default for Checkpoint {
+    fn default() -> Self {
+        Checkpoint {
+            radius:     2.0,
+            activated:  false,
+        }
+    }
+}
+
+impl ScriptTrait for Checkpoint {
+
+    #[instrument(skip(context))]
+    fn on_update(&mut self, context: &mut ScriptContext) {
+
+        if self.activated {
+            return;
+        }
+
+        // Activate once the living player comes within reach, making this where they respawn.
+        let player = context.plugins.get::<crate::Game>().player;
+        let graph = &mut context.scene.graph;
+        let checkpoint = graph[context.handle].global_position();
+        let Some(position) = graph.try_get_script_of::<Player>(player)
+            .filter(|player| player.health.is_alive())
+            .and_then(|player| graph.try_get(player.playermodel))
+            .map(|playermodel| playermodel.global_position())
+        else {
+            return;
+        };
+
+        if self.reaches(checkpoint, position) {
+            self.activated = true;
+            if let Some(player) = graph.try_get_script_of_mut::<Player>(player) {
+                player.checkpoint = context.handle;
+            }
+            info!("checkpoint {} activated", graph[context.handle].name());
+        }
+
+    }
+
+}
@@ -0,0 +1,100 @@
+//!
+//! Damage.
+//!
+//! Damage of different types, and the armour and resistances that protect against it. Shared by everything with
+//! health: the player's `PlayerHealth` and the `Health` script of any other entity.
+//!
+
+use fyrox:: {
+    core:: { visitor::prelude::*, reflect::prelude::* },
+};
+
+
+
+#[test]
+fn test_resistances_reduce() {
+
+    let resistances = Resistances { armour: 5, physical: 0.5, fire: 2.0, ..Default::default() };
+
+    // Armour comes off physical damage first, then the resistance share, rounding to the nearest point.
+    assert_eq!(resistances.reduce(Damage::new(12, DamageType::Physical)), 4);
+    assert_eq!(resistances.reduce(Damage::new(4, DamageType::Physical)), 0);
+
+    // Other types ignore armour, and resistances past full are immunity.
+    assert_eq!(resistances.reduce(Damage::new(12, DamageType::Fall)), 12);
+    assert_eq!(resistances.reduce(Damage::new(u16::MAX, DamageType::Fire)), 0);
+
+}
+
+
+/// Kinds of damage, each resisted separately.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DamageType {
+
+    /// Blows, cuts and projectiles. Armour protects against it.
+    #[default]
+    Physical,
+
+    /// Burning.
+    Fire,
+
+    /// Damage over time from poison. Neither blocked by nor granting invulnerability frames.
+    Poison,
+
+    /// Landing too hard.
+    Fall,
+
+}
+
+impl DamageType {
+
+    /// Returns `true` if damage of this type is blocked by invulnerability frames, and grants them when it lands.
+    pub fn uses_iframes(&self) -> bool {
+        !matches!(self, DamageType::Poison)
+    }
+
+}
+
+/// An amount of damage of some type, before resistances.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Damage {
+    pub amount: u16,
+    pub kind:   DamageType,
+}
+
+impl Damage {
+
+    pub fn new(amount: u16, kind: DamageType) -> Self {
+        Damage { amount, kind }
+    }
+
+}
+
+/// Protection against damage. Armour takes a flat amount off physical damage; resistances then take a share off
+/// damage of their type, from `0.0` (none) to `1.0` (immune).
+#[derive(Visit, Reflect, Debug, Clone, Default, PartialEq)]
+#[visit(optional)]
+pub struct Resistances {
+    pub armour:     u16,
+    pub physical:   f32,
+    pub fire:       f32,
+    pub poison:     f32,
+    pub fall:       f32,
+}
+
+impl Resistances {
+
+    /// Lifepoints `damage` takes after armour and resistances, rounded to the nearest point.
+    pub fn reduce(&self, damage: Damage) -> u16 {
+
+        let (amount, resistance) = match damage.kind {
+            DamageType::Physical    => (damage.amount.saturating_sub(self.armour), self.physical),
+            DamageType::Fire        => (damage.amount, self.fire),
+            DamageType::Poison      => (damage.amount, self.poison),
+            DamageType::Fall        => (damage.amount, self.fall),
+        };
+        (amount as f32 * (1.0 - resistance.clamp(0.0, 1.0))).round() as u16
+
+    }
+
+}
@@ -0,0 +1,170 @@
+//!
+//! Lifepoints.
+//!
+//! The saturating core of every kind of health: lifepoints that never overflow or pass capacity, and damage taken
+//! through `Resistances`. The player's `PlayerHealth` and the `Health` script both build on it, and dereference to it.
+//!
+
+use std::fmt::Display;
+use fyrox:: {
+    core:: { visitor::prelude::*, reflect::prelude::* },
+};
+use super::damage:: { Damage, DamageType, Resistances };
+
+
+
+#[test]
+fn test_lifepoints_safety() {
+
+    let mut pool = Lifepoints::new(100);
+
+    // LP start at CAP.
+    assert_eq!(*pool.lifepoints(), 100);
+    assert_eq!(*pool.capacity(), 100);
+
+    // You can't add more LP than your CAP, and adding never overflows.
+    assert_eq!(pool.lifepoints_add(10), &100);
+    assert_eq!(pool.capacity_change(0), Err(()));
+    assert_eq!(pool.capacity_change(150), Ok(150));
+    assert_eq!(pool.lifepoints_add(u16::MAX), &150);
+
+    // Subtracting never overflows either, and zero LP equates death.
+    assert_eq!(pool.lifepoints_sub(u16::MAX), &0);
+    assert_eq!(pool.lifepoints_sub(1), &0);
+    assert!(pool.is_dead());
+
+}
+
+#[test]
+fn test_lifepoints_damage_and_heal() {
+
+    let mut pool = Lifepoints::new(100);
+    pool.resistances.armour = 2;
+
+    // Damage goes through resistances and returns what was actually lost...
+    assert_eq!(pool.damage(Damage::new(12, DamageType::Physical)), 10);
+    assert_eq!(pool.damage(Damage::new(u16::MAX, DamageType::Fire)), 90);
+    assert!(pool.is_dead());
+
+    // ...and the dead can be neither hurt nor healed.
+    assert_eq!(pool.damage(Damage::new(1, DamageType::Fire)), 0);
+    assert_eq!(pool.heal(10), 0);
+
+    let mut pool = Lifepoints::new(100);
+    pool.lifepoints_sub(30);
+    assert_eq!(pool.heal(50), 30);
+
+}
+
+
+#[derive(Visit, Reflect, Debug, Clone, PartialEq)]
+#[visit(optional)]
+pub struct Lifepoints {
+
+    /// Current health points.
+    pub lifepoints:     u16,
+
+    /// Capacity of health points, as in, the maximum amount of points.
+    capacity:           u16,
+
+    /// Armour and resistances to damage types.
+    pub resistances:    Resistances,
+
+}
+
+impl Lifepoints {
+
+    /// Full lifepoints of `capacity`, without resistances. A `capacity` of zero is taken as one.
+    pub fn new(capacity: u16) -> Self {
+        let capacity = capacity.max(1);
+        Lifepoints { lifepoints: capacity, capacity, resistances: Resistances::default() }
+    }
+
+    /// Return health capacity.
+    pub fn capacity(&self) -> &u16 {
+        &self.capacity
+    }
+
+    /// Return lifepoints at the current frame.
+    pub fn lifepoints(&self) -> &u16 {
+        &self.lifepoints
+    }
+
+    /// Change health capacity to the `new` value provided.
+    /// Returns `Err` if `new` is zero, as a capacity of zero is illogical. Lifepoints don't change with capacity.
+    pub fn capacity_change(&mut self, new: u16) -> Result<u16, ()> {
+        if new >= 1 {
+            self.capacity = new;
+            Ok(self.capacity)
+        }
+        else {
+            Err(())
+        }
+    }
+
+    /// Add the amount of lifepoints in `add`. Returns the new value.
+    /// This function saturates at capacity instead of overflowing.
+    ///
+    /// ```lifepoints = lifepoints + add```
+    pub fn lifepoints_add(&mut self, add: u16) -> &u16 {
+        self.lifepoints = self.lifepoints.saturating_add(add).min(self.capacity);
+        &self.lifepoints
+    }
+
+    /// Subtract the amount of lifepoints in `subtract`. Returns the new value.
+    /// This function saturates at zero instead of overflowing.
+    ///
+    /// ```lifepoints = lifepoints - subtract```
+    pub fn lifepoints_sub(&mut self, subtract: u16) -> &u16 {
+        self.lifepoints = self.lifepoints.saturating_sub(subtract);
+        &self.lifepoints
+    }
+
+    /// Deal `damage`, reduced by resistances. Returns the lifepoints actually lost; nothing while dead.
+    pub fn damage(&mut self, damage: Damage) -> u16 {
+        if self.is_dead() {
+            return 0;
+        }
+        let before = self.lifepoints;
+        before - *self.lifepoints_sub(self.resistances.reduce(damage))
+    }
+
+    /// Heal `amount` lifepoints, up to capacity. Returns the lifepoints actually gained. The dead can't be healed.
+    pub fn heal(&mut self, amount: u16) -> u16 {
+        if self.is_dead() {
+            return 0;
+        }
+        let before = self.lifepoints;
+        *self.lifepoints_add(amount) - before
+    }
+
+    pub fn is_alive(&self) -> bool {
+        self.lifepoints != 0
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.lifepoints == 0
+    }
+
+    /// Visit lifepoints, capacity and resistances straight into the region `visitor` is in rather than one of their
+    /// own, the way `Health` and `PlayerHealth` saved them before sharing this core. Missing fields are left as they
+    /// are.
+    pub fn visit_flat(&mut self, visitor: &mut Visitor) {
+        let _ = self.lifepoints.visit("Lifepoints", visitor);
+        let _ = self.capacity.visit("Capacity", visitor);
+        let _ = self.resistances.visit("Resistances", visitor);
+    }
+
+}
+
+impl Default for Lifepoints {
+    fn default() -> Self {
+        Self::new(100)
+    }
+}
+
+impl Display for Lifepoints {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{}/{}", self.lifepoints, self.capacity))
+    }
+}
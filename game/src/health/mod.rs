@@ -0,0 +1,178 @@
+//!
+//! Health of any entity.
+//!
+//! The `Health` script gives NPCs, destructible props and anything else in a scene the same `Lifepoints` the player's
+//! `PlayerHealth` builds on, taking `damage::Damage` through the same resistances, and handles running out: the node
+//! is kept, despawned, or swapped for a broken version instantiated from a prefab in its place.
+//!
+
+use std::fmt::Display;
+use fyrox:: {
+    core:: { visitor::prelude::*, reflect::prelude::*, type_traits::prelude::* },
+    resource::model:: { ModelResource, ModelResourceExtension },
+    script:: { ScriptContext, ScriptTrait },
+};
+use strum_macros::*;
+use tracing:: { info, warn, instrument };
+
+pub mod damage;
+pub mod lifepoints;
+pub use lifepoints::Lifepoints;
+use damage::Damage;
+
+
+/// What becomes of a node whose `Health` runs out.
+#[derive(Visit, Reflect, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(AsRefStr, EnumString, VariantNames, TypeUuidProvider)]
+#[type_uuid(id = "0f6b3a52-8f0e-4c1d-b6a4-3e9c2d7a5b18")]
+pub enum DeathAction {
+
+    /// The node stays as it is; other scripts may check `Health::is_dead()`.
+    #[default]
+    Keep,
+
+    /// The node is removed from the scene.
+    Despawn,
+
+    /// The node is removed, and `Health::broken` is instantiated where it was.
+    Replace,
+
+}
+
+#[derive(Reflect, Debug, Clone, TypeUuidProvider, ComponentProvider)]
+#[type_uuid(id = "c3d1e7a4-6b2f-4a89-9e5d-1f7b0c8a2d64")]
+pub struct Health {
+
+    /// Lifepoints, capacity and resistances.
+    pub pool:           Lifepoints,
+
+    /// What becomes of the node once its lifepoints run out.
+    pub on_death:       DeathAction,
+
+    /// Prefab of the broken version of the node, instantiated in its place when `on_death` is `Replace`.
+    pub broken:         Option<ModelResource>,
+
+    /// Death has been handled.
+    #[reflect(hidden)]
+    died:               bool,
+
+}
+
+impl Health {
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Deal `damage`, reduced by resistances. Returns the lifepoints actually lost; nothing while dead.
+    pub fn damage(&mut self, damage: Damage) -> u16 {
+        self.pool.damage(damage)
+    }
+
+    /// Heal `amount` lifepoints, up to capacity. Returns the lifepoints actually gained. The dead can't be healed.
+    pub fn heal(&mut self, amount: u16) -> u16 {
+        self.pool.heal(amount)
+    }
+
+    /// Return lifepoints at the current frame.
+    pub fn lifepoints(&self) -> &u16 {
+        self.pool.lifepoints()
+    }
+
+    /// Return health capacity.
+    pub fn capacity(&self) -> &u16 {
+        self.pool.capacity()
+    }
+
+    /// Change health capacity to the `new` value provided. Returns `Err` if `new` is zero.
+    pub fn capacity_change(&mut self, new: u16) -> Result<u16, ()> {
+        self.pool.capacity_change(new)
+    }
+
+    pub fn is_alive(&self) -> bool {
+        self.pool.is_alive()
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.pool.is_dead()
+    }
+
+}
+
+// Lifepoints are visited flat, as they were saved before `pool` held them, so older scenes and saves keep them.
+impl Visit for Health {
+    fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        let mut region = visitor.enter_region(name)?;
+        self.pool.visit_flat(&mut region);
+        let _ = self.on_death.visit("OnDeath", &mut region);
+        let _ = self.broken.visit("Broken", &mut region);
+        Ok(())
+    }
+}
+
+impl Default for Health {
+    fn default() -> Self {
+        Health {
+            pool:           Lifepoints::new(100),
+            on_death:       DeathAction::default(),
+            broken:         None,
+            died:           false,
+        }
+    }
+}
+
+impl Display for Health {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.pool.fmt(f)
+    }
+}
+
+impl ScriptTrait for Health {
+
+    #[instrument(skip(context))]
+    fn on_update(&mut self, context: &mut ScriptContext) {
+
+        // Nothing dies while the game is paused.
+        if context.plugins.get::<crate::Game>().is_paused() {
+            return;
+        }
+
+        // Death is handled once; lifepoints set back above zero bring the node back to life.
+        if self.is_alive() {
+            self.died = false;
+            return;
+        }
+        if self.died {
+            return;
+        }
+        self.died = true;
+
+        let node = &context.scene.graph[context.handle];
+        let name = node.name().to_owned();
+        let (position, rotation) = (node.global_position(), node.global_rotation());
+        info!("{} died", name);
+
+        match self.on_death {
+
+            DeathAction::Keep       => {},
+
+            DeathAction::Despawn    => context.scene.graph.remove_node(context.handle),
+
+            DeathAction::Replace    => {
+                match &self.broken {
+                    Some(broken) => {
+                        let instance = broken.instantiate(context.scene);
+                        context.scene.graph[instance].local_transform_mut()
+                            .set_position(position)
+                            .set_rotation(rotation);
+                    },
+                    None => warn!("{} has no broken version to be replaced with", name),
+                }
+                context.scene.graph.remove_node(context.handle);
+            },
+
+        }
+
+    }
+
+}
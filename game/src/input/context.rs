@@ -0,0 +1,141 @@
+//!
+//! Input contexts.
+//!
+//! Each context on the `InputSystem` stack has its own bindings and trigger state. Events go to the topmost context
+//! first; it either consumes them or, if it's a *passthrough* context and has no binding for the event, passes them to
+//! the context beneath.
+//!
+
+use std::collections::HashMap;
+use fyrox::keyboard:: { KeyCode, PhysicalKey };
+use crate::settings::input:: {
+    Binding,
+    BindingActions,
+    Bindings,
+    BindingSources,
+    desktop_input::DesktopInputSources,
+    touch_input::TouchInputSources,
+};
+use super::triggers::TriggerEvaluator;
+
+
+/// Kinds of input context, from the bottom of the stack up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InputContextKind {
+
+    /// Moving and looking around. Always at the bottom of the stack, using the player's bindings from settings.
+    Gameplay,
+
+    /// The pause menu is open.
+    PauseMenu,
+
+    /// A text field has focus; keys type text instead of triggering actions.
+    TextEntry,
+
+    /// The developer console is open.
+    DeveloperConsole,
+
+    /// Waiting for the player to press the source they want to bind.
+    RebindCapture,
+
+    /// The player died, and is asked to respawn.
+    Death,
+
+}
+
+impl InputContextKind {
+
+    /// Returns `true` if this context hides the cursor and holds it inside the game window.
+    pub fn grabs_cursor(&self) -> bool {
+        match self {
+            InputContextKind::Gameplay  => true,
+            _                           => false,
+        }
+    }
+
+}
+
+/// A layer of the input context stack.
+#[derive(Debug)]
+pub struct InputContext {
+
+    /// What this context is for.
+    pub kind:           InputContextKind,
+
+    /// Bindings of this context. `None` uses the player's bindings from settings.
+    pub bindings:       Option<Bindings>,
+
+    /// Pass events without a binding here to the context beneath, instead of consuming them.
+    pub passthrough:    bool,
+
+    pub(super) triggers: TriggerEvaluator,
+
+}
+
+impl InputContext {
+
+    /// Create a context of `kind` with its default bindings, consuming every event.
+    pub fn new(kind: InputContextKind) -> Self {
+        InputContext {
+            kind,
+            bindings:       Self::default_bindings(kind),
+            passthrough:    false,
+            triggers:       TriggerEvaluator::default(),
+        }
+    }
+
+    /// Pass events without a binding here to the context beneath.
+    pub fn with_passthrough(mut self, passthrough: bool) -> Self {
+        self.passthrough = passthrough;
+        self
+    }
+
+    /// Replace the bindings of this context.
+    pub fn with_bindings(mut self, bindings: Bindings) -> Self {
+        self.bindings = Some(bindings);
+        self
+    }
+
+    /// Bindings in effect, given the player's `gameplay` bindings.
+    pub fn bindings<'a>(&'a self, gameplay: &'a Bindings) -> &'a Bindings {
+        self.bindings.as_ref().unwrap_or(gameplay)
+    }
+
+    // Menus and overlays get fixed bindings to close themselves; gameplay uses the player's bindings.
+    fn default_bindings(kind: InputContextKind) -> Option<Bindings> {
+
+        let key = |code| BindingSources::Desktop(DesktopInputSources::Key(PhysicalKey::Code(code)));
+        let touch = |index| BindingSources::Touch(TouchInputSources::Button(index));
+        let mut map = HashMap::new();
+
+        match kind {
+            InputContextKind::Gameplay          => return None,
+            InputContextKind::PauseMenu         => {
+                map.insert(key(KeyCode::Escape).into(), Binding::held(BindingActions::pause()));
+                // The on-screen button that pauses in the default touch layout unpauses too.
+                map.insert(touch(1).into(), Binding::held(BindingActions::pause()));
+            },
+            InputContextKind::TextEntry         => {
+                map.insert(key(KeyCode::Escape).into(), Binding::held(BindingActions::cancel()));
+            },
+            InputContextKind::DeveloperConsole  => {
+                map.insert(key(KeyCode::Escape).into(), Binding::held(BindingActions::cancel()));
+                map.insert(key(KeyCode::Backquote).into(), Binding::held(BindingActions::developer_console()));
+            },
+            InputContextKind::RebindCapture     => {
+                map.insert(key(KeyCode::Escape).into(), Binding::held(BindingActions::cancel()));
+            },
+            InputContextKind::Death             => {
+                let pad = |button: gilrs::Button| BindingSources::Gamepad(button.into());
+                map.insert(key(KeyCode::Enter).into(), Binding::held(BindingActions::respawn()));
+                map.insert(key(KeyCode::Space).into(), Binding::held(BindingActions::respawn()));
+                map.insert(pad(gilrs::Button::South).into(), Binding::held(BindingActions::respawn()));
+                map.insert(touch(0).into(), Binding::held(BindingActions::respawn()));
+            },
+        }
+
+        Some(map)
+
+    }
+
+}
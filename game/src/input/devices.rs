@@ -0,0 +1,245 @@
+//!
+//! Input devices.
+//!
+//! Tracks gamepads as they connect and disconnect, assigns each to a player slot, and follows which device the player
+//! in the first slot is using: keyboard and mouse, or their gamepad. A slot whose gamepad disconnects stays reserved for
+//! it, so the same pad (or the next one connected) picks up where it left off.
+//!
+//! Gamepad identifiers are generic so assignment can be exercised without a device; the game uses `gilrs::GamepadId`.
+//!
+
+use crate::settings::input::gamepad_input::GamepadId;
+
+
+/// Number of player slots gamepads can be assigned to.
+pub const PLAYER_SLOTS: usize = 4;
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_deviceassignments_slots() {
+
+        let mut devices = DeviceAssignments::<u32>::default();
+
+        // Pads take the first free slot, once.
+        assert_eq!(devices.connect(7), Some(DeviceChange::Connected { gamepad: 7, slot: 0 }));
+        assert_eq!(devices.connect(7), None);
+        assert_eq!(devices.connect(9), Some(DeviceChange::Connected { gamepad: 9, slot: 1 }));
+        assert_eq!(devices.slot_of(9), Some(1));
+        assert_eq!(devices.gamepad_of(0), Some(7));
+
+        // A disconnected pad keeps its slot and gets it back on reconnecting.
+        assert_eq!(devices.disconnect(9), Some(DeviceChange::Disconnected { gamepad: 9, slot: 1, was_active: false }));
+        assert_eq!(devices.gamepad_of(1), None);
+        assert_eq!(devices.connect(3), Some(DeviceChange::Connected { gamepad: 3, slot: 1 }));
+        assert_eq!(devices.slot_of(9), None);
+
+        // Slots run out.
+        for gamepad in 10..20 {
+            devices.connect(gamepad);
+        }
+        assert_eq!(devices.connect(42), None);
+        assert_eq!(devices.slot_of(42), None);
+
+    }
+
+    #[test]
+    fn test_deviceassignments_active_device() {
+
+        let mut devices = DeviceAssignments::<u32>::default();
+        assert_eq!(devices.active(), InputDevice::KeyboardMouse);
+        devices.connect(7);
+        devices.connect(9);
+
+        // Using the first player's pad switches to it; keyboard and mouse switch back.
+        assert_eq!(devices.use_device(InputDevice::Gamepad(7)), Some(DeviceChange::Switched(InputDevice::Gamepad(7))));
+        assert_eq!(devices.use_device(InputDevice::Gamepad(7)), None);
+        assert_eq!(devices.use_device(InputDevice::KeyboardMouse), Some(DeviceChange::Switched(InputDevice::KeyboardMouse)));
+
+        // Other players' pads don't.
+        assert_eq!(devices.use_device(InputDevice::Gamepad(9)), None);
+        assert_eq!(devices.active(), InputDevice::KeyboardMouse);
+
+    }
+
+    #[test]
+    fn test_deviceassignments_reconnect() {
+
+        let mut devices = DeviceAssignments::<u32>::default();
+        devices.connect(7);
+        devices.use_device(InputDevice::Gamepad(7));
+
+        // Losing the active pad waits for a reconnect...
+        assert_eq!(devices.disconnect(7), Some(DeviceChange::Disconnected { gamepad: 7, slot: 0, was_active: true }));
+        assert!(devices.awaiting_reconnect());
+        assert_eq!(devices.use_device(InputDevice::Gamepad(7)), None);
+
+        // ...which the same pad ends.
+        assert_eq!(devices.connect(7), Some(DeviceChange::Reconnected { gamepad: 7, slot: 0 }));
+        assert!(!devices.awaiting_reconnect());
+        assert_eq!(devices.active(), InputDevice::Gamepad(7));
+
+        // So does another pad taking over the slot, or switching to keyboard and mouse.
+        devices.disconnect(7);
+        assert_eq!(devices.connect(8), Some(DeviceChange::Reconnected { gamepad: 8, slot: 0 }));
+        assert_eq!(devices.active(), InputDevice::Gamepad(8));
+        devices.disconnect(8);
+        devices.use_device(InputDevice::KeyboardMouse);
+        assert!(!devices.awaiting_reconnect());
+
+    }
+
+}
+
+
+/// A device a player provides input with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InputDevice<Id = GamepadId> {
+    KeyboardMouse,
+    Gamepad(Id),
+}
+
+/// A change of device, reported by `DeviceAssignments`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeviceChange<Id = GamepadId> {
+
+    /// `gamepad` connected and was assigned to `slot`.
+    Connected { gamepad: Id, slot: usize },
+
+    /// `gamepad` connected to `slot`, which was waiting for its active pad to reconnect.
+    Reconnected { gamepad: Id, slot: usize },
+
+    /// `gamepad` in `slot` disconnected. `was_active` if the first player was using it.
+    Disconnected { gamepad: Id, slot: usize, was_active: bool },
+
+    /// The first player switched to using this device.
+    Switched(InputDevice<Id>),
+
+}
+
+/// A gamepad assigned to a player slot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct PadAssignment<Id> {
+    gamepad:    Id,
+    connected:  bool,
+}
+
+/// Assignment of gamepads to player slots, and the first player's active device.
+#[derive(Debug)]
+pub struct DeviceAssignments<Id = GamepadId> {
+
+    // Gamepad assigned to each player slot.
+    slots:      [Option<PadAssignment<Id>>; PLAYER_SLOTS],
+
+    // Device the first player is using.
+    active:     InputDevice<Id>,
+
+    // Whether the first player's active pad disconnected and hasn't come back.
+    awaiting:   bool,
+
+}
+
+impl<Id: Copy + Eq> DeviceAssignments<Id> {
+
+    /// Note `gamepad` connecting, assigning it a slot.
+    ///
+    /// A pad gets back the slot it held before disconnecting. Otherwise it takes the slot waiting for a reconnect, or
+    /// any slot whose pad disconnected, or the first free slot, in that order. Returns `None` if `gamepad` was already
+    /// connected or no slot is left.
+    pub fn connect(&mut self, gamepad: Id) -> Option<DeviceChange<Id>> {
+
+        if let Some(slot) = self.slots.iter().position(|pad| pad.is_some_and(|pad| pad.gamepad == gamepad)) {
+            if self.slots[slot].is_some_and(|pad| pad.connected) {
+                return None;
+            }
+            return Some(self.assign(slot, gamepad));
+        }
+
+        let slot = if self.awaiting { Some(0) } else { None }
+            .or_else(|| self.slots.iter().position(|pad| pad.is_some_and(|pad| !pad.connected)))
+            .or_else(|| self.slots.iter().position(Option::is_none))?;
+        Some(self.assign(slot, gamepad))
+
+    }
+
+    // Put `gamepad` in `slot`, resuming the first player's pad if it was awaited.
+    fn assign(&mut self, slot: usize, gamepad: Id) -> DeviceChange<Id> {
+
+        self.slots[slot] = Some(PadAssignment { gamepad, connected: true });
+
+        if slot == 0 && self.awaiting {
+            self.awaiting = false;
+            self.active = InputDevice::Gamepad(gamepad);
+            return DeviceChange::Reconnected { gamepad, slot };
+        }
+        DeviceChange::Connected { gamepad, slot }
+
+    }
+
+    /// Note `gamepad` disconnecting. Its slot stays reserved for it. Returns `None` if it had no slot.
+    pub fn disconnect(&mut self, gamepad: Id) -> Option<DeviceChange<Id>> {
+
+        let slot = self.slot_of(gamepad)?;
+        self.slots[slot] = Some(PadAssignment { gamepad, connected: false });
+
+        let was_active = self.active == InputDevice::Gamepad(gamepad);
+        if was_active {
+            self.awaiting = true;
+        }
+        Some(DeviceChange::Disconnected { gamepad, slot, was_active })
+
+    }
+
+    /// Note the first player using `device`, switching to it if it's keyboard and mouse or their connected gamepad.
+    pub fn use_device(&mut self, device: InputDevice<Id>) -> Option<DeviceChange<Id>> {
+
+        if let InputDevice::Gamepad(gamepad) = device {
+            if self.slot_of(gamepad) != Some(0) {
+                return None;
+            }
+        }
+        if self.active == device {
+            return None;
+        }
+
+        self.active = device;
+        self.awaiting = false;
+        Some(DeviceChange::Switched(device))
+
+    }
+
+    /// Slot of the connected `gamepad`, if any.
+    pub fn slot_of(&self, gamepad: Id) -> Option<usize> {
+        self.slots.iter().position(|pad| pad.is_some_and(|pad| pad.connected && pad.gamepad == gamepad))
+    }
+
+    /// Connected gamepad assigned to `slot`, if any.
+    pub fn gamepad_of(&self, slot: usize) -> Option<Id> {
+        self.slots.get(slot)?.filter(|pad| pad.connected).map(|pad| pad.gamepad)
+    }
+
+    /// Device the first player is using.
+    pub fn active(&self) -> InputDevice<Id> {
+        self.active
+    }
+
+    /// Returns `true` while the first player's active gamepad is disconnected.
+    pub fn awaiting_reconnect(&self) -> bool {
+        self.awaiting
+    }
+
+}
+
+impl<Id> Default for DeviceAssignments<Id> {
+    fn default() -> Self {
+        DeviceAssignments {
+            slots:      std::array::from_fn(|_| None),
+            active:     InputDevice::KeyboardMouse,
+            awaiting:   false,
+        }
+    }
+}
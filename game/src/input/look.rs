@@ -0,0 +1,146 @@
+//!
+//! Mouse look.
+//!
+//! Accumulates raw mouse motion between update ticks and maps it to yaw and pitch changes in degrees, according to the
+//! player's `DesktopInput` settings: acceleration first, then sensitivity, per-axis scaling and inversion, then
+//! smoothing.
+//!
+
+use crate::settings::input::desktop_input::DesktopInput;
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::settings::input::desktop_input::MouseAcceleration;
+
+    fn approx(a: (f32, f32), b: (f32, f32)) -> bool {
+        (a.0 - b.0).abs() < 1e-4 && (a.1 - b.1).abs() < 1e-4
+    }
+
+    #[test]
+    fn test_mouselook_defaults() {
+
+        // One count is one degree; moving right turns right (yaw decreases), moving back looks down (pitch increases).
+        let settings = DesktopInput::default();
+        assert!(approx(look_angles(&settings, (3.0, -2.0)), (-3.0, -2.0)));
+        assert!(approx(look_angles(&settings, (0.0, 0.0)), (0.0, 0.0)));
+
+    }
+
+    #[test]
+    fn test_mouselook_sensitivity_and_scale() {
+
+        let settings = DesktopInput {
+            mouse_sensitivity:  0.5,
+            mouse_scale_x:      2.0,
+            mouse_scale_y:      0.5,
+            ..Default::default()
+        };
+        assert!(approx(look_angles(&settings, (4.0, 4.0)), (-4.0, 1.0)));
+
+        let inverted = DesktopInput { mouse_invert_y: true, ..Default::default() };
+        assert!(approx(look_angles(&inverted, (4.0, 4.0)), (-4.0, -4.0)));
+
+    }
+
+    #[test]
+    fn test_mouselook_acceleration() {
+
+        let acceleration = MouseAcceleration { factor: 0.1, exponent: 1.0, max_gain: 2.0 };
+        let settings = DesktopInput { mouse_acceleration: acceleration, ..Default::default() };
+
+        // Gain grows with speed, and is capped.
+        assert_eq!(acceleration.gain(0.0), 1.0);
+        assert!((acceleration.gain(5.0) - 1.5).abs() < 1e-6);
+        assert_eq!(acceleration.gain(100.0), 2.0);
+        assert_eq!(MouseAcceleration::default().gain(100.0), 1.0);
+
+        // Speed is the length of the movement, so both axes get the same gain.
+        assert!(approx(look_angles(&settings, (3.0, 4.0)), (-4.5, 6.0)));
+
+    }
+
+    #[test]
+    fn test_mouselook_smoothing() {
+
+        let settings = DesktopInput { mouse_smoothing: 0.5, ..Default::default() };
+        let mut look = MouseLook::default();
+
+        // Motion between ticks is accumulated, then released over following ticks...
+        look.accumulate(6.0, 0.0);
+        look.accumulate(2.0, 0.0);
+        assert!(approx(look.update(&settings), (-4.0, 0.0)));
+        assert!(approx(look.update(&settings), (-2.0, 0.0)));
+        assert!(approx(look.update(&settings), (-1.0, 0.0)));
+
+        // ...adding up to the unsmoothed total.
+        let total = (0..32).fold(-7.0, |total, _| total + look.update(&settings).0);
+        assert!((total - -8.0).abs() < 1e-4);
+
+        // Without smoothing, motion applies in full on the next tick.
+        let mut look = MouseLook::default();
+        look.accumulate(6.0, 2.0);
+        assert!(approx(look.update(&DesktopInput::default()), (-6.0, 2.0)));
+        assert!(approx(look.update(&DesktopInput::default()), (0.0, 0.0)));
+
+    }
+
+}
+
+
+/// Map a mouse movement of `delta` counts to `(yaw, pitch)` changes in degrees, without smoothing.
+pub fn look_angles(settings: &DesktopInput, delta: (f32, f32)) -> (f32, f32) {
+
+    let gain = settings.mouse_acceleration.gain((delta.0 * delta.0 + delta.1 * delta.1).sqrt());
+    let invert = if settings.mouse_invert_y { -1.0 } else { 1.0 };
+
+    (
+        -delta.0 * gain * settings.mouse_sensitivity * settings.mouse_scale_x,
+        delta.1 * gain * settings.mouse_sensitivity * settings.mouse_scale_y * invert,
+    )
+
+}
+
+/// Mouse look state carried between update ticks.
+#[derive(Debug, Clone, Default)]
+pub struct MouseLook {
+
+    // Raw motion since the last update tick, in counts.
+    pending:    (f32, f32),
+
+    // Look applied on the last update tick, in degrees.
+    smoothed:   (f32, f32),
+
+}
+
+impl MouseLook {
+
+    /// Add raw mouse motion, in counts.
+    pub fn accumulate(&mut self, dx: f32, dy: f32) {
+        self.pending.0 += dx;
+        self.pending.1 += dy;
+    }
+
+    /// Take the motion accumulated since the last call and return the `(yaw, pitch)` change to apply this update
+    /// tick, in degrees.
+    pub fn update(&mut self, settings: &DesktopInput) -> (f32, f32) {
+
+        let target = look_angles(settings, std::mem::take(&mut self.pending));
+        let carry = settings.mouse_smoothing.clamp(0.0, 0.99);
+
+        self.smoothed = (
+            self.smoothed.0 * carry + target.0 * (1.0 - carry),
+            self.smoothed.1 * carry + target.1 * (1.0 - carry),
+        );
+        self.smoothed
+
+    }
+
+    /// Drop accumulated and smoothed motion, so nothing carries over once look input resumes.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+}
@@ -0,0 +1,793 @@
+//!
+//! Input subsystem.
+//!
+//! Feeds window, device and gamepad events through a stack of input contexts and resolves them into actions. See
+//! [`context`] for how events travel down the stack and [`triggers`] for how each binding's `BindingTrigger` is
+//! evaluated. Resolved actions can be recorded and replayed in place of live input; see [`recording`]. Mouse look is
+//! mapped separately, in [`look`]. Gamepads are assigned to player slots as they connect, in [`devices`],
+//! and [`rumble`] schedules their force feedback. [`prompts`] labels actions for on-screen prompts. On-screen touch
+//! controls are followed in [`touch`].
+//!
+
+use std:: { collections::HashMap, io, path::PathBuf };
+use fyrox::event:: { DeviceEvent, ElementState, Event, MouseScrollDelta, Touch, WindowEvent };
+use tracing::info;
+use crate::settings::input:: {
+    BindingActions,
+    BindingChord,
+    Bindings,
+    BindingSources,
+    ChordModifiers,
+    desktop_input:: { DesktopInputSources, abstractions:: { self, MouseScrollDirection } },
+    gamepad_input:: { AxisDirection, GamepadId, GamepadInputSources },
+    touch_input::TouchInput,
+};
+
+pub mod context;
+pub mod devices;
+pub mod look;
+pub mod prompts;
+pub mod recording;
+pub mod rumble;
+pub mod touch;
+pub mod triggers;
+
+pub use context:: { InputContext, InputContextKind };
+pub use devices:: { DeviceAssignments, DeviceChange, InputDevice };
+pub use look::MouseLook;
+pub use prompts:: { ControllerFamily, KeyLabels, PromptLabel };
+pub use recording:: { InputRecorder, InputRecording, InputReplay, TickMotion };
+pub use rumble:: { Rumble, RumbleScheduler };
+pub use touch:: { TouchChange, TouchControls };
+pub use triggers:: { ActionState, ResolvedAction };
+
+
+/// How far an analog axis must be pushed before it counts as a press of its `GamepadInputSources::Axis`.
+const AXIS_PRESS_THRESHOLD: f32 = 0.5;
+
+/// How far, in counts, the mouse must move in one event to count as switching to keyboard and mouse.
+const MOUSE_USE_THRESHOLD: f64 = 2.0;
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use fyrox:: { dpi::PhysicalPosition, keyboard:: { KeyCode, PhysicalKey } };
+    use crate::settings::input:: { Binding, LinearDirectionSetting, touch_input };
+
+    fn key(code: KeyCode) -> BindingSources {
+        BindingSources::Desktop(DesktopInputSources::Key(PhysicalKey::Code(code)))
+    }
+
+    fn gameplay_bindings() -> Bindings {
+        let mut bindings = Bindings::new();
+        bindings.insert(key(KeyCode::KeyW).into(), Binding::held(BindingActions::linear(LinearDirectionSetting::forward())));
+        bindings.insert(key(KeyCode::Escape).into(), Binding::held(BindingActions::pause()));
+        bindings
+    }
+
+    #[test]
+    fn test_inputsystem_menu_stops_gameplay() {
+
+        let bindings = gameplay_bindings();
+        let forward = BindingActions::linear(LinearDirectionSetting::forward());
+        let mut input = InputSystem::default();
+        input.set_focused(true);
+
+        // Gameplay is at the bottom and grabs the cursor.
+        assert_eq!(input.top(), InputContextKind::Gameplay);
+        assert!(input.gameplay_active());
+        assert_eq!(input.cursor_change(), Some(true));
+        assert_eq!(input.cursor_change(), None);
+
+        // Walking...
+        input.press(key(KeyCode::KeyW), &bindings);
+        assert!(input.is_active(&forward));
+
+        // ...until the pause menu opens, which stops movement and releases the cursor.
+        input.push(InputContext::new(InputContextKind::PauseMenu));
+        assert!(!input.is_active(&forward));
+        assert!(!input.gameplay_active());
+        assert_eq!(input.cursor_change(), Some(false));
+        assert_eq!(input.take_resolved().last(), Some(&ResolvedAction::ended(forward.clone())));
+
+        // Gameplay bindings don't reach through the menu.
+        input.release(key(KeyCode::KeyW), &bindings);
+        input.press(key(KeyCode::KeyW), &bindings);
+        assert!(!input.is_active(&forward));
+        assert!(input.take_resolved().is_empty());
+
+        // Closing the menu hands input back to gameplay.
+        assert_eq!(input.pop().map(|context| context.kind), Some(InputContextKind::PauseMenu));
+        assert!(input.gameplay_active());
+        assert_eq!(input.cursor_change(), Some(true));
+
+        // Gameplay can never be popped.
+        assert!(input.pop().is_none());
+        assert_eq!(input.top(), InputContextKind::Gameplay);
+
+    }
+
+    #[test]
+    fn test_inputsystem_pause_toggles() {
+
+        let bindings = gameplay_bindings();
+        let mut input = InputSystem::default();
+        input.set_focused(true);
+
+        // Escape resolves to `Pause` in gameplay...
+        input.press(key(KeyCode::Escape), &bindings);
+        assert_eq!(input.take_resolved(), vec![ResolvedAction::started(BindingActions::pause())]);
+        input.push(InputContext::new(InputContextKind::PauseMenu));
+        input.release(key(KeyCode::Escape), &bindings);
+        input.take_resolved();
+
+        // ...and again in the pause menu, through the menu's own bindings.
+        input.press(key(KeyCode::Escape), &bindings);
+        assert_eq!(input.take_resolved(), vec![ResolvedAction::started(BindingActions::pause())]);
+        input.release(key(KeyCode::Escape), &bindings);
+        input.take_resolved();
+
+        // The on-screen pause button unpauses too.
+        let button = BindingSources::Touch(touch_input::TouchInputSources::Button(1));
+        input.press(button, &bindings);
+        assert_eq!(input.take_resolved(), vec![ResolvedAction::started(BindingActions::pause())]);
+
+    }
+
+    #[test]
+    fn test_inputsystem_death_respawn() {
+
+        let mut bindings = gameplay_bindings();
+        bindings.insert(key(KeyCode::Space).into(), Binding::held(BindingActions::jump()));
+        let mut input = InputSystem::default();
+        input.set_focused(true);
+
+        // Dying takes input away from gameplay, and Space respawns instead of jumping.
+        input.push(InputContext::new(InputContextKind::Death));
+        assert!(!input.gameplay_active());
+        input.press(key(KeyCode::Space), &bindings);
+        assert!(input.is_active(&BindingActions::respawn()));
+        assert!(!input.is_active(&BindingActions::jump()));
+
+        // Respawning hands input back, without the held key jumping.
+        assert_eq!(input.remove(InputContextKind::Death).map(|context| context.kind), Some(InputContextKind::Death));
+        assert!(input.gameplay_active());
+        assert!(!input.is_active(&BindingActions::jump()));
+
+    }
+
+    #[test]
+    fn test_inputsystem_passthrough() {
+
+        let bindings = gameplay_bindings();
+        let forward = BindingActions::linear(LinearDirectionSetting::forward());
+        let mut input = InputSystem::default();
+
+        // A passthrough context consumes only what it binds.
+        input.push(InputContext::new(InputContextKind::DeveloperConsole).with_passthrough(true));
+        input.press(key(KeyCode::KeyW), &bindings);
+        assert!(input.is_active(&forward));
+        input.press(key(KeyCode::Escape), &bindings);
+        assert_eq!(input.take_resolved(), vec![
+            ResolvedAction::started(forward.clone()),
+            ResolvedAction::started(BindingActions::cancel())
+        ]);
+
+        // Text entry consumes everything.
+        input.push(InputContext::new(InputContextKind::TextEntry));
+        input.take_resolved();
+        input.press(key(KeyCode::KeyA), &bindings);
+        assert!(input.take_resolved().is_empty());
+
+    }
+
+    #[test]
+    fn test_inputsystem_scroll() {
+
+        let mut bindings = Bindings::new();
+        let scroll = |direction| BindingSources::Desktop(DesktopInputSources::MouseScroll(direction));
+        bindings.insert(scroll(MouseScrollDirection::Up).into(), Binding::held(BindingActions::interact()));
+        bindings.insert(scroll(MouseScrollDirection::Right).into(), Binding::held(BindingActions::pause()));
+        let mut input = InputSystem::default();
+
+        // A wheel notch presses and releases its source.
+        input.scroll(MouseScrollDelta::LineDelta(0.0, 1.0), &bindings);
+        assert_eq!(input.take_resolved(), vec![
+            ResolvedAction::started(BindingActions::interact()),
+            ResolvedAction::ended(BindingActions::interact())
+        ]);
+
+        // Touchpad pixels add up to whole lines, horizontally too.
+        let pixels = |x, y| MouseScrollDelta::PixelDelta(PhysicalPosition::new(x, y));
+        let line = abstractions::SCROLL_PIXELS_PER_LINE as f64;
+        input.scroll(pixels(line * 0.6, 0.0), &bindings);
+        assert!(input.take_resolved().is_empty());
+        input.scroll(pixels(line * 0.6, 0.0), &bindings);
+        assert_eq!(input.take_resolved().len(), 2);
+
+        // Reversing direction starts over.
+        input.scroll(pixels(0.0, line * 0.6), &bindings);
+        input.scroll(pixels(0.0, -line * 0.2), &bindings);
+        input.scroll(pixels(0.0, line * 0.6), &bindings);
+        assert!(input.take_resolved().is_empty());
+
+        // Magnitude over the last tick is an analog value of the bound action.
+        assert_eq!(input.scroll_value(&BindingActions::interact(), &bindings), 0.0);
+        input.update(0.1, &bindings);
+        assert!((input.scroll_value(&BindingActions::interact(), &bindings) - 2.2).abs() < 1e-4);
+        assert!((input.scroll_value(&BindingActions::pause(), &bindings) - 1.2).abs() < 1e-4);
+        input.update(0.1, &bindings);
+        assert_eq!(input.scroll_value(&BindingActions::interact(), &bindings), 0.0);
+
+    }
+
+    #[test]
+    fn test_inputsystem_rebind_capture() {
+
+        let bindings = gameplay_bindings();
+        let mut input = InputSystem::default();
+        input.push(InputContext::new(InputContextKind::RebindCapture));
+
+        // Modifiers are held rather than captured; the next source completes the chord and ends the capture.
+        input.press(key(KeyCode::ShiftLeft), &bindings);
+        assert_eq!(input.take_captured(), None);
+        input.press(key(KeyCode::KeyW), &bindings);
+        assert_eq!(input.take_captured(), Some(BindingChord::new(ChordModifiers::shift(), key(KeyCode::KeyW))));
+        assert_eq!(input.top(), InputContextKind::Gameplay);
+
+        // Escape cancels a capture instead of being captured.
+        input.push(InputContext::new(InputContextKind::RebindCapture));
+        input.press(key(KeyCode::Escape), &bindings);
+        assert_eq!(input.take_captured(), None);
+        assert_eq!(input.take_resolved(), vec![ResolvedAction::started(BindingActions::cancel())]);
+
+    }
+
+}
+
+
+/// Resolves input events into actions through a stack of input contexts.
+#[derive(Debug)]
+pub struct InputSystem {
+
+    // Seconds elapsed, advanced on every update tick.
+    time:           f32,
+
+    // Update ticks elapsed.
+    tick:           u64,
+
+    // Context stack; `Gameplay` is always at the bottom.
+    contexts:       Vec<InputContext>,
+
+    // Actions resolved and not yet taken.
+    resolved:       Vec<ResolvedAction>,
+
+    // Whether the game window has focus.
+    focused:        bool,
+
+    // Chord captured by a `RebindCapture` context and not yet taken.
+    captured:       Option<BindingChord>,
+
+    // Cursor grab state last reported by `cursor_change()`.
+    cursor_grabbed: Option<bool>,
+
+    // Lines scrolled in each direction since the last update tick.
+    scroll_pending: HashMap<MouseScrollDirection, f32>,
+
+    // Lines scrolled in each direction over the last update tick.
+    scroll_tick:    HashMap<MouseScrollDirection, f32>,
+
+    // Lines scrolled in each direction towards the next whole line.
+    scroll_notches: HashMap<MouseScrollDirection, f32>,
+
+    // Raw mouse motion since motion was last taken, in counts.
+    mouse_pending:  (f32, f32),
+
+    // Gamepad slot assignment and the active device.
+    devices:        DeviceAssignments,
+
+    // Family of each gamepad, for labelling its buttons.
+    families:       HashMap<GamepadId, ControllerFamily>,
+
+    // Labels of keys on the player's keyboard layout.
+    key_labels:     KeyLabels,
+
+    // On-screen touch controls.
+    touch:          TouchControls,
+
+    // Device changes not yet taken.
+    device_changes: Vec<DeviceChange>,
+
+    // Recording of the actions taken, if recording.
+    recorder:       Option<InputRecorder>,
+
+    // Recording replayed in place of live input, if replaying.
+    replay:         Option<InputReplay>,
+
+}
+
+impl InputSystem {
+
+    /// Advance time by `dt` seconds and evaluate time-driven triggers. Call this once per update tick.
+    ///
+    /// While replaying, this resolves the actions recorded for the new tick instead.
+    pub fn update(&mut self, dt: f32, bindings: &Bindings) {
+
+        self.time += dt;
+        self.tick += 1;
+        self.scroll_tick = std::mem::take(&mut self.scroll_pending);
+
+        if let Some(replay) = &mut self.replay {
+            self.resolved.extend(replay.advance(self.tick));
+            if replay.is_finished() {
+                info!("Input replay finished at tick {}", self.tick);
+                self.replay = None;
+            }
+            return;
+        }
+
+        for context in &mut self.contexts {
+            let context_bindings = context.bindings.as_ref().unwrap_or(bindings);
+            self.resolved.extend(context.triggers.update(context_bindings, self.time));
+        }
+
+    }
+
+    /// Feed a window or device event.
+    pub fn on_os_event(&mut self, event: &Event<()>, bindings: &Bindings) {
+
+        if let Event::WindowEvent { event, .. } = event {
+            match event {
+
+                WindowEvent::KeyboardInput { event, .. } => {
+                    self.use_device(InputDevice::KeyboardMouse);
+                    if event.state == ElementState::Pressed && self.modifiers().count() == 0 {
+                        self.key_labels.learn(event.physical_key, &event.logical_key);
+                    }
+                    let source = BindingSources::Desktop(DesktopInputSources::Key(event.physical_key));
+                    match event.state {
+                        ElementState::Pressed   => self.press(source, bindings),
+                        ElementState::Released  => self.release(source, bindings),
+                    }
+                },
+
+                WindowEvent::MouseInput { state, button, .. } => {
+                    self.use_device(InputDevice::KeyboardMouse);
+                    let source = BindingSources::Desktop(DesktopInputSources::MouseButton(*button));
+                    match state {
+                        ElementState::Pressed   => self.press(source, bindings),
+                        ElementState::Released  => self.release(source, bindings),
+                    }
+                },
+
+                WindowEvent::MouseWheel { delta, .. } => self.scroll(*delta, bindings),
+
+                WindowEvent::Focused(focused) => self.set_focused(*focused),
+
+                WindowEvent::Resized(size) => self.touch.resize(size.width as f32, size.height as f32),
+
+                _ => {}
+
+            }
+        }
+
+        if let Event::DeviceEvent { event: DeviceEvent::MouseMotion { delta }, .. } = event {
+            self.mouse_motion(*delta);
+        }
+
+    }
+
+    /// Move the mouse by raw `delta` counts. Moving it counts as using it; jitter doesn't.
+    ///
+    /// Motion only looks around while gameplay has input, and is ignored while replaying; see `take_motion()`.
+    pub fn mouse_motion(&mut self, delta: (f64, f64)) {
+        if delta.0.abs() + delta.1.abs() >= MOUSE_USE_THRESHOLD {
+            self.use_device(InputDevice::KeyboardMouse);
+        }
+        if self.replay.is_none() && self.gameplay_active() {
+            self.mouse_pending.0 += delta.0 as f32;
+            self.mouse_pending.1 += delta.1 as f32;
+        }
+    }
+
+    /// Feed an event from `gamepad`.
+    ///
+    /// Connecting and disconnecting assign and release player slots; see `take_device_changes()`. Only the pad in the
+    /// first player's slot drives actions, switching the active device to it as it's used.
+    pub fn on_gamepad_event(&mut self, gamepad: GamepadId, event: &gilrs::EventType, bindings: &Bindings) {
+
+        match event {
+            gilrs::EventType::Connected     => {
+                self.device_changes.extend(self.devices.connect(gamepad));
+                return;
+            },
+            gilrs::EventType::Disconnected  => {
+                self.device_changes.extend(self.devices.disconnect(gamepad));
+                return;
+            },
+            _                               => {},
+        }
+
+        if self.devices.slot_of(gamepad) != Some(0) {
+            return;
+        }
+
+        match event {
+
+            gilrs::EventType::ButtonPressed(button, _)  => {
+                self.use_device(InputDevice::Gamepad(gamepad));
+                self.press(BindingSources::Gamepad(GamepadInputSources::Button(*button)), bindings)
+            },
+
+            gilrs::EventType::ButtonReleased(button, _) => {
+                self.release(BindingSources::Gamepad(GamepadInputSources::Button(*button)), bindings)
+            },
+
+            // Each half of an axis is pressed while pushed beyond the threshold in its direction.
+            gilrs::EventType::AxisChanged(axis, value, _) => {
+                for (direction, pushed) in [
+                    (AxisDirection::Positive, *value >= AXIS_PRESS_THRESHOLD),
+                    (AxisDirection::Negative, *value <= -AXIS_PRESS_THRESHOLD),
+                ] {
+                    let source = BindingSources::Gamepad(GamepadInputSources::Axis(*axis, direction));
+                    if pushed {
+                        self.use_device(InputDevice::Gamepad(gamepad));
+                        self.press(source, bindings);
+                    }
+                    else { self.release(source, bindings) }
+                }
+            },
+
+            _ => {}
+
+        }
+
+    }
+
+    /// Feed a touch event, laid out on screen according to the player's touch `settings`.
+    ///
+    /// Fingers on on-screen buttons and the virtual stick press and release their `TouchInputSources`; swipes to
+    /// look and the stick's position are taken with `take_motion()`.
+    pub fn on_touch(&mut self, touch: &Touch, settings: &TouchInput, bindings: &Bindings) {
+
+        let position = (touch.location.x as f32, touch.location.y as f32);
+        for change in self.touch.on_touch(touch.id, touch.phase, position, settings) {
+            match change {
+                TouchChange::Pressed(source)    => self.press(BindingSources::Touch(source), bindings),
+                TouchChange::Released(source)   => self.release(BindingSources::Touch(source), bindings),
+            }
+        }
+
+    }
+
+    /// Take the look and stick motion for this update tick: mouse motion and swipes to look since the last call, and
+    /// where the virtual stick is. While recording, it's recorded as taken at the current tick; while replaying, the
+    /// motion recorded for the tick is taken instead, and live motion dropped.
+    pub fn take_motion(&mut self, settings: &TouchInput) -> TickMotion {
+
+        let motion = TickMotion {
+            mouse:  std::mem::take(&mut self.mouse_pending),
+            swipe:  self.touch.take_look(settings),
+            stick:  self.touch.stick(settings),
+        };
+
+        if let Some(replay) = &mut self.replay {
+            return replay.motion(self.tick);
+        }
+        if let Some(recorder) = &mut self.recorder {
+            recorder.record_motion(self.tick, motion);
+        }
+        motion
+
+    }
+
+    /// Press `source` now.
+    ///
+    /// The press travels down from the top of the stack until a context consumes it: a context consumes presses it
+    /// has a binding for, and every press unless it's a passthrough context. A `RebindCapture` context captures any
+    /// unbound press and then removes itself; see `take_captured()`. Ignored while replaying.
+    pub fn press(&mut self, source: BindingSources, bindings: &Bindings) {
+
+        if self.replay.is_some() {
+            return;
+        }
+
+        let mut captured = false;
+
+        for context in self.contexts.iter_mut().rev() {
+
+            let context_bindings = context.bindings.as_ref().unwrap_or(bindings);
+            let bound = context_bindings.keys().any(|chord| chord.source == source);
+
+            if context.kind == InputContextKind::RebindCapture && !bound {
+                // Modifiers are held until the rest of the chord is pressed.
+                if ChordModifiers::from_held([&source]).count() > 0 {
+                    context.triggers.press(context_bindings, source, self.time);
+                } else {
+                    self.captured = Some(BindingChord::new(context.triggers.modifiers(), source));
+                    captured = true;
+                }
+                break;
+            }
+
+            if bound || !context.passthrough {
+                self.resolved.extend(context.triggers.press(context_bindings, source, self.time));
+                break;
+            }
+
+        }
+
+        if captured {
+            self.pop();
+        }
+
+    }
+
+    /// Release `source` now, in whichever contexts hold it. Ignored while replaying.
+    pub fn release(&mut self, source: BindingSources, bindings: &Bindings) {
+        if self.replay.is_some() {
+            return;
+        }
+        for context in &mut self.contexts {
+            let context_bindings = context.bindings.as_ref().unwrap_or(bindings);
+            self.resolved.extend(context.triggers.release(context_bindings, source, self.time));
+        }
+    }
+
+    /// Scroll the mouse wheel or touchpad by `delta`.
+    ///
+    /// Every whole line scrolled in a direction presses and releases its `MouseScroll` source once, so fine touchpad
+    /// scrolling adds up to the same presses as wheel notches; reversing direction starts the count over. The amount
+    /// scrolled is also available as an analog value through `scroll_value()`.
+    pub fn scroll(&mut self, delta: MouseScrollDelta, bindings: &Bindings) {
+
+        self.use_device(InputDevice::KeyboardMouse);
+
+        for (direction, lines) in abstractions::scroll_amounts(delta) {
+
+            *self.scroll_pending.entry(direction).or_default() += lines;
+
+            self.scroll_notches.remove(&direction.opposite());
+            let notches = self.scroll_notches.entry(direction).or_default();
+            *notches += lines;
+            let whole = notches.floor();
+            *notches -= whole;
+
+            let source = BindingSources::Desktop(DesktopInputSources::MouseScroll(direction));
+            for _ in 0..whole as u32 {
+                self.press(source, bindings);
+                self.release(source, bindings);
+            }
+
+        }
+
+    }
+
+    /// Lines scrolled over the last update tick through scroll sources bound to `action` in the topmost context, for
+    /// analog actions like zooming. Sources chorded with modifiers count only while those modifiers are held.
+    pub fn scroll_value(&self, action: &BindingActions, bindings: &Bindings) -> f32 {
+
+        let Some(context) = self.contexts.last() else { return 0.0 };
+        let held = context.triggers.modifiers();
+
+        context.bindings(bindings).iter()
+            .filter(|(chord, binding)| binding.action == *action && held.contains(&chord.modifiers))
+            .filter_map(|(chord, _)| match chord.source {
+                BindingSources::Desktop(DesktopInputSources::MouseScroll(direction)) => self.scroll_tick.get(&direction),
+                _ => None,
+            })
+            .sum()
+
+    }
+
+    /// Release all sources and end all actions, in every context.
+    pub fn clear(&mut self) {
+        for context in &mut self.contexts {
+            self.resolved.extend(context.triggers.clear());
+        }
+    }
+
+    /// Push `context` onto the stack.
+    ///
+    /// Unless it passes events through, the contexts beneath are cleared, so actions held there (like walking) end.
+    pub fn push(&mut self, context: InputContext) {
+        if !context.passthrough {
+            self.clear();
+        }
+        self.contexts.push(context);
+    }
+
+    /// Pop the topmost context, ending its actions. `Gameplay` at the bottom is never popped.
+    pub fn pop(&mut self) -> Option<InputContext> {
+        if self.contexts.len() <= 1 {
+            return None;
+        }
+        let mut context = self.contexts.pop()?;
+        self.resolved.extend(context.triggers.clear());
+        Some(context)
+    }
+
+    /// Remove the topmost context of `kind`, ending its actions. `Gameplay` is never removed.
+    pub fn remove(&mut self, kind: InputContextKind) -> Option<InputContext> {
+        let index = self.contexts.iter().rposition(|context| context.kind == kind).filter(|index| *index > 0)?;
+        let mut context = self.contexts.remove(index);
+        self.resolved.extend(context.triggers.clear());
+        Some(context)
+    }
+
+    /// Kind of the topmost context.
+    pub fn top(&self) -> InputContextKind {
+        self.contexts.last().map(|context| context.kind).unwrap_or(InputContextKind::Gameplay)
+    }
+
+    /// Returns `true` if a context of `kind` is on the stack.
+    pub fn contains(&self, kind: InputContextKind) -> bool {
+        self.contexts.iter().any(|context| context.kind == kind)
+    }
+
+    /// Returns `true` while `action` is active in any context, or in the replay while replaying.
+    pub fn is_active(&self, action: &BindingActions) -> bool {
+        if let Some(replay) = &self.replay {
+            return replay.is_active(action);
+        }
+        self.contexts.iter().any(|context| context.triggers.is_active(action))
+    }
+
+    /// Returns `true` while the window has focus (or a replay is running) and gameplay is the topmost context.
+    pub fn gameplay_active(&self) -> bool {
+        (self.focused || self.replay.is_some()) && self.top() == InputContextKind::Gameplay
+    }
+
+    /// Set whether the game window has focus. Losing focus releases everything, as releases that happen elsewhere
+    /// never reach us.
+    pub fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+        if !focused {
+            self.touch.clear();
+            self.clear();
+        }
+    }
+
+    /// Returns `true` if the cursor should be hidden and held inside the window.
+    pub fn grabs_cursor(&self) -> bool {
+        self.focused && self.top().grabs_cursor()
+    }
+
+    /// Returns `Some(grab)` when `grabs_cursor()` changed since the last call, and `None` otherwise.
+    pub fn cursor_change(&mut self) -> Option<bool> {
+        let grab = self.grabs_cursor();
+        if self.cursor_grabbed == Some(grab) {
+            return None;
+        }
+        self.cursor_grabbed = Some(grab);
+        Some(grab)
+    }
+
+    /// Take the actions resolved since the last call. While recording, they're recorded as taken at the current tick.
+    pub fn take_resolved(&mut self) -> Vec<ResolvedAction> {
+        let resolved = std::mem::take(&mut self.resolved);
+        if let Some(recorder) = &mut self.recorder {
+            for action in &resolved {
+                recorder.record(self.tick, action);
+            }
+        }
+        resolved
+    }
+
+    /// Note `gamepad` as connected, such as those found at startup.
+    pub fn connect_gamepad(&mut self, gamepad: GamepadId) {
+        self.device_changes.extend(self.devices.connect(gamepad));
+    }
+
+    // Note the first player using `device`.
+    fn use_device(&mut self, device: InputDevice) {
+        self.device_changes.extend(self.devices.use_device(device));
+    }
+
+    /// Gamepad slot assignment and the active device.
+    pub fn devices(&self) -> &DeviceAssignments {
+        &self.devices
+    }
+
+    /// Set the controller family of `gamepad`, to label its buttons.
+    pub fn set_gamepad_family(&mut self, gamepad: GamepadId, family: ControllerFamily) {
+        self.families.insert(gamepad, family);
+    }
+
+    /// Controller family of the active gamepad, or `None` while the player is using keyboard and mouse.
+    pub fn controller_family(&self) -> Option<ControllerFamily> {
+        match self.devices.active() {
+            InputDevice::Gamepad(gamepad)   => Some(self.families.get(&gamepad).copied().unwrap_or_default()),
+            InputDevice::KeyboardMouse      => None,
+        }
+    }
+
+    /// Label of the chord bound to `action` in `bindings` for the active device, for prompts like "Press E to
+    /// interact". See `prompts::action_label()`.
+    pub fn action_label(&self, action: &BindingActions, bindings: &Bindings) -> Option<String> {
+        prompts::action_label(bindings, action, self.controller_family(), &self.key_labels)
+    }
+
+    // Modifiers held in the topmost context.
+    fn modifiers(&self) -> ChordModifiers {
+        self.contexts.last().map(|context| context.triggers.modifiers()).unwrap_or_default()
+    }
+
+    /// Take the device changes since the last call.
+    pub fn take_device_changes(&mut self) -> Vec<DeviceChange> {
+        std::mem::take(&mut self.device_changes)
+    }
+
+    /// Take the chord captured by a `RebindCapture` context, if any.
+    pub fn take_captured(&mut self) -> Option<BindingChord> {
+        self.captured.take()
+    }
+
+    /// Seconds elapsed across all update ticks.
+    pub fn time(&self) -> f32 {
+        self.time
+    }
+
+    /// Update ticks elapsed.
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    /// Start recording the actions taken, to be written to `path` by `save_recording()`.
+    pub fn start_recording(&mut self, path: PathBuf) {
+        info!("Recording input to {}", path.display());
+        self.recorder = Some(InputRecorder::new(path));
+    }
+
+    /// Stop recording, returning the recording made so far.
+    pub fn stop_recording(&mut self) -> Option<InputRecording> {
+        self.recorder.take().map(|recorder| recorder.recording)
+    }
+
+    /// Stop recording and write the recording to the path it was started with. Does nothing if not recording.
+    pub fn save_recording(&mut self) -> io::Result<()> {
+        match self.recorder.take() {
+            Some(recorder)  => recorder.recording.save(&recorder.path),
+            None            => Ok(()),
+        }
+    }
+
+    /// Replay `recording` in place of live input, from the next update tick on.
+    pub fn start_replay(&mut self, recording: InputRecording) {
+        self.clear();
+        self.replay = Some(InputReplay::new(recording));
+    }
+
+    /// Returns `true` while a recording is being replayed.
+    pub fn is_replaying(&self) -> bool {
+        self.replay.is_some()
+    }
+
+}
+
+impl Default for InputSystem {
+    fn default() -> Self {
+        InputSystem {
+            time:           0.0,
+            tick:           0,
+            contexts:       vec![InputContext::new(InputContextKind::Gameplay)],
+            resolved:       Vec::new(),
+            focused:        false,
+            captured:       None,
+            cursor_grabbed: None,
+            scroll_pending: HashMap::new(),
+            mouse_pending:  (0.0, 0.0),
+            scroll_tick:    HashMap::new(),
+            scroll_notches: HashMap::new(),
+            devices:        DeviceAssignments::default(),
+            families:       HashMap::new(),
+            key_labels:     KeyLabels::default(),
+            touch:          TouchControls::default(),
+            device_changes: Vec::new(),
+            recorder:       None,
+            replay:         None,
+        }
+    }
+}
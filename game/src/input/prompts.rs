@@ -0,0 +1,442 @@
+//!
+//! Input prompts.
+//!
+//! Resolves a `BindingActions` value to the label a prompt like "Press E to interact" should show: the key as it's
+//! printed on the player's keyboard layout, a mouse button, or a gamepad button named for the player's controller
+//! family. Labels follow the active device, preferring bindings on the device the player is using.
+//!
+
+use std::collections::HashMap;
+use fyrox:: { event::MouseButton, keyboard:: { Key, KeyCode, PhysicalKey } };
+use crate::settings::input:: {
+    BindingActions,
+    BindingChord,
+    Bindings,
+    BindingSources,
+    desktop_input:: { DesktopInputSources, abstractions::MouseScrollDirection },
+    gamepad_input:: { AxisDirection, GamepadInputSources },
+    touch_input:: { StickDirection, TouchInputSources },
+};
+use super::InputSystem;
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::settings::input:: { Binding, ChordModifiers, LinearDirectionSetting };
+
+    fn key(code: KeyCode) -> BindingSources {
+        BindingSources::Desktop(DesktopInputSources::Key(PhysicalKey::Code(code)))
+    }
+
+    fn pad(button: gilrs::Button) -> BindingSources {
+        BindingSources::Gamepad(GamepadInputSources::Button(button))
+    }
+
+    #[test]
+    fn test_prompts_controller_family() {
+
+        assert_eq!(ControllerFamily::detect(Some(0x045e), "Controller"), ControllerFamily::Xbox);
+        assert_eq!(ControllerFamily::detect(Some(0x054c), "Wireless Controller"), ControllerFamily::PlayStation);
+        assert_eq!(ControllerFamily::detect(None, "Pro Controller (Nintendo Switch)"), ControllerFamily::Nintendo);
+        assert_eq!(ControllerFamily::detect(None, "DualSense Wireless Controller"), ControllerFamily::PlayStation);
+        assert_eq!(ControllerFamily::detect(Some(0x1234), "USB Gamepad"), ControllerFamily::Generic);
+
+        // The same physical button has a different name on each family.
+        assert_eq!(ControllerFamily::Xbox.button_label(gilrs::Button::South), "A");
+        assert_eq!(ControllerFamily::PlayStation.button_label(gilrs::Button::South), "Cross");
+        assert_eq!(ControllerFamily::Nintendo.button_label(gilrs::Button::South), "B");
+        assert_eq!(ControllerFamily::Generic.button_label(gilrs::Button::South), "South");
+
+    }
+
+    #[test]
+    fn test_prompts_key_labels() {
+
+        let mut keys = KeyLabels::default();
+        assert_eq!(keys.label(PhysicalKey::Code(KeyCode::KeyW)), "W");
+        assert_eq!(keys.label(PhysicalKey::Code(KeyCode::Escape)), "Esc");
+        assert_eq!(keys.label(PhysicalKey::Code(KeyCode::F5)), "F5");
+
+        // Keys are labelled as printed on the player's layout once pressed, e.g. `KeyW` on AZERTY.
+        keys.learn(PhysicalKey::Code(KeyCode::KeyW), &Key::Character("z".into()));
+        assert_eq!(keys.label(PhysicalKey::Code(KeyCode::KeyW)), "Z");
+
+        // Named keys and whitespace keep their fixed labels.
+        keys.learn(PhysicalKey::Code(KeyCode::Space), &Key::Character(" ".into()));
+        assert_eq!(keys.label(PhysicalKey::Code(KeyCode::Space)), "Space");
+
+    }
+
+    #[test]
+    fn test_prompts_action_label() {
+
+        let keys = KeyLabels::default();
+        let forward = BindingActions::linear(LinearDirectionSetting::forward());
+        let mut bindings = Bindings::new();
+        bindings.insert(key(KeyCode::KeyE).into(), Binding::held(BindingActions::interact()));
+        bindings.insert(
+            BindingChord::new(ChordModifiers::shift(), key(KeyCode::KeyF)),
+            Binding::held(BindingActions::interact())
+        );
+        bindings.insert(pad(gilrs::Button::West).into(), Binding::held(BindingActions::interact()));
+        bindings.insert(key(KeyCode::KeyW).into(), Binding::held(forward.clone()));
+
+        // Keyboard and mouse prefer the simplest desktop binding; gamepads their own buttons.
+        assert_eq!(action_label(&bindings, &BindingActions::interact(), None, &keys).as_deref(), Some("E"));
+        assert_eq!(
+            action_label(&bindings, &BindingActions::interact(), Some(ControllerFamily::Xbox), &keys).as_deref(),
+            Some("X")
+        );
+        assert_eq!(
+            action_label(&bindings, &BindingActions::interact(), Some(ControllerFamily::PlayStation), &keys).as_deref(),
+            Some("Square")
+        );
+
+        // Without a binding on the active device, another device's binding beats nothing at all.
+        assert_eq!(action_label(&bindings, &forward, Some(ControllerFamily::Xbox), &keys).as_deref(), Some("W"));
+        assert_eq!(action_label(&bindings, &BindingActions::pause(), None, &keys), None);
+
+        // Chords name their modifiers.
+        let chord = BindingChord::new(ChordModifiers::shift(), key(KeyCode::KeyF));
+        assert_eq!(chord_label(&chord, None, &keys), "Shift + F");
+
+    }
+
+    #[test]
+    fn test_prompts_label_refresh() {
+
+        let mut bindings = Bindings::new();
+        bindings.insert(key(KeyCode::KeyE).into(), Binding::held(BindingActions::interact()));
+        let input = InputSystem::default();
+        let mut prompt = PromptLabel::new(BindingActions::interact());
+
+        // Reports a change once, until the bindings change.
+        assert!(prompt.refresh(&input, &bindings));
+        assert_eq!(prompt.label(), Some("E"));
+        assert!(!prompt.refresh(&input, &bindings));
+
+        bindings.clear();
+        bindings.insert(key(KeyCode::KeyR).into(), Binding::held(BindingActions::interact()));
+        assert!(prompt.refresh(&input, &bindings));
+        assert_eq!(prompt.label(), Some("R"));
+
+    }
+
+}
+
+
+/// Families of gamepad, which print different names on the same buttons.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ControllerFamily {
+    Xbox,
+    PlayStation,
+    Nintendo,
+    #[default]
+    Generic,
+}
+
+impl ControllerFamily {
+
+    /// Guess the family of a gamepad from its USB `vendor_id` and `name`.
+    pub fn detect(vendor_id: Option<u16>, name: &str) -> Self {
+
+        match vendor_id {
+            Some(0x045e)    => return ControllerFamily::Xbox,           // Microsoft
+            Some(0x054c)    => return ControllerFamily::PlayStation,    // Sony
+            Some(0x057e)    => return ControllerFamily::Nintendo,       // Nintendo
+            _               => {},
+        }
+
+        let name = name.to_lowercase();
+        if name.contains("xbox") || name.contains("xinput") {
+            ControllerFamily::Xbox
+        }
+        else if ["playstation", "dualshock", "dualsense", "ps4", "ps5"].iter().any(|each| name.contains(each)) {
+            ControllerFamily::PlayStation
+        }
+        else if ["nintendo", "switch", "joy-con"].iter().any(|each| name.contains(each)) {
+            ControllerFamily::Nintendo
+        }
+        else {
+            ControllerFamily::Generic
+        }
+
+    }
+
+    /// Name printed on `button` by this family.
+    pub fn button_label(&self, button: gilrs::Button) -> &'static str {
+
+        use gilrs::Button;
+        use ControllerFamily::*;
+
+        match (button, self) {
+            (Button::South, Xbox)           => "A",
+            (Button::South, PlayStation)    => "Cross",
+            (Button::South, Nintendo)       => "B",
+            (Button::East, Xbox)            => "B",
+            (Button::East, PlayStation)     => "Circle",
+            (Button::East, Nintendo)        => "A",
+            (Button::North, Xbox)           => "Y",
+            (Button::North, PlayStation)    => "Triangle",
+            (Button::North, Nintendo)       => "X",
+            (Button::West, Xbox)            => "X",
+            (Button::West, PlayStation)     => "Square",
+            (Button::West, Nintendo)        => "Y",
+            (Button::LeftTrigger, Xbox)     => "LB",
+            (Button::LeftTrigger, PlayStation) => "L1",
+            (Button::LeftTrigger, Nintendo) => "L",
+            (Button::LeftTrigger2, Xbox)    => "LT",
+            (Button::LeftTrigger2, PlayStation) => "L2",
+            (Button::LeftTrigger2, Nintendo) => "ZL",
+            (Button::RightTrigger, Xbox)    => "RB",
+            (Button::RightTrigger, PlayStation) => "R1",
+            (Button::RightTrigger, Nintendo) => "R",
+            (Button::RightTrigger2, Xbox)   => "RT",
+            (Button::RightTrigger2, PlayStation) => "R2",
+            (Button::RightTrigger2, Nintendo) => "ZR",
+            (Button::Select, Xbox)          => "View",
+            (Button::Select, PlayStation)   => "Share",
+            (Button::Select, Nintendo)      => "-",
+            (Button::Start, Xbox)           => "Menu",
+            (Button::Start, PlayStation)    => "Options",
+            (Button::Start, Nintendo)       => "+",
+            (Button::Mode, Xbox)            => "Xbox",
+            (Button::Mode, PlayStation)     => "PS",
+            (Button::Mode, Nintendo)        => "Home",
+            (Button::LeftThumb, Xbox)       => "LS",
+            (Button::LeftThumb, PlayStation) => "L3",
+            (Button::RightThumb, Xbox)      => "RS",
+            (Button::RightThumb, PlayStation) => "R3",
+
+            (Button::South, Generic)        => "South",
+            (Button::East, Generic)         => "East",
+            (Button::North, Generic)        => "North",
+            (Button::West, Generic)         => "West",
+            (Button::LeftTrigger, Generic)  => "L1",
+            (Button::LeftTrigger2, Generic) => "L2",
+            (Button::RightTrigger, Generic) => "R1",
+            (Button::RightTrigger2, Generic) => "R2",
+            (Button::Select, Generic)       => "Select",
+            (Button::Start, Generic)        => "Start",
+            (Button::Mode, Generic)         => "Mode",
+            (Button::LeftThumb, _)          => "Left Stick",
+            (Button::RightThumb, _)         => "Right Stick",
+            (Button::C, _)                  => "C",
+            (Button::Z, _)                  => "Z",
+            (Button::DPadUp, _)             => "D-Pad Up",
+            (Button::DPadDown, _)           => "D-Pad Down",
+            (Button::DPadLeft, _)           => "D-Pad Left",
+            (Button::DPadRight, _)          => "D-Pad Right",
+            (Button::Unknown, _)            => "?",
+        }
+
+    }
+
+}
+
+/// Labels of keys as printed on the player's keyboard layout.
+///
+/// Layouts are learned from the characters keys type as they're pressed; keys not pressed yet are labelled as on a
+/// US QWERTY keyboard.
+#[derive(Debug, Default)]
+pub struct KeyLabels {
+    learned:    HashMap<PhysicalKey, String>,
+}
+
+impl KeyLabels {
+
+    /// Learn that `physical` types `logical` on the player's layout, without modifiers held.
+    pub fn learn(&mut self, physical: PhysicalKey, logical: &Key) {
+        if let Key::Character(text) = logical {
+            if !text.trim().is_empty() {
+                self.learned.insert(physical, text.to_uppercase());
+            }
+        }
+    }
+
+    /// Label of `physical`.
+    pub fn label(&self, physical: PhysicalKey) -> String {
+
+        if let Some(label) = self.learned.get(&physical) {
+            return label.clone();
+        }
+
+        let PhysicalKey::Code(code) = physical else { return String::from("?") };
+        let label = match code {
+            KeyCode::Escape         => "Esc",
+            KeyCode::Space          => "Space",
+            KeyCode::Enter          => "Enter",
+            KeyCode::Tab            => "Tab",
+            KeyCode::Backspace      => "Backspace",
+            KeyCode::ShiftLeft      => "Left Shift",
+            KeyCode::ShiftRight     => "Right Shift",
+            KeyCode::ControlLeft    => "Left Ctrl",
+            KeyCode::ControlRight   => "Right Ctrl",
+            KeyCode::AltLeft        => "Left Alt",
+            KeyCode::AltRight       => "Right Alt",
+            KeyCode::ArrowUp        => "Up",
+            KeyCode::ArrowDown      => "Down",
+            KeyCode::ArrowLeft      => "Left",
+            KeyCode::ArrowRight     => "Right",
+            KeyCode::Backquote      => "`",
+            KeyCode::Minus          => "-",
+            KeyCode::Equal          => "=",
+            KeyCode::BracketLeft    => "[",
+            KeyCode::BracketRight   => "]",
+            KeyCode::Backslash      => "\\",
+            KeyCode::Semicolon      => ";",
+            KeyCode::Quote          => "'",
+            KeyCode::Comma          => ",",
+            KeyCode::Period         => ".",
+            KeyCode::Slash          => "/",
+            _                       => {
+                // Otherwise, the portable name without its family, like `W` or `F5`.
+                let source = BindingSources::Desktop(DesktopInputSources::Key(physical));
+                return source.portable_name()
+                    .map(|name| name.trim_start_matches("Key:").to_string())
+                    .unwrap_or_else(|_| String::from("?"));
+            },
+        };
+        String::from(label)
+
+    }
+
+}
+
+/// Label of `source`, naming gamepad buttons for `gamepad`'s family (or generically, if `None`).
+pub fn source_label(source: &BindingSources, gamepad: Option<ControllerFamily>, keys: &KeyLabels) -> String {
+
+    match source {
+
+        BindingSources::Desktop(DesktopInputSources::Key(physical))     => keys.label(*physical),
+
+        BindingSources::Desktop(DesktopInputSources::MouseButton(button)) => match button {
+            MouseButton::Left       => String::from("Left Mouse"),
+            MouseButton::Right      => String::from("Right Mouse"),
+            MouseButton::Middle     => String::from("Middle Mouse"),
+            MouseButton::Back       => String::from("Mouse Back"),
+            MouseButton::Forward    => String::from("Mouse Forward"),
+            MouseButton::Other(id)  => format!("Mouse {id}"),
+        },
+
+        BindingSources::Desktop(DesktopInputSources::MouseScroll(direction)) => String::from(match direction {
+            MouseScrollDirection::Up    => "Scroll Up",
+            MouseScrollDirection::Down  => "Scroll Down",
+            MouseScrollDirection::Left  => "Scroll Left",
+            MouseScrollDirection::Right => "Scroll Right",
+        }),
+
+        BindingSources::Gamepad(GamepadInputSources::Button(button))    => {
+            String::from(gamepad.unwrap_or_default().button_label(*button))
+        },
+
+        BindingSources::Gamepad(GamepadInputSources::Axis(axis, direction)) => {
+            let positive = *direction == AxisDirection::Positive;
+            String::from(match axis {
+                gilrs::Axis::LeftStickX     => if positive { "Left Stick Right" } else { "Left Stick Left" },
+                gilrs::Axis::LeftStickY     => if positive { "Left Stick Up" } else { "Left Stick Down" },
+                gilrs::Axis::RightStickX    => if positive { "Right Stick Right" } else { "Right Stick Left" },
+                gilrs::Axis::RightStickY    => if positive { "Right Stick Up" } else { "Right Stick Down" },
+                gilrs::Axis::DPadX          => if positive { "D-Pad Right" } else { "D-Pad Left" },
+                gilrs::Axis::DPadY          => if positive { "D-Pad Up" } else { "D-Pad Down" },
+                gilrs::Axis::LeftZ          => "Left Trigger",
+                gilrs::Axis::RightZ         => "Right Trigger",
+                gilrs::Axis::Unknown        => "?",
+            })
+        },
+
+        BindingSources::Touch(TouchInputSources::Stick(direction))      => String::from(match direction {
+            StickDirection::Up      => "Stick Up",
+            StickDirection::Down    => "Stick Down",
+            StickDirection::Left    => "Stick Left",
+            StickDirection::Right   => "Stick Right",
+        }),
+
+        // Buttons are numbered from one on screen.
+        BindingSources::Touch(TouchInputSources::Button(index))         => format!("Button {}", *index as u16 + 1),
+
+    }
+
+}
+
+/// Label of `chord`, like `Shift + F`.
+pub fn chord_label(chord: &BindingChord, gamepad: Option<ControllerFamily>, keys: &KeyLabels) -> String {
+
+    let modifiers = [
+        (chord.modifiers.shift, "Shift"),
+        (chord.modifiers.ctrl, "Ctrl"),
+        (chord.modifiers.alt, "Alt"),
+        (chord.modifiers.logo, "Super"),
+    ];
+
+    let mut parts: Vec<String> = modifiers.iter()
+        .filter(|(held, _)| *held)
+        .map(|(_, name)| String::from(*name))
+        .collect();
+    parts.push(source_label(&chord.source, gamepad, keys));
+    parts.join(" + ")
+
+}
+
+/// Label of the chord bound to `action` that best suits the active device: `gamepad`'s family, or keyboard and mouse
+/// if `None`.
+///
+/// Chords on the active device come first, then those with the fewest modifiers. Returns `None` if `action` is unbound.
+pub fn action_label(
+    bindings: &Bindings,
+    action: &BindingActions,
+    gamepad: Option<ControllerFamily>,
+    keys: &KeyLabels
+) -> Option<String> {
+
+    let on_active_device = |chord: &BindingChord| match chord.source {
+        BindingSources::Desktop(_)  => gamepad.is_none(),
+        BindingSources::Gamepad(_)  => gamepad.is_some(),
+        BindingSources::Touch(_)    => false,
+    };
+
+    bindings.iter()
+        .filter(|(_, binding)| binding.action == *action)
+        .map(|(chord, _)| chord)
+        // Portable names break ties, so the same bindings always pick the same label.
+        .min_by_key(|chord| (!on_active_device(chord), chord.modifiers.count(), chord.to_string()))
+        .map(|chord| chord_label(chord, gamepad, keys))
+
+}
+
+/// A prompt's label for an action, refreshed as bindings and the active device change.
+#[derive(Debug, Clone)]
+pub struct PromptLabel {
+
+    action:     BindingActions,
+
+    // Label last resolved; `None` until the first refresh.
+    label:      Option<Option<String>>,
+
+}
+
+impl PromptLabel {
+
+    pub fn new(action: BindingActions) -> Self {
+        PromptLabel { action, label: None }
+    }
+
+    /// Resolve the label again, given `bindings` and the active device of `input`. Returns `true` on the first call
+    /// and whenever the label changed, so the prompt should be redrawn.
+    pub fn refresh(&mut self, input: &InputSystem, bindings: &Bindings) -> bool {
+        let label = input.action_label(&self.action, bindings);
+        if self.label.as_ref() == Some(&label) {
+            return false;
+        }
+        self.label = Some(label);
+        true
+    }
+
+    /// Label of the action, or `None` if it's unbound or not resolved yet.
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_ref().and_then(|label| label.as_deref())
+    }
+
+}
@@ -0,0 +1,353 @@
+//!
+//! Input recording and replay.
+//!
+//! A recording is the stream of actions resolved by the `InputSystem`, each stamped with the update tick at which the
+//! game consumed it, along with the look and stick motion taken at each tick. Replaying a recording feeds the same
+//! actions and motion back at the same ticks in place of live input, so a bug report can carry an exact input trace
+//! and tests can replay one without a window.
+//!
+
+use std::{ fs, io, io::Write, path::{ Path, PathBuf } };
+use serde:: { Serialize, Deserialize };
+use tracing::info;
+use crate::settings::input::BindingActions;
+use super::triggers:: { ActionState, ResolvedAction };
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use fyrox::keyboard:: { KeyCode, PhysicalKey };
+    use crate::input:: { InputContext, InputContextKind, InputSystem };
+    use crate::settings::input:: {
+        Binding, Bindings, BindingSources, LinearDirectionSetting, desktop_input::DesktopInputSources,
+        touch_input::TouchInput,
+    };
+
+    fn key(code: KeyCode) -> BindingSources {
+        BindingSources::Desktop(DesktopInputSources::Key(PhysicalKey::Code(code)))
+    }
+
+    // Run a short session: walk, long-press interact, then pause. Returns resolved actions per tick.
+    fn session(input: &mut InputSystem) -> Vec<Vec<ResolvedAction>> {
+
+        let mut bindings = Bindings::new();
+        bindings.insert(key(KeyCode::KeyW).into(), Binding::held(BindingActions::linear(LinearDirectionSetting::forward())));
+        bindings.insert(key(KeyCode::KeyE).into(), Binding::long_press(BindingActions::interact(), 0.1));
+        bindings.insert(key(KeyCode::Escape).into(), Binding::held(BindingActions::pause()));
+
+        let mut ticks = vec![];
+        for tick in 0..10 {
+            match tick {
+                1 => input.press(key(KeyCode::KeyW), &bindings),
+                3 => input.press(key(KeyCode::KeyE), &bindings),
+                6 => input.release(key(KeyCode::KeyE), &bindings),
+                8 => input.press(key(KeyCode::Escape), &bindings),
+                _ => {}
+            }
+            input.update(0.05, &bindings);
+            let resolved = input.take_resolved();
+            // The game pauses on `Pause`, which ends walking.
+            if resolved.contains(&ResolvedAction::started(BindingActions::pause())) {
+                input.push(InputContext::new(InputContextKind::PauseMenu));
+            }
+            ticks.push(resolved);
+        }
+        ticks
+
+    }
+
+    #[test]
+    fn test_inputrecording_replay_matches_live() {
+
+        // Record a live session.
+        let mut live = InputSystem::default();
+        live.set_focused(true);
+        live.start_recording(PathBuf::from("unused.ron"));
+        let live_ticks = session(&mut live);
+        let recording = live.stop_recording().unwrap();
+        assert!(!recording.actions.is_empty());
+
+        // Written and read back as RON.
+        let buffer = recording.to_ron().unwrap();
+        let recording = InputRecording::from_ron(&buffer).unwrap();
+
+        // Replay ignores live input, resolving exactly what was recorded at the same ticks.
+        let mut replay = InputSystem::default();
+        replay.start_replay(recording);
+        let replay_ticks = session(&mut replay);
+        assert_eq!(replay_ticks, live_ticks);
+
+        // Replayed actions are active just as they were live.
+        assert!(!replay.is_active(&BindingActions::linear(LinearDirectionSetting::forward())));
+        assert_eq!(replay.top(), InputContextKind::PauseMenu);
+
+    }
+
+    #[test]
+    fn test_inputrecording_replays_motion() {
+
+        let settings = TouchInput::default();
+        let bindings = Bindings::new();
+
+        // Record mouse motion on every other tick...
+        let mut live = InputSystem::default();
+        live.set_focused(true);
+        live.start_recording(PathBuf::from("unused.ron"));
+        let mut live_motion = vec![];
+        for tick in 0..6 {
+            if tick % 2 == 1 {
+                live.mouse_motion((3.0, -1.0));
+            }
+            live.update(0.05, &bindings);
+            live_motion.push(live.take_motion(&settings));
+        }
+        let recording = live.stop_recording().unwrap();
+        assert_eq!(recording.motion.len(), 3);
+        let recording = InputRecording::from_ron(&recording.to_ron().unwrap()).unwrap();
+
+        // ...and replay it in place of live motion, which is ignored.
+        let mut replay = InputSystem::default();
+        replay.start_replay(recording);
+        let mut replay_motion = vec![];
+        for _ in 0..6 {
+            replay.mouse_motion((50.0, 50.0));
+            replay.update(0.05, &bindings);
+            replay_motion.push(replay.take_motion(&settings));
+        }
+        assert_eq!(replay_motion, live_motion);
+
+    }
+
+    #[test]
+    fn test_inputreplay_stamps() {
+
+        let recording = InputRecording {
+            version:    String::from("test"),
+            actions:    vec![
+                RecordedAction { tick: 2, action: BindingActions::interact(), state: ActionState::Started },
+                RecordedAction { tick: 4, action: BindingActions::interact(), state: ActionState::Ended },
+            ],
+            motion:     vec![
+                RecordedMotion { tick: 3, motion: TickMotion { mouse: (4.0, -2.0), ..Default::default() } },
+            ],
+        };
+        let mut replay = InputReplay::new(recording);
+
+        assert!(replay.advance(1).is_empty());
+        assert_eq!(replay.motion(1), TickMotion::default());
+        assert_eq!(replay.advance(2), vec![ResolvedAction::started(BindingActions::interact())]);
+        assert!(replay.is_active(&BindingActions::interact()));
+        assert!(!replay.is_finished());
+        assert_eq!(replay.motion(3).mouse, (4.0, -2.0));
+        assert_eq!(replay.advance(4), vec![ResolvedAction::ended(BindingActions::interact())]);
+        assert!(!replay.is_active(&BindingActions::interact()));
+        assert!(replay.is_finished());
+
+    }
+
+}
+
+
+/// A recorded stream of resolved actions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InputRecording {
+
+    /// Game version the recording was made with.
+    pub version:    String,
+
+    /// Actions in the order they were resolved.
+    pub actions:    Vec<RecordedAction>,
+
+    /// Motion taken at each tick there was any, in order.
+    #[serde(default)]
+    pub motion:     Vec<RecordedMotion>,
+
+}
+
+impl InputRecording {
+
+    pub fn new() -> Self {
+        InputRecording { version: String::from(crate::GAME_VERSION), actions: vec![], motion: vec![] }
+    }
+
+    /// Serialize into [Rusty Object Notation][1].
+    ///
+    /// [1]: https://docs.rs/ron/0.8.1/ron/index.html
+    pub fn to_ron(&self) -> io::Result<String> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(|why| io::Error::new(io::ErrorKind::InvalidData, why))
+    }
+
+    /// Deserialize from [Rusty Object Notation][1].
+    ///
+    /// [1]: https://docs.rs/ron/0.8.1/ron/index.html
+    pub fn from_ron(buffer: &str) -> io::Result<Self> {
+        ron::de::from_str(buffer)
+            .map_err(|why| io::Error::new(io::ErrorKind::InvalidData, why))
+    }
+
+    /// Write the recording to the file at `path`.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut file = fs::File::create(path)?;
+        file.write_all(self.to_ron()?.as_bytes())
+    }
+
+    /// Read a recording from the file at `path`.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        Self::from_ron(&fs::read_to_string(path)?)
+    }
+
+}
+
+impl Default for InputRecording {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An action resolved at an update tick.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedAction {
+
+    /// Update tick at which the game consumed the action.
+    pub tick:       u64,
+
+    pub action:     BindingActions,
+    pub state:      ActionState,
+
+}
+
+/// Look and stick motion taken for an update tick; see `InputSystem::take_motion()`.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct TickMotion {
+
+    /// Raw mouse motion, in counts.
+    pub mouse:      (f32, f32),
+
+    /// Swipe-to-look motion, as a `(yaw, pitch)` change in degrees.
+    pub swipe:      (f32, f32),
+
+    /// Position of the virtual stick, from `-1.0` to `1.0` on each axis with up and right positive.
+    pub stick:      (f32, f32),
+
+}
+
+/// Motion taken at an update tick.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedMotion {
+
+    /// Update tick at which the game took the motion.
+    pub tick:       u64,
+
+    pub motion:     TickMotion,
+
+}
+
+/// A recording in progress, and where to write it.
+#[derive(Debug)]
+pub struct InputRecorder {
+    pub path:       PathBuf,
+    pub recording:  InputRecording,
+}
+
+impl InputRecorder {
+
+    pub fn new(path: PathBuf) -> Self {
+        InputRecorder { path, recording: InputRecording::new() }
+    }
+
+    /// Record `resolved` as consumed at `tick`.
+    pub fn record(&mut self, tick: u64, resolved: &ResolvedAction) {
+        self.recording.actions.push(RecordedAction {
+            tick,
+            action: resolved.action.clone(),
+            state:  resolved.state,
+        });
+    }
+
+    /// Record `motion` as taken at `tick`. Ticks without any aren't recorded.
+    pub fn record_motion(&mut self, tick: u64, motion: TickMotion) {
+        if motion != TickMotion::default() {
+            self.recording.motion.push(RecordedMotion { tick, motion });
+        }
+    }
+
+}
+
+/// A recording being replayed.
+#[derive(Debug)]
+pub struct InputReplay {
+
+    recording:      InputRecording,
+
+    // Index of the next action to replay.
+    next:           usize,
+
+    // Index of the next motion to replay.
+    next_motion:    usize,
+
+    // Replayed actions that started and haven't ended.
+    active:         Vec<BindingActions>,
+
+}
+
+impl InputReplay {
+
+    pub fn new(recording: InputRecording) -> Self {
+        if recording.version != crate::GAME_VERSION {
+            info!("Replaying input recorded with version {} on version {}", recording.version, crate::GAME_VERSION);
+        }
+        InputReplay { recording, next: 0, next_motion: 0, active: vec![] }
+    }
+
+    /// Replay every action recorded up to and including `tick`.
+    pub fn advance(&mut self, tick: u64) -> Vec<ResolvedAction> {
+
+        let mut resolved = vec![];
+        while let Some(recorded) = self.recording.actions.get(self.next).filter(|recorded| recorded.tick <= tick) {
+
+            match recorded.state {
+                ActionState::Started    => self.active.push(recorded.action.clone()),
+                ActionState::Ended      => {
+                    if let Some(index) = self.active.iter().position(|active| active == &recorded.action) {
+                        self.active.swap_remove(index);
+                    }
+                },
+            }
+
+            resolved.push(ResolvedAction { action: recorded.action.clone(), state: recorded.state });
+            self.next += 1;
+
+        }
+        resolved
+
+    }
+
+    /// Motion recorded as taken at `tick`, or none if there was none. Motion recorded for earlier ticks is skipped.
+    pub fn motion(&mut self, tick: u64) -> TickMotion {
+        let motion = &self.recording.motion;
+        while motion.get(self.next_motion).is_some_and(|recorded| recorded.tick < tick) {
+            self.next_motion += 1;
+        }
+        match motion.get(self.next_motion).filter(|recorded| recorded.tick == tick) {
+            Some(recorded)  => {
+                self.next_motion += 1;
+                recorded.motion
+            },
+            None            => TickMotion::default(),
+        }
+    }
+
+    /// Returns `true` while a replayed `action` is active.
+    pub fn is_active(&self, action: &BindingActions) -> bool {
+        self.active.contains(action)
+    }
+
+    /// Returns `true` once every recorded action and motion has been replayed.
+    pub fn is_finished(&self) -> bool {
+        self.next >= self.recording.actions.len() && self.next_motion >= self.recording.motion.len()
+    }
+
+}
@@ -0,0 +1,309 @@
+//!
+//! Gamepad force feedback.
+//!
+//! Gameplay plays named `Rumble` effects on a `RumbleScheduler`, which mixes whatever is playing into a level for each
+//! of the gamepad's two motors on every update tick. Scheduling is plain arithmetic on elapsed time, so it's testable
+//! without a device; `RumbleMotors` drives an actual gamepad's motors at those levels.
+//!
+
+use gilrs::ff:: { BaseEffect, BaseEffectType, EffectBuilder };
+use crate::settings::input::gamepad_input:: { GamepadId, GamepadInput };
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn approx(a: f32, b: f32) -> bool {
+        (a - b).abs() < 1e-4
+    }
+
+    #[test]
+    fn test_rumbleenvelope_shape() {
+
+        let envelope = RumbleEnvelope { strength: 0.8, attack: 0.1, hold: 0.2, fade: 0.4 };
+        assert!(approx(envelope.duration(), 0.7));
+
+        // Ramps up, holds, then fades out.
+        assert!(approx(envelope.level_at(0.0), 0.0));
+        assert!(approx(envelope.level_at(0.05), 0.4));
+        assert!(approx(envelope.level_at(0.2), 0.8));
+        assert!(approx(envelope.level_at(0.5), 0.4));
+        assert!(approx(envelope.level_at(0.7), 0.0));
+        assert!(approx(envelope.level_at(2.0), 0.0));
+        assert!(approx(envelope.level_at(-1.0), 0.0));
+
+        // No attack starts at full strength.
+        assert!(approx(RumbleEnvelope::pulse(0.5, 0.1).level_at(0.0), 0.5));
+
+    }
+
+    #[test]
+    fn test_rumblescheduler_mixing() {
+
+        let settings = GamepadInput::default();
+        let mut rumble = RumbleScheduler::default();
+        assert_eq!(rumble.update(0.1, &settings), RumbleLevels::default());
+
+        // Overlapping effects take the strongest level of each motor, and end after their duration.
+        rumble.play(RumbleEffect {
+            strong: RumbleEnvelope::pulse(0.5, 0.3),
+            weak:   RumbleEnvelope::pulse(0.2, 0.3)
+        });
+        rumble.play(RumbleEffect {
+            strong: RumbleEnvelope::pulse(0.3, 0.1),
+            weak:   RumbleEnvelope::pulse(0.9, 0.1)
+        });
+        assert_eq!(rumble.update(0.05, &settings), RumbleLevels { strong: 0.5, weak: 0.9 });
+        assert_eq!(rumble.update(0.1, &settings), RumbleLevels { strong: 0.5, weak: 0.2 });
+        assert!(rumble.is_playing());
+        assert_eq!(rumble.update(0.2, &settings), RumbleLevels::default());
+        assert!(!rumble.is_playing());
+
+        // Scaled effects are weaker.
+        rumble.play_scaled(Rumble::Impact.effect(), 0.5);
+        let levels = rumble.update(0.0, &settings);
+        assert!(approx(levels.strong, Rumble::Impact.effect().strong.strength * 0.5));
+
+    }
+
+    #[test]
+    fn test_rumblescheduler_settings() {
+
+        let mut rumble = RumbleScheduler::default();
+        rumble.play(RumbleEffect { strong: RumbleEnvelope::pulse(0.8, 1.0), weak: RumbleEnvelope::pulse(0.4, 1.0) });
+
+        // Intensity scales levels...
+        let settings = GamepadInput { rumble_intensity: 0.5, ..Default::default() };
+        assert_eq!(rumble.update(0.1, &settings), RumbleLevels { strong: 0.4, weak: 0.2 });
+
+        // ...and disabling rumble silences it, without stopping effects in the meantime.
+        let settings = GamepadInput { rumble_enabled: false, ..Default::default() };
+        assert_eq!(rumble.update(0.1, &settings), RumbleLevels::default());
+        assert!(rumble.is_playing());
+
+        // Stopping ends everything.
+        rumble.stop();
+        assert!(!rumble.is_playing());
+
+    }
+
+}
+
+
+/// Named rumble effects gameplay can play.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rumble {
+
+    /// The player took damage.
+    Damage,
+
+    /// A heavy hit or hard landing.
+    Impact,
+
+    /// A light tick, confirming an interaction.
+    Click,
+
+}
+
+impl Rumble {
+
+    /// Motor envelopes of this effect.
+    pub fn effect(&self) -> RumbleEffect {
+        match self {
+            Rumble::Damage  => RumbleEffect {
+                strong: RumbleEnvelope { strength: 0.6, attack: 0.0, hold: 0.1, fade: 0.2 },
+                weak:   RumbleEnvelope { strength: 0.8, attack: 0.0, hold: 0.15, fade: 0.1 },
+            },
+            Rumble::Impact  => RumbleEffect {
+                strong: RumbleEnvelope { strength: 1.0, attack: 0.0, hold: 0.15, fade: 0.35 },
+                weak:   RumbleEnvelope { strength: 0.4, attack: 0.0, hold: 0.1, fade: 0.1 },
+            },
+            Rumble::Click   => RumbleEffect {
+                strong: RumbleEnvelope::default(),
+                weak:   RumbleEnvelope::pulse(0.5, 0.05),
+            },
+        }
+    }
+
+}
+
+/// Strength of a motor over time, in seconds since an effect started.
+///
+/// Ramps up from nothing to `strength` over `attack`, holds it for `hold`, then fades back to nothing over `fade`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct RumbleEnvelope {
+    pub strength:   f32,
+    pub attack:     f32,
+    pub hold:       f32,
+    pub fade:       f32,
+}
+
+impl RumbleEnvelope {
+
+    /// Constant `strength` for `duration` seconds.
+    pub fn pulse(strength: f32, duration: f32) -> Self {
+        RumbleEnvelope { strength, attack: 0.0, hold: duration, fade: 0.0 }
+    }
+
+    /// Seconds from start to end.
+    pub fn duration(&self) -> f32 {
+        self.attack + self.hold + self.fade
+    }
+
+    /// Strength `time` seconds after starting.
+    pub fn level_at(&self, time: f32) -> f32 {
+
+        if time < 0.0 || time >= self.duration() {
+            0.0
+        }
+        else if time < self.attack {
+            self.strength * time / self.attack
+        }
+        else if time < self.attack + self.hold {
+            self.strength
+        }
+        else {
+            self.strength * (self.duration() - time) / self.fade
+        }
+
+    }
+
+}
+
+/// Envelopes of a gamepad's strong (low frequency) and weak (high frequency) motors.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct RumbleEffect {
+    pub strong:     RumbleEnvelope,
+    pub weak:       RumbleEnvelope,
+}
+
+impl RumbleEffect {
+
+    /// Seconds until both motors are done.
+    pub fn duration(&self) -> f32 {
+        self.strong.duration().max(self.weak.duration())
+    }
+
+    // The same effect with both envelopes' strength multiplied by `scale`.
+    fn scaled(mut self, scale: f32) -> Self {
+        self.strong.strength *= scale;
+        self.weak.strength *= scale;
+        self
+    }
+
+}
+
+/// Motor levels for an update tick, from `0.0` (off) to `1.0` (full).
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct RumbleLevels {
+    pub strong:     f32,
+    pub weak:       f32,
+}
+
+/// Effects playing, mixed into motor levels on every update tick.
+#[derive(Debug, Default)]
+pub struct RumbleScheduler {
+
+    // Playing effects, with the seconds elapsed since each started.
+    playing:    Vec<(RumbleEffect, f32)>,
+
+}
+
+impl RumbleScheduler {
+
+    /// Start playing `effect` on the next update tick.
+    pub fn play(&mut self, effect: RumbleEffect) {
+        self.playing.push((effect, 0.0));
+    }
+
+    /// Start playing `effect` with its strength multiplied by `scale`, e.g. to match how much damage was taken.
+    pub fn play_scaled(&mut self, effect: RumbleEffect, scale: f32) {
+        self.play(effect.scaled(scale.max(0.0)));
+    }
+
+    /// Stop all effects.
+    pub fn stop(&mut self) {
+        self.playing.clear();
+    }
+
+    /// Returns `true` while any effect is playing.
+    pub fn is_playing(&self) -> bool {
+        !self.playing.is_empty()
+    }
+
+    /// Advance playing effects by `dt` seconds and mix them into motor levels, given the player's gamepad `settings`.
+    ///
+    /// Each motor takes the strongest level of all effects playing. Finished effects are dropped.
+    pub fn update(&mut self, dt: f32, settings: &GamepadInput) -> RumbleLevels {
+
+        let mut levels = RumbleLevels::default();
+        for (effect, elapsed) in &mut self.playing {
+            *elapsed += dt;
+            levels.strong = levels.strong.max(effect.strong.level_at(*elapsed));
+            levels.weak = levels.weak.max(effect.weak.level_at(*elapsed));
+        }
+        self.playing.retain(|(effect, elapsed)| *elapsed < effect.duration());
+
+        if !settings.rumble_enabled {
+            return RumbleLevels::default();
+        }
+        let intensity = settings.rumble_intensity.clamp(0.0, 1.0);
+        RumbleLevels {
+            strong: (levels.strong * intensity).clamp(0.0, 1.0),
+            weak:   (levels.weak * intensity).clamp(0.0, 1.0),
+        }
+
+    }
+
+}
+
+/// Force feedback motors of a gamepad, held at the levels set on each update tick.
+pub struct RumbleMotors {
+
+    /// Gamepad the motors belong to.
+    pub gamepad:    GamepadId,
+
+    strong:         gilrs::ff::Effect,
+    weak:           gilrs::ff::Effect,
+
+}
+
+impl RumbleMotors {
+
+    /// Start both motors of `gamepad`, silent until `set()`.
+    pub fn new(gilrs: &mut gilrs::Gilrs, gamepad: GamepadId) -> Result<Self, gilrs::ff::Error> {
+
+        // Each motor plays at full magnitude forever; its gain sets the level.
+        let mut motor = |kind| -> Result<gilrs::ff::Effect, gilrs::ff::Error> {
+            let effect = EffectBuilder::new()
+                .add_effect(BaseEffect { kind, ..Default::default() })
+                .gamepads(&[gamepad])
+                .gain(0.0)
+                .finish(gilrs)?;
+            effect.play()?;
+            Ok(effect)
+        };
+
+        Ok(RumbleMotors {
+            gamepad,
+            strong: motor(BaseEffectType::Strong { magnitude: u16::MAX })?,
+            weak:   motor(BaseEffectType::Weak { magnitude: u16::MAX })?,
+        })
+
+    }
+
+    /// Set the motors to `levels`.
+    pub fn set(&self, levels: RumbleLevels) -> Result<(), gilrs::ff::Error> {
+        self.strong.set_gain(levels.strong)?;
+        self.weak.set_gain(levels.weak)
+    }
+
+}
+
+impl std::fmt::Debug for RumbleMotors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RumbleMotors").field("gamepad", &self.gamepad).finish_non_exhaustive()
+    }
+}
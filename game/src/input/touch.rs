@@ -0,0 +1,414 @@
+//!
+//! Touch controls.
+//!
+//! Turns fingers on the screen into presses and releases of `TouchInputSources` and into look motion, following the
+//! layout in the player's `TouchInput` settings: a finger touching down on an on-screen button holds that button,
+//! elsewhere on the left half of the screen it pushes a floating virtual stick, and on the right half it swipes to
+//! look. `TouchControls` is a plain state machine over finger ids and positions, so it's testable without a screen.
+//!
+
+use std::collections::HashMap;
+use fyrox::event::TouchPhase;
+use crate::settings::input::touch_input:: { StickDirection, TouchInput, TouchInputSources };
+
+
+/// How far the virtual stick must be pushed in a direction before it counts as a press of its
+/// `TouchInputSources::Stick`.
+const STICK_PRESS_THRESHOLD: f32 = 0.5;
+
+/// Stick directions, in the order `TouchControls` holds them.
+const STICK_DIRECTIONS: [StickDirection; 4] = [
+    StickDirection::Up,
+    StickDirection::Down,
+    StickDirection::Left,
+    StickDirection::Right,
+];
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::settings::input::touch_input::TouchButtonLayout;
+
+    const SCREEN: (f32, f32) = (2000.0, 1000.0);
+
+    fn approx(a: (f32, f32), b: (f32, f32)) -> bool {
+        (a.0 - b.0).abs() < 1e-4 && (a.1 - b.1).abs() < 1e-4
+    }
+
+    fn stick(direction: StickDirection) -> TouchInputSources {
+        TouchInputSources::Stick(direction)
+    }
+
+    fn controls() -> TouchControls {
+        let mut controls = TouchControls::default();
+        controls.resize(SCREEN.0, SCREEN.1);
+        controls
+    }
+
+    #[test]
+    fn test_touchcontrols_stick() {
+
+        let settings = TouchInput::default();
+        let radius = settings.stick_radius * SCREEN.1;
+        let mut touch = controls();
+
+        // The stick centres wherever it's touched down on the left half...
+        assert!(touch.on_touch(1, TouchPhase::Started, (300.0, 600.0), &settings).is_empty());
+        assert!(approx(touch.stick(&settings), (0.0, 0.0)));
+
+        // ...and pushing it all the way up presses up.
+        assert_eq!(
+            touch.on_touch(1, TouchPhase::Moved, (300.0, 600.0 - radius), &settings),
+            vec![TouchChange::Pressed(stick(StickDirection::Up))]
+        );
+        assert!(approx(touch.stick(&settings), (0.0, 1.0)));
+
+        // Pushing beyond the radius is full deflection, and diagonals press both directions.
+        assert_eq!(
+            touch.on_touch(1, TouchPhase::Moved, (300.0 + radius * 2.0, 600.0 - radius * 2.0), &settings),
+            vec![TouchChange::Pressed(stick(StickDirection::Right))]
+        );
+        let (x, y) = touch.stick(&settings);
+        assert!(((x * x + y * y).sqrt() - 1.0).abs() < 1e-4);
+
+        // Back inside the deadzone is centred, releasing everything.
+        assert_eq!(
+            touch.on_touch(1, TouchPhase::Moved, (300.0 + radius * 0.1, 600.0), &settings),
+            vec![TouchChange::Released(stick(StickDirection::Up)), TouchChange::Released(stick(StickDirection::Right))]
+        );
+        assert!(approx(touch.stick(&settings), (0.0, 0.0)));
+
+        // Lifting the finger while pushed releases too.
+        touch.on_touch(1, TouchPhase::Moved, (300.0 - radius, 600.0), &settings);
+        assert_eq!(
+            touch.on_touch(1, TouchPhase::Ended, (300.0 - radius, 600.0), &settings),
+            vec![TouchChange::Released(stick(StickDirection::Left))]
+        );
+        assert!(!touch.is_touched());
+
+    }
+
+    #[test]
+    fn test_touchcontrols_look() {
+
+        let settings = TouchInput::default();
+        let mut touch = controls();
+
+        // Swiping on the right half looks around, in degrees per screen height swiped...
+        touch.on_touch(1, TouchPhase::Started, (1500.0, 500.0), &settings);
+        touch.on_touch(1, TouchPhase::Moved, (1550.0, 480.0), &settings);
+        touch.on_touch(1, TouchPhase::Moved, (1600.0, 480.0), &settings);
+        assert!(approx(touch.take_look(&settings), (-18.0, -3.6)));
+        assert!(approx(touch.take_look(&settings), (0.0, 0.0)));
+
+        // ...alongside the stick, with each finger keeping its role wherever it moves.
+        touch.on_touch(2, TouchPhase::Started, (300.0, 500.0), &settings);
+        touch.on_touch(2, TouchPhase::Moved, (1500.0, 500.0), &settings);
+        touch.on_touch(1, TouchPhase::Moved, (100.0, 480.0), &settings);
+        assert!(approx(touch.stick(&settings), (1.0, 0.0)));
+        assert!(approx(touch.take_look(&settings), (270.0, 0.0)));
+
+        // A second finger on the same half is ignored.
+        touch.on_touch(3, TouchPhase::Started, (1800.0, 500.0), &settings);
+        touch.on_touch(3, TouchPhase::Moved, (1900.0, 500.0), &settings);
+        assert!(approx(touch.take_look(&settings), (0.0, 0.0)));
+
+        // Inverted look swipes the other way vertically.
+        let inverted = TouchInput { look_invert_y: true, ..Default::default() };
+        touch.on_touch(1, TouchPhase::Moved, (100.0, 500.0), &inverted);
+        assert!(approx(touch.take_look(&inverted), (0.0, -3.6)));
+
+    }
+
+    #[test]
+    fn test_touchcontrols_buttons() {
+
+        let settings = TouchInput {
+            buttons: vec![
+                TouchButtonLayout { x: 0.9, y: 0.5, radius: 0.1 },
+                TouchButtonLayout { x: 0.1, y: 0.9, radius: 0.1 },
+            ],
+            ..Default::default()
+        };
+        let mut touch = controls();
+
+        // Buttons take precedence over the stick and look beneath them.
+        assert_eq!(
+            touch.on_touch(1, TouchPhase::Started, (1820.0, 540.0), &settings),
+            vec![TouchChange::Pressed(TouchInputSources::Button(0))]
+        );
+        assert_eq!(
+            touch.on_touch(2, TouchPhase::Started, (200.0, 900.0), &settings),
+            vec![TouchChange::Pressed(TouchInputSources::Button(1))]
+        );
+        assert!(touch.on_touch(2, TouchPhase::Moved, (600.0, 300.0), &settings).is_empty());
+        assert!(approx(touch.stick(&settings), (0.0, 0.0)));
+
+        // Held by two fingers, a button is released when the last one lifts.
+        assert!(touch.on_touch(3, TouchPhase::Started, (1800.0, 500.0), &settings).is_empty());
+        assert!(touch.on_touch(1, TouchPhase::Ended, (1820.0, 540.0), &settings).is_empty());
+        assert_eq!(
+            touch.on_touch(3, TouchPhase::Cancelled, (1800.0, 500.0), &settings),
+            vec![TouchChange::Released(TouchInputSources::Button(0))]
+        );
+
+        // Clearing releases whatever is still held.
+        assert_eq!(touch.clear(), vec![TouchChange::Released(TouchInputSources::Button(1))]);
+        assert!(!touch.is_touched());
+
+    }
+
+    #[test]
+    fn test_touchcontrols_unknown_screen() {
+
+        // Until the screen size is known, touches can't be placed and are ignored.
+        let settings = TouchInput::default();
+        let mut touch = TouchControls::default();
+        assert!(touch.on_touch(1, TouchPhase::Started, (10.0, 10.0), &settings).is_empty());
+        assert!(!touch.is_touched());
+
+        // Fingers lifted that never touched down are ignored too.
+        let mut touch = controls();
+        assert!(touch.on_touch(7, TouchPhase::Ended, (10.0, 10.0), &settings).is_empty());
+
+    }
+
+}
+
+
+/// A touch control pressed or released.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TouchChange {
+    Pressed(TouchInputSources),
+    Released(TouchInputSources),
+}
+
+// What a finger on the screen does, decided when it touches down.
+#[derive(Debug, Clone, Copy)]
+enum Finger {
+
+    // Holds an on-screen button.
+    Button(u8),
+
+    // Pushes the virtual stick, centred on `origin`.
+    Stick { origin: (f32, f32), position: (f32, f32) },
+
+    // Swipes to look, last seen at `position`.
+    Look { position: (f32, f32) },
+
+}
+
+/// Touch controls state, fed touch events in pixels from the top left of the screen.
+#[derive(Debug, Default)]
+pub struct TouchControls {
+
+    // Screen size in pixels; zero until known.
+    screen:     (f32, f32),
+
+    // Fingers on the screen, by touch id.
+    fingers:    HashMap<u64, Finger>,
+
+    // Stick directions pressed, in the order of `STICK_DIRECTIONS`.
+    stick_held: [bool; 4],
+
+    // Swipe-to-look motion since the last `take_look()`, in pixels.
+    look:       (f32, f32),
+
+}
+
+impl TouchControls {
+
+    /// Set the screen size, in pixels.
+    pub fn resize(&mut self, width: f32, height: f32) {
+        self.screen = (width, height);
+    }
+
+    /// Feed a touch event of finger `id` at `position`, laid out according to `settings`. Returns the controls it
+    /// pressed or released.
+    pub fn on_touch(
+        &mut self,
+        id: u64,
+        phase: TouchPhase,
+        position: (f32, f32),
+        settings: &TouchInput
+    ) -> Vec<TouchChange> {
+
+        let mut changes = Vec::new();
+
+        match phase {
+
+            TouchPhase::Started     => {
+
+                if self.screen.0 <= 0.0 || self.screen.1 <= 0.0 {
+                    return changes;
+                }
+
+                let button = settings.buttons.iter()
+                    .take(u8::MAX as usize + 1)
+                    .position(|button| button.contains(position, self.screen));
+
+                let finger = if let Some(index) = button {
+                    if !self.holds_button(index as u8) {
+                        changes.push(TouchChange::Pressed(TouchInputSources::Button(index as u8)));
+                    }
+                    Finger::Button(index as u8)
+                }
+                // Each half of the screen follows one finger; others touching down there are ignored.
+                else if position.0 < self.screen.0 / 2.0 {
+                    if self.fingers.values().any(|finger| matches!(finger, Finger::Stick { .. })) {
+                        return changes;
+                    }
+                    Finger::Stick { origin: position, position }
+                }
+                else {
+                    if self.fingers.values().any(|finger| matches!(finger, Finger::Look { .. })) {
+                        return changes;
+                    }
+                    Finger::Look { position }
+                };
+                self.fingers.insert(id, finger);
+
+            },
+
+            TouchPhase::Moved       => {
+                let mut stick_moved = false;
+                match self.fingers.get_mut(&id) {
+                    Some(Finger::Stick { position: last, .. }) => {
+                        *last = position;
+                        stick_moved = true;
+                    },
+                    Some(Finger::Look { position: last }) => {
+                        self.look.0 += position.0 - last.0;
+                        self.look.1 += position.1 - last.1;
+                        *last = position;
+                    },
+                    _ => {},
+                }
+                if stick_moved {
+                    changes.extend(self.update_stick(settings));
+                }
+            },
+
+            TouchPhase::Ended
+            | TouchPhase::Cancelled => {
+                match self.fingers.remove(&id) {
+                    Some(Finger::Button(index)) if !self.holds_button(index) => {
+                        changes.push(TouchChange::Released(TouchInputSources::Button(index)));
+                    },
+                    Some(Finger::Stick { .. }) => changes.extend(self.update_stick(settings)),
+                    _ => {},
+                }
+            },
+
+        }
+
+        changes
+
+    }
+
+    /// Position of the virtual stick, from `-1.0` to `1.0` on each axis with up and right positive, given `settings`.
+    ///
+    /// Pushing the stick further than `stick_radius` is full deflection. Deflection within `stick_deadzone` of the
+    /// centre is ignored, and the rest rescaled to still reach full deflection.
+    pub fn stick(&self, settings: &TouchInput) -> (f32, f32) {
+
+        let Some((origin, position)) = self.fingers.values().find_map(|finger| match finger {
+            Finger::Stick { origin, position }  => Some((*origin, *position)),
+            _                                   => None,
+        }) else { return (0.0, 0.0) };
+
+        let radius = (settings.stick_radius * self.screen.1).max(1.0);
+        let offset = ((position.0 - origin.0) / radius, (origin.1 - position.1) / radius);
+        let length = (offset.0 * offset.0 + offset.1 * offset.1).sqrt();
+
+        let deadzone = settings.stick_deadzone.clamp(0.0, 0.99);
+        if length <= deadzone {
+            return (0.0, 0.0);
+        }
+        let scale = ((length.min(1.0) - deadzone) / (1.0 - deadzone)) / length;
+        (offset.0 * scale, offset.1 * scale)
+
+    }
+
+    /// Take the swipe-to-look motion since the last call, as a `(yaw, pitch)` change in degrees given `settings`.
+    ///
+    /// Swipes map to look like mouse motion does: swiping right turns right, and swiping up looks up.
+    pub fn take_look(&mut self, settings: &TouchInput) -> (f32, f32) {
+
+        let (dx, dy) = std::mem::take(&mut self.look);
+        if self.screen.1 <= 0.0 {
+            return (0.0, 0.0);
+        }
+        let invert = if settings.look_invert_y { -1.0 } else { 1.0 };
+
+        (
+            -dx / self.screen.1 * settings.look_sensitivity,
+            dy / self.screen.1 * settings.look_sensitivity * invert,
+        )
+
+    }
+
+    /// Lift every finger, returning the controls released.
+    pub fn clear(&mut self) -> Vec<TouchChange> {
+
+        let mut changes: Vec<TouchChange> = self.stick_held.iter()
+            .zip(STICK_DIRECTIONS)
+            .filter(|(held, _)| **held)
+            .map(|(_, direction)| TouchChange::Released(TouchInputSources::Stick(direction)))
+            .collect();
+
+        let mut buttons: Vec<u8> = self.fingers.values()
+            .filter_map(|finger| match finger {
+                Finger::Button(index)   => Some(*index),
+                _                       => None,
+            })
+            .collect();
+        buttons.sort_unstable();
+        buttons.dedup();
+        changes.extend(buttons.into_iter().map(|index| TouchChange::Released(TouchInputSources::Button(index))));
+
+        self.fingers.clear();
+        self.stick_held = [false; 4];
+        self.look = (0.0, 0.0);
+        changes
+
+    }
+
+    /// Returns `true` while any finger followed by the controls is on the screen.
+    pub fn is_touched(&self) -> bool {
+        !self.fingers.is_empty()
+    }
+
+    // Returns `true` if any finger holds button `index`.
+    fn holds_button(&self, index: u8) -> bool {
+        self.fingers.values().any(|finger| matches!(finger, Finger::Button(held) if *held == index))
+    }
+
+    // Press and release stick directions as the stick crosses `STICK_PRESS_THRESHOLD` in them.
+    fn update_stick(&mut self, settings: &TouchInput) -> Vec<TouchChange> {
+
+        let (x, y) = self.stick(settings);
+        let pushed = [
+            y >= STICK_PRESS_THRESHOLD,
+            y <= -STICK_PRESS_THRESHOLD,
+            x <= -STICK_PRESS_THRESHOLD,
+            x >= STICK_PRESS_THRESHOLD,
+        ];
+
+        let mut changes = Vec::new();
+        for ((held, pushed), direction) in self.stick_held.iter_mut().zip(pushed).zip(STICK_DIRECTIONS) {
+            let source = TouchInputSources::Stick(direction);
+            match (*held, pushed) {
+                (false, true)   => changes.push(TouchChange::Pressed(source)),
+                (true, false)   => changes.push(TouchChange::Released(source)),
+                _               => continue,
+            }
+            *held = pushed;
+        }
+        changes
+
+    }
+
+}
@@ -0,0 +1,335 @@
+//!
+//! Trigger evaluation.
+//!
+//! Turns presses and releases of input sources into started and ended actions, according to each binding's chord and
+//! `BindingTrigger`. Time is supplied by the caller in seconds, so evaluation is deterministic and testable without a
+//! window or a clock.
+//!
+
+use std::collections::HashMap;
+use serde:: { Serialize, Deserialize };
+use crate::settings::input:: {
+    Binding,
+    BindingActions,
+    BindingChord,
+    Bindings,
+    BindingSources,
+    BindingTrigger,
+    ChordModifiers,
+};
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use fyrox::keyboard:: { KeyCode, PhysicalKey };
+    use crate::settings::input:: { LinearDirectionSetting, desktop_input::DesktopInputSources };
+
+    fn key(code: KeyCode) -> BindingSources {
+        BindingSources::Desktop(DesktopInputSources::Key(PhysicalKey::Code(code)))
+    }
+
+    #[test]
+    fn test_triggers_held() {
+
+        let mut bindings = Bindings::new();
+        bindings.insert(key(KeyCode::KeyW).into(), Binding::held(BindingActions::linear(LinearDirectionSetting::forward())));
+        let forward = BindingActions::linear(LinearDirectionSetting::forward());
+        let mut triggers = TriggerEvaluator::default();
+
+        // Starts on press, ignores key repeat and ends on release.
+        assert_eq!(triggers.press(&bindings, key(KeyCode::KeyW), 0.0), vec![ResolvedAction::started(forward.clone())]);
+        assert!(triggers.press(&bindings, key(KeyCode::KeyW), 0.1).is_empty());
+        assert!(triggers.is_active(&forward));
+        assert_eq!(triggers.release(&bindings, key(KeyCode::KeyW), 0.5), vec![ResolvedAction::ended(forward.clone())]);
+        assert!(!triggers.is_active(&forward));
+
+        // Unbound sources resolve to nothing.
+        assert!(triggers.press(&bindings, key(KeyCode::KeyQ), 1.0).is_empty());
+        assert!(triggers.release(&bindings, key(KeyCode::KeyQ), 1.1).is_empty());
+
+    }
+
+    #[test]
+    fn test_triggers_chords() {
+
+        let walk = BindingActions::linear(LinearDirectionSetting::forward());
+        let interact = BindingActions::interact();
+        let mut bindings = Bindings::new();
+        bindings.insert(key(KeyCode::KeyW).into(), Binding::held(walk.clone()));
+        bindings.insert(BindingChord::new(ChordModifiers::shift(), key(KeyCode::KeyW)), Binding::held(interact.clone()));
+        let mut triggers = TriggerEvaluator::default();
+
+        // Without Shift, the plain binding wins.
+        assert_eq!(triggers.press(&bindings, key(KeyCode::KeyW), 0.0), vec![ResolvedAction::started(walk.clone())]);
+        assert_eq!(triggers.release(&bindings, key(KeyCode::KeyW), 0.1), vec![ResolvedAction::ended(walk.clone())]);
+
+        // With either Shift key held, the more specific chord wins.
+        assert!(triggers.press(&bindings, key(KeyCode::ShiftRight), 1.0).is_empty());
+        assert_eq!(triggers.press(&bindings, key(KeyCode::KeyW), 1.1), vec![ResolvedAction::started(interact.clone())]);
+
+        // Releasing the modifier first doesn't end the chord; releasing its source does.
+        assert!(triggers.release(&bindings, key(KeyCode::ShiftRight), 1.2).is_empty());
+        assert!(triggers.is_active(&interact));
+        assert_eq!(triggers.release(&bindings, key(KeyCode::KeyW), 1.3), vec![ResolvedAction::ended(interact.clone())]);
+
+        // Chords with as many modifiers held resolve the same way every time, whatever order bindings iterate in.
+        bindings.insert(BindingChord::new(ChordModifiers::ctrl(), key(KeyCode::KeyW)), Binding::held(walk.clone()));
+        for _ in 0..16 {
+            let bindings = bindings.clone().into_iter().collect::<Bindings>();
+            let mut triggers = TriggerEvaluator::default();
+            triggers.press(&bindings, key(KeyCode::ControlLeft), 0.0);
+            triggers.press(&bindings, key(KeyCode::ShiftLeft), 0.0);
+            let resolved = triggers.press(&bindings, key(KeyCode::KeyW), 0.1);
+            assert_eq!(resolved, vec![ResolvedAction::started(interact.clone())]);
+        }
+
+    }
+
+    #[test]
+    fn test_triggers_long_press() {
+
+        let interact = BindingActions::interact();
+        let mut bindings = Bindings::new();
+        bindings.insert(key(KeyCode::KeyE).into(), Binding::long_press(interact.clone(), 0.5));
+        let mut triggers = TriggerEvaluator::default();
+
+        // A short press does nothing.
+        assert!(triggers.press(&bindings, key(KeyCode::KeyE), 0.0).is_empty());
+        assert!(triggers.update(&bindings, 0.25).is_empty());
+        assert!(triggers.release(&bindings, key(KeyCode::KeyE), 0.3).is_empty());
+        assert!(triggers.update(&bindings, 1.0).is_empty());
+
+        // Holding up to the threshold starts the action once.
+        assert!(triggers.press(&bindings, key(KeyCode::KeyE), 2.0).is_empty());
+        assert!(triggers.update(&bindings, 2.49).is_empty());
+        assert_eq!(triggers.update(&bindings, 2.5), vec![ResolvedAction::started(interact.clone())]);
+        assert!(triggers.update(&bindings, 3.0).is_empty());
+        assert_eq!(triggers.release(&bindings, key(KeyCode::KeyE), 3.1), vec![ResolvedAction::ended(interact.clone())]);
+
+    }
+
+    #[test]
+    fn test_triggers_double_tap() {
+
+        let interact = BindingActions::interact();
+        let mut bindings = Bindings::new();
+        bindings.insert(key(KeyCode::KeyE).into(), Binding::double_tap(interact.clone(), 0.3));
+        let mut triggers = TriggerEvaluator::default();
+
+        // Taps too far apart do nothing.
+        assert!(triggers.press(&bindings, key(KeyCode::KeyE), 0.0).is_empty());
+        assert!(triggers.release(&bindings, key(KeyCode::KeyE), 0.1).is_empty());
+        assert!(triggers.press(&bindings, key(KeyCode::KeyE), 0.5).is_empty());
+        assert!(triggers.release(&bindings, key(KeyCode::KeyE), 0.6).is_empty());
+
+        // The second tap within the window (counted from the previous tap) starts the action.
+        assert_eq!(triggers.press(&bindings, key(KeyCode::KeyE), 0.7), vec![ResolvedAction::started(interact.clone())]);
+        assert_eq!(triggers.release(&bindings, key(KeyCode::KeyE), 0.9), vec![ResolvedAction::ended(interact.clone())]);
+
+        // A third tap starts a new pair rather than firing again.
+        assert!(triggers.press(&bindings, key(KeyCode::KeyE), 1.0).is_empty());
+
+    }
+
+    #[test]
+    fn test_triggers_toggle() {
+
+        let forward = BindingActions::linear(LinearDirectionSetting::forward());
+        let mut bindings = Bindings::new();
+        bindings.insert(key(KeyCode::KeyW).into(), Binding::toggle(forward.clone()));
+        let mut triggers = TriggerEvaluator::default();
+
+        // Releases are ignored; each press flips the action.
+        assert_eq!(triggers.press(&bindings, key(KeyCode::KeyW), 0.0), vec![ResolvedAction::started(forward.clone())]);
+        assert!(triggers.release(&bindings, key(KeyCode::KeyW), 0.1).is_empty());
+        assert!(triggers.is_active(&forward));
+        assert_eq!(triggers.press(&bindings, key(KeyCode::KeyW), 1.0), vec![ResolvedAction::ended(forward.clone())]);
+        assert!(triggers.release(&bindings, key(KeyCode::KeyW), 1.1).is_empty());
+        assert!(!triggers.is_active(&forward));
+
+    }
+
+}
+
+/// Whether a resolved action has started or ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ActionState {
+    Started,
+    Ended
+}
+
+/// An action that started or ended as the result of evaluating triggers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedAction {
+    pub action: BindingActions,
+    pub state:  ActionState,
+}
+
+impl ResolvedAction {
+
+    pub fn started(action: BindingActions) -> Self {
+        ResolvedAction { action, state: ActionState::Started }
+    }
+
+    pub fn ended(action: BindingActions) -> Self {
+        ResolvedAction { action, state: ActionState::Ended }
+    }
+
+}
+
+/// Evaluates `BindingTrigger`s over presses and releases of input sources.
+///
+/// Every function takes the current time in seconds; the evaluator doesn't keep a clock of its own.
+#[derive(Debug, Default)]
+pub struct TriggerEvaluator {
+
+    // Sources held down, with the time they were pressed.
+    held:       HashMap<BindingSources, f32>,
+
+    // Chord each held source resolved to when it was pressed.
+    pressed:    HashMap<BindingSources, BindingChord>,
+
+    // Chords whose actions are active.
+    active:     HashMap<BindingChord, BindingActions>,
+
+    // Time of the first tap of a possible double-tap.
+    taps:       HashMap<BindingChord, f32>,
+
+}
+
+impl TriggerEvaluator {
+
+    /// Evaluate a press of `source` at `time`.
+    ///
+    /// Repeated presses of a source that's already held (such as OS key repeat) are ignored.
+    pub fn press(&mut self, bindings: &Bindings, source: BindingSources, time: f32) -> Vec<ResolvedAction> {
+
+        if self.held.contains_key(&source) {
+            return vec![];
+        }
+
+        // Modifiers are those held *before* this press, so a modifier key never chords with itself.
+        let modifiers = self.modifiers();
+        self.held.insert(source, time);
+
+        // The chord with the most satisfied modifiers wins; `Shift+W` takes precedence over `W` while Shift is held.
+        // Ties go by modifier, in the order chords are written (`Shift`, `Ctrl`, `Alt`, `Super`), rather than by
+        // however the bindings happen to iterate.
+        let Some((chord, binding)) = bindings.iter()
+            .filter(|(chord, _)| chord.source == source && modifiers.contains(&chord.modifiers))
+            .max_by_key(|(chord, _)| {
+                let ChordModifiers { shift, ctrl, alt, logo } = chord.modifiers;
+                (chord.modifiers.count(), shift, ctrl, alt, logo)
+            })
+        else {
+            return vec![];
+        };
+        self.pressed.insert(source, *chord);
+
+        match binding.trigger {
+
+            BindingTrigger::Held            => vec![self.start(chord, binding)],
+
+            BindingTrigger::Toggle          => {
+                if self.active.contains_key(chord) { self.end(chord).into_iter().collect() }
+                else { vec![self.start(chord, binding)] }
+            },
+
+            // Started later in `update()` once held long enough.
+            BindingTrigger::LongPress { .. }  => vec![],
+
+            BindingTrigger::DoubleTap { window } => {
+                match self.taps.remove(chord) {
+                    Some(first) if time - first <= window   => vec![self.start(chord, binding)],
+                    _                                       => {
+                        self.taps.insert(*chord, time);
+                        vec![]
+                    }
+                }
+            },
+
+        }
+
+    }
+
+    /// Evaluate a release of `source` at `time`.
+    pub fn release(&mut self, bindings: &Bindings, source: BindingSources, _time: f32)
+        -> Vec<ResolvedAction> {
+
+        self.held.remove(&source);
+        let Some(chord) = self.pressed.remove(&source) else {
+            return vec![];
+        };
+
+        match bindings.get(&chord).map(|binding| binding.trigger) {
+            Some(BindingTrigger::Toggle)    => vec![],
+            // Also ends actions whose binding was removed while held.
+            _                               => self.end(&chord).into_iter().collect(),
+        }
+
+    }
+
+    /// Evaluate time-driven triggers at `time`. Call this once per update tick.
+    pub fn update(&mut self, bindings: &Bindings, time: f32) -> Vec<ResolvedAction> {
+
+        let mut due: Vec<(f32, BindingChord)> = self.pressed.iter()
+            .filter(|(_, chord)| !self.active.contains_key(chord))
+            .filter_map(|(source, chord)| {
+                let pressed_at = self.held.get(source)?;
+                match bindings.get(chord)?.trigger {
+                    BindingTrigger::LongPress { threshold } if time - pressed_at >= threshold
+                        => Some((*pressed_at, *chord)),
+                    _   => None,
+                }
+            })
+            .collect();
+
+        // Earliest press first, so simultaneous long presses resolve in a stable order.
+        due.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        due.into_iter()
+            .filter_map(|(_, chord)| {
+                let binding = bindings.get(&chord)?;
+                Some(self.start(&chord, binding))
+            })
+            .collect()
+
+    }
+
+    /// Returns `true` if `action` is active through any chord.
+    pub fn is_active(&self, action: &BindingActions) -> bool {
+        self.active.values().any(|active| active == action)
+    }
+
+    /// Returns `true` if `source` is held down.
+    pub fn is_held(&self, source: &BindingSources) -> bool {
+        self.held.contains_key(source)
+    }
+
+    /// Modifier keys held down.
+    pub fn modifiers(&self) -> ChordModifiers {
+        ChordModifiers::from_held(self.held.keys())
+    }
+
+    /// Release everything held and end every active action, as when the window loses focus.
+    pub fn clear(&mut self) -> Vec<ResolvedAction> {
+        self.held.clear();
+        self.pressed.clear();
+        self.taps.clear();
+        self.active.drain()
+            .map(|(_, action)| ResolvedAction::ended(action))
+            .collect()
+    }
+
+    fn start(&mut self, chord: &BindingChord, binding: &Binding) -> ResolvedAction {
+        self.active.insert(*chord, binding.action.clone());
+        ResolvedAction::started(binding.action.clone())
+    }
+
+    fn end(&mut self, chord: &BindingChord) -> Option<ResolvedAction> {
+        self.active.remove(chord).map(ResolvedAction::ended)
+    }
+
+}
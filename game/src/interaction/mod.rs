@@ -0,0 +1,396 @@
+//!
+//! Interaction.
+//!
+//! Anything the player can use, from doors to switches to pickups, is `Interactable`: it has a prompt, a range within
+//! which the player can reach it, and may be disabled. The player focuses whatever they're looking at within range,
+//! which highlights it and prompts them, and interacts with it on pressing *Interact*.
+//!
+//! Designers make things interactable in the editor with the `Interaction` script, choosing what interacting does
+//! with an `InteractAction`, so most doors, switches and pickups need no new code. Other scripts can be interactable
+//! too, by implementing `Interactable` and being listed in `INTERACTABLES`.
+//!
+
+use fyrox:: {
+    core:: {
+        algebra:: { UnitQuaternion, Vector3 },
+        pool::Handle,
+        visitor::prelude::*,
+        reflect::prelude::*,
+        type_traits::prelude::*,
+    },
+    scene:: { graph::Graph, node::Node },
+    script:: { Script, ScriptContext, ScriptTrait },
+};
+use strum_macros::*;
+use tracing:: { info, warn, instrument };
+use crate::player::Player;
+
+
+
+#[test]
+fn test_interaction_toggle() {
+
+    let mut door = Interaction {
+        prompt:         String::from("Open"),
+        prompt_toggled: String::from("Close"),
+        ..Default::default()
+    };
+    assert!(door.is_enabled());
+    assert_eq!(door.prompt(), "Open");
+
+    // Interacting toggles, and the prompt follows...
+    door.interact();
+    assert!(door.toggled);
+    assert_eq!(door.prompt(), "Close");
+    door.interact();
+    assert!(!door.toggled);
+
+    // ...falling back on the untoggled prompt if there's no toggled one.
+    door.prompt_toggled.clear();
+    door.interact();
+    assert_eq!(door.prompt(), "Open");
+
+    // Disabled interactables can't be interacted with, and single use ones disable themselves.
+    let mut switch = Interaction { once: true, ..Default::default() };
+    switch.interact();
+    assert!(switch.toggled);
+    assert!(!switch.is_enabled());
+    switch.interact();
+    assert!(switch.toggled);
+
+}
+
+#[test]
+fn test_interaction_swing() {
+
+    let mut door = Interaction { open_angle: 90.0, swing_speed: 180.0, ..Default::default() };
+
+    // Closed doors stay closed.
+    assert_eq!(door.swing(1.0), 0.0);
+
+    // Opened doors swing open at their swing speed, and stop once open...
+    door.interact();
+    assert_eq!(door.swing(0.25), 45.0);
+    assert_eq!(door.swing(0.25), 90.0);
+    assert_eq!(door.swing(0.25), 90.0);
+
+    // ...and back shut again.
+    door.interact();
+    assert_eq!(door.swing(0.25), 45.0);
+    assert_eq!(door.swing(1.0), 0.0);
+
+    // Doors may swing the other way, and without a swing speed they snap.
+    let mut door = Interaction { open_angle: -90.0, swing_speed: 0.0, ..Default::default() };
+    door.interact();
+    assert_eq!(door.swing(0.01), -90.0);
+
+}
+
+
+/// Something the player can interact with.
+pub trait Interactable {
+
+    /// Text prompting the player to interact, such as "Open door".
+    fn prompt(&self) -> &str;
+
+    /// Furthest the player can be from the point on the interactable they look at to interact with it, in metres.
+    fn range(&self) -> f32;
+
+    /// Returns `true` if the player can interact with it now.
+    fn is_enabled(&self) -> bool;
+
+    /// Focus or unfocus, as the player looks at it or away.
+    fn focus(&mut self, focused: bool);
+
+    /// Interact. Acting on the interaction may wait until the interactable's next update tick.
+    fn interact(&mut self);
+
+}
+
+/// The `Interactable` script on `node` or its nearest ancestor with one, as a collider hit by a cast belongs to the
+/// node it shapes.
+pub fn interactable_of(graph: &Graph, node: Handle<Node>) -> Option<Handle<Node>> {
+    let mut node = node;
+    while let Some(each) = graph.try_get(node) {
+        if interactable(graph, node).is_some() {
+            return Some(node);
+        }
+        node = each.parent();
+    }
+    None
+}
+
+/// Casts from a script to the `Interactable` it is, for a script type that's interactable.
+pub struct InteractableCast {
+    pub cast:       fn(&Script) -> Option<&dyn Interactable>,
+    pub cast_mut:   fn(&mut Script) -> Option<&mut dyn Interactable>,
+}
+
+impl InteractableCast {
+
+    /// Casts for scripts of type `T`.
+    pub const fn of<T: ScriptTrait + Interactable>() -> Self {
+        InteractableCast {
+            cast:       |script| script.cast::<T>().map(|script| script as &dyn Interactable),
+            cast_mut:   |script| script.cast_mut::<T>().map(|script| script as &mut dyn Interactable),
+        }
+    }
+
+}
+
+/// Every script type that's `Interactable`. Scripts must be listed here for the player to focus and interact with them.
+pub const INTERACTABLES: &[InteractableCast] = &[
+    InteractableCast::of::<Interaction>(),
+];
+
+/// The first `Interactable` script on `node`, if it has one.
+pub fn interactable(graph: &Graph, node: Handle<Node>) -> Option<&dyn Interactable> {
+    graph.try_get(node)?.scripts()
+        .find_map(|script| INTERACTABLES.iter().find_map(|each| (each.cast)(script)))
+}
+
+/// The first `Interactable` script on `node`, if it has one, mutably.
+pub fn interactable_mut(graph: &mut Graph, node: Handle<Node>) -> Option<&mut dyn Interactable> {
+    graph.try_get_mut(node)?.scripts_mut()
+        .find_map(|script| {
+            let each = INTERACTABLES.iter().find(|each| (each.cast)(script).is_some())?;
+            (each.cast_mut)(script)
+        })
+}
+
+
+/// What interacting with an `Interaction` does.
+#[derive(Visit, Reflect, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(AsRefStr, EnumString, VariantNames, TypeUuidProvider)]
+#[type_uuid(id = "5a8e2f17-c4d9-4b36-9e0a-7d1c3b6f8e25")]
+pub enum InteractAction {
+
+    /// Nothing but toggling; other scripts may check `Interaction::toggled`.
+    #[default]
+    Toggle,
+
+    /// The node swings open about its vertical axis by `Interaction::open_angle`, and shut again.
+    Door,
+
+    /// Each of `Interaction::targets` is interacted with if it's interactable, or else enabled or disabled.
+    Switch,
+
+    /// The node is picked up, removing it from the scene, and `Interaction::count` of `Interaction::item` are added
+    /// to the player's inventory. It stays if they don't all fit, or there's no player to take them.
+    Pickup,
+
+}
+
+#[derive(Visit, Reflect, Debug, Clone, TypeUuidProvider, ComponentProvider)]
+#[type_uuid(id = "b71f4c08-2e6a-4d93-a5c1-8f0e3d7b9a46")]
+#[visit(optional)]
+pub struct Interaction {
+
+    /// Text prompting the player to interact.
+    pub prompt:         String,
+
+    /// Text prompting the player to interact while toggled, such as "Close door"; `prompt` is used if it's empty.
+    pub prompt_toggled: String,
+
+    /// Furthest the player can be from the point on the node they look at to interact with it, in metres.
+    pub range:          f32,
+
+    /// The player can interact with it.
+    pub enabled:        bool,
+
+    /// Disable after the first interaction.
+    pub once:           bool,
+
+    /// What interacting does.
+    pub action:         InteractAction,
+
+    /// Nodes a `Switch` acts on.
+    pub targets:        Vec<Handle<Node>>,
+
+    /// Angle a `Door` swings open to, in degrees. Negative angles swing the other way.
+    pub open_angle:     f32,
+
+    /// Speed a `Door` swings at, in degrees per second, or `0.0` to snap open and shut.
+    pub swing_speed:    f32,
+
+    /// ID of the item a `Pickup` adds to the player's inventory, if any, as defined in `data/items.ron`.
+    pub item:           String,
+
+    /// How many of `item` a `Pickup` adds.
+    pub count:          u32,
+
+    /// Node shown only while the player focuses this one, such as an outline or a light. Optional.
+    pub highlight:      Handle<Node>,
+
+    /// Toggled by interacting, as a door's open or a switch is on.
+    #[reflect(hidden)]
+    pub toggled:        bool,
+
+    /// Angle a `Door` has swung open to, in degrees.
+    #[reflect(hidden)]
+    pub angle:          f32,
+
+    /// Rotation of a `Door` when shut.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    shut:               Option<UnitQuaternion<f32>>,
+
+    /// The player is focusing it.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    focused:            bool,
+
+    /// Interactions not yet acted on.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pending:            u8,
+
+}
+
+impl Interaction {
+
+    /// Advance a `Door` swinging towards open or shut by `dt` seconds. Returns the angle it's swung open to.
+    pub fn swing(&mut self, dt: f32) -> f32 {
+        let target = if self.toggled { self.open_angle } else { 0.0 };
+        let step = if self.swing_speed > 0.0 { self.swing_speed * dt } else { f32::INFINITY };
+        self.angle += (target - self.angle).clamp(-step, step);
+        self.angle
+    }
+
+}
+
+impl Interactable for Interaction {
+
+    fn prompt(&self) -> &str {
+        if self.toggled && !self.prompt_toggled.is_empty() { &self.prompt_toggled } else { &self.prompt }
+    }
+
+    fn range(&self) -> f32 {
+        self.range
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn focus(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    fn interact(&mut self) {
+        if !self.enabled {
+            return;
+        }
+        self.toggled = !self.toggled;
+        self.pending = self.pending.saturating_add(1);
+        if self.once {
+            self.enabled = false;
+        }
+    }
+
+}
+
+impl Default for Interaction {
+    fn default() -> Self {
+        Interaction {
+            prompt:         String::from("Interact"),
+            prompt_toggled: String::new(),
+            range:          2.5,
+            enabled:        true,
+            once:           false,
+            action:         InteractAction::default(),
+            targets:        Vec::new(),
+            open_angle:     90.0,
+            swing_speed:    180.0,
+            item:           String::new(),
+            count:          1,
+            highlight:      Handle::NONE,
+            toggled:        false,
+            angle:          0.0,
+            shut:           None,
+            focused:        false,
+            pending:        0,
+        }
+    }
+}
+
+impl ScriptTrait for Interaction {
+
+    #[instrument(skip(context))]
+    fn on_start(&mut self, context: &mut ScriptContext) {
+
+        // Remember how a door sits when shut, from however far open it was saved.
+        if self.action == InteractAction::Door {
+            let rotation = **context.scene.graph[context.handle].local_transform().rotation();
+            let opened = UnitQuaternion::from_axis_angle(&Vector3::y_axis(), self.angle.to_radians());
+            self.shut = Some(rotation * opened.inverse());
+        }
+
+    }
+
+    #[instrument(skip(context))]
+    fn on_update(&mut self, context: &mut ScriptContext) {
+
+        // Doors stand still and interactions wait while the game is paused.
+        if context.plugins.get::<crate::Game>().is_paused() {
+            return;
+        }
+
+        let graph = &mut context.scene.graph;
+
+        // Highlight while focused, as long as it can be interacted with.
+        if let Some(highlight) = graph.try_get_mut(self.highlight) {
+            highlight.set_enabled(self.focused && self.enabled);
+        }
+
+        // Act on interactions since the last tick.
+        for _ in 0..std::mem::take(&mut self.pending) {
+            info!("{} interacted with", graph[context.handle].name());
+            match self.action {
+
+                InteractAction::Toggle | InteractAction::Door   => {},
+
+                InteractAction::Switch                          => {
+                    for target in self.targets.iter().copied() {
+                        if let Some(interactable) = interactable_mut(graph, target) {
+                            interactable.interact();
+                        }
+                        else if let Some(target) = graph.try_get_mut(target) {
+                            target.set_enabled(!target.is_enabled());
+                        }
+                    }
+                },
+
+                InteractAction::Pickup                          => {
+                    if !self.item.is_empty() {
+                        let gameplugin = context.plugins.get::<crate::Game>();
+                        let added = graph.try_get_script_of_mut::<Player>(gameplugin.player)
+                            .map(|player| player.inventory.add(&gameplugin.items, &self.item, self.count));
+                        let refused = match added {
+                            Some(Ok(()))    => None,
+                            Some(Err(why))  => Some(why.to_string()),
+                            None            => Some(String::from("there's no player to take it")),
+                        };
+                        if let Some(why) = refused {
+                            warn!("{} can't be picked up: {}", graph[context.handle].name(), why);
+                            self.enabled = true;
+                            continue;
+                        }
+                    }
+                    graph.remove_node(context.handle);
+                    return;
+                },
+
+            }
+        }
+
+        // Doors swing towards open or shut.
+        if let Some(shut) = self.shut {
+            let angle = self.swing(context.dt);
+            graph[context.handle].local_transform_mut()
+                .set_rotation(shut * UnitQuaternion::from_axis_angle(&Vector3::y_axis(), angle.to_radians()));
+        }
+
+    }
+
+}
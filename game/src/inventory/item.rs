@@ -0,0 +1,180 @@
+//!
+//! Item definitions.
+//!
+//! Every kind of item is defined once, by an `ItemDef` in the `ItemCatalog` read from a data file in [Rusty Object
+//! Notation][1]. Inventories only hold item IDs and counts, and look everything else up in the catalog.
+//!
+//! [1]: https://docs.rs/ron/0.8.1/ron/index.html
+//!
+
+use std:: { collections::HashMap, fs, io, path::Path };
+use serde:: { Deserialize, Serialize };
+
+/// Item definitions file path.
+pub const ITEMS_FILEPATH: &str = "data/items.ron";
+
+
+
+#[test]
+fn test_itemcatalog_from_ron() {
+
+    let catalog = ItemCatalog::from_ron(r#"[
+        (id: "coin", name: "Coin", weight: 0.01, stack: 99),
+        (id: "keycard", name: "Keycard", description: "Opens the lab.", unique: true),
+    ]"#).unwrap();
+
+    // Fields left out take their defaults.
+    assert_eq!(catalog.len(), 2);
+    let coin = catalog.get("coin").unwrap();
+    assert_eq!(coin.stack, 99);
+    assert!(coin.is_stackable());
+    assert!(coin.description.is_empty());
+    let keycard = catalog.get("keycard").unwrap();
+    assert_eq!(keycard.weight, 0.0);
+    assert_eq!(keycard.stack, 1);
+    assert!(!keycard.is_stackable());
+    assert!(catalog.get("lockpick").is_none());
+
+}
+
+#[test]
+fn test_itemcatalog_errors() {
+
+    let invalid = |buffer: &str| ItemCatalog::from_ron(buffer).unwrap_err().kind();
+
+    // Malformed files, duplicate IDs and nonsensical definitions are all invalid data.
+    assert_eq!(invalid(r#"[ (id: "coin") ]"#), io::ErrorKind::InvalidData);
+    assert_eq!(invalid(r#"[ (id: "coin", name: "Coin"), (id: "coin", name: "Penny") ]"#), io::ErrorKind::InvalidData);
+    assert_eq!(invalid(r#"[ (id: "", name: "Nothing") ]"#), io::ErrorKind::InvalidData);
+    assert_eq!(invalid(r#"[ (id: "coin", name: "Coin", stack: 0) ]"#), io::ErrorKind::InvalidData);
+    assert_eq!(invalid(r#"[ (id: "coin", name: "Coin", weight: -1.0) ]"#), io::ErrorKind::InvalidData);
+    assert_eq!(invalid(r#"[ (id: "key", name: "Key", stack: 5, unique: true) ]"#), io::ErrorKind::InvalidData);
+
+    // The game's own definitions are valid.
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("..").join(ITEMS_FILEPATH);
+    assert!(!ItemCatalog::load(&path).unwrap().is_empty());
+
+}
+
+
+/// Definition of a kind of item.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ItemDef {
+
+    /// Unique ID inventories and scripts refer to the item by, such as `"medkit"`.
+    pub id:             String,
+
+    /// Name shown to the player.
+    pub name:           String,
+
+    /// Description shown to the player.
+    #[serde(default)]
+    pub description:    String,
+
+    /// Weight of one, in kilograms.
+    #[serde(default)]
+    pub weight:         f32,
+
+    /// Most held in one inventory slot. Items that stack to `1` take a slot each.
+    #[serde(default = "default_stack")]
+    pub stack:          u32,
+
+    /// At most one may be held in an inventory.
+    #[serde(default)]
+    pub unique:         bool,
+
+}
+
+fn default_stack() -> u32 {
+    1
+}
+
+impl ItemDef {
+
+    /// Returns `true` if more than one fits in a slot.
+    pub fn is_stackable(&self) -> bool {
+        self.stack > 1
+    }
+
+    // Why the definition makes no sense, if it doesn't.
+    fn invalid(&self) -> Option<&'static str> {
+        if self.id.is_empty() {
+            Some("its ID is empty")
+        }
+        else if self.stack == 0 {
+            Some("it stacks to zero")
+        }
+        else if !self.weight.is_finite() || self.weight < 0.0 {
+            Some("its weight is negative or not a number")
+        }
+        else if self.unique && self.stack > 1 {
+            Some("it's unique but stacks")
+        }
+        else {
+            None
+        }
+    }
+
+}
+
+/// Every item definition, by ID.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ItemCatalog {
+    items:  HashMap<String, ItemDef>,
+}
+
+impl ItemCatalog {
+
+    /// Catalog of `definitions`. Returns an `io::ErrorKind::InvalidData` error if two share an ID or one makes no
+    /// sense, such as stacking to zero.
+    pub fn new(definitions: impl IntoIterator<Item = ItemDef>) -> io::Result<Self> {
+
+        let invalid = |why: String| io::Error::new(io::ErrorKind::InvalidData, why);
+
+        let mut items = HashMap::new();
+        for definition in definitions {
+            if let Some(why) = definition.invalid() {
+                return Err(invalid(format!("item '{}' is invalid: {}", definition.id, why)));
+            }
+            if items.contains_key(&definition.id) {
+                return Err(invalid(format!("item '{}' is defined more than once", definition.id)));
+            }
+            items.insert(definition.id.clone(), definition);
+        }
+        Ok(ItemCatalog { items })
+
+    }
+
+    /// Deserialize a list of item definitions from [Rusty Object Notation][1].
+    ///
+    /// [1]: https://docs.rs/ron/0.8.1/ron/index.html
+    pub fn from_ron(buffer: &str) -> io::Result<Self> {
+        let definitions: Vec<ItemDef> = ron::de::from_str(buffer)
+            .map_err(|why| io::Error::new(io::ErrorKind::InvalidData, why))?;
+        Self::new(definitions)
+    }
+
+    /// Read item definitions from the file at `path`.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        Self::from_ron(&fs::read_to_string(path)?)
+    }
+
+    /// Definition of the item with ID `id`, if there's one.
+    pub fn get(&self, id: &str) -> Option<&ItemDef> {
+        self.items.get(id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Every item definition, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = &ItemDef> {
+        self.items.values()
+    }
+
+}
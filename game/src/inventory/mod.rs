@@ -0,0 +1,353 @@
+//!
+//! Inventories.
+//!
+//! An `Inventory` holds stacks of items, by ID, in a limited number of slots and up to a weight limit. Adding,
+//! removing and transferring items either happens in full or not at all, failing with an `InventoryError` saying
+//! exactly why. What each item is, how far it stacks and whether it's unique comes from the `item::ItemCatalog`.
+//!
+//! Inventories are saved with whatever holds them, such as the `Player` script.
+//!
+
+use std::fmt::Display;
+use fyrox:: {
+    core:: { visitor::prelude::*, reflect::prelude::* },
+};
+use serde:: { Deserialize, Serialize };
+
+pub mod item;
+use item::ItemCatalog;
+
+
+
+#[cfg(test)]
+fn catalog() -> ItemCatalog {
+    ItemCatalog::from_ron(r#"[
+        (id: "coin", name: "Coin", weight: 0.01, stack: 50),
+        (id: "battery", name: "Battery", weight: 1.0, stack: 5),
+        (id: "flashlight", name: "Flashlight", weight: 2.0),
+        (id: "keycard", name: "Keycard", unique: true),
+    ]"#).unwrap()
+}
+
+#[test]
+fn test_inventory_add_and_stack() {
+
+    let catalog = catalog();
+    let mut inventory = Inventory { slots: 4, max_weight: 10.0, ..Default::default() };
+
+    // Stackable items fill the stacks they have before starting new ones...
+    assert_eq!(inventory.add(&catalog, "coin", 30), Ok(()));
+    assert_eq!(inventory.add(&catalog, "coin", 30), Ok(()));
+    assert_eq!(inventory.stacks(), &[ItemStack::new("coin", 50), ItemStack::new("coin", 10)]);
+    assert_eq!(inventory.count("coin"), 60);
+
+    // ...while those that don't stack take a slot each.
+    assert_eq!(inventory.add(&catalog, "flashlight", 2), Ok(()));
+    assert_eq!(inventory.slots_used(), 4);
+    assert_eq!(inventory.add(&catalog, "coin", 40), Ok(()));
+    assert_eq!(inventory.count("coin"), 100);
+
+    // Nothing else fits.
+    assert_eq!(
+        inventory.add(&catalog, "coin", 1),
+        Err(InventoryError::Full { item: String::from("coin"), needed: 1, free: 0 })
+    );
+
+}
+
+#[test]
+fn test_inventory_add_errors() {
+
+    let catalog = catalog();
+    let mut inventory = Inventory { slots: 2, max_weight: 5.0, ..Default::default() };
+
+    assert_eq!(inventory.add(&catalog, "lockpick", 1), Err(InventoryError::UnknownItem(String::from("lockpick"))));
+    assert_eq!(inventory.add(&catalog, "coin", 0), Err(InventoryError::ZeroCount));
+
+    // Unique items can only be held once.
+    assert_eq!(inventory.add(&catalog, "keycard", 2), Err(InventoryError::Unique(String::from("keycard"))));
+    assert_eq!(inventory.add(&catalog, "keycard", 1), Ok(()));
+    assert_eq!(inventory.add(&catalog, "keycard", 1), Err(InventoryError::Unique(String::from("keycard"))));
+
+    // Nothing is added past the weight limit, or without room for all of it.
+    assert_eq!(
+        inventory.add(&catalog, "flashlight", 3),
+        Err(InventoryError::Overweight { item: String::from("flashlight"), weight: 6.0, limit: 5.0 })
+    );
+    assert_eq!(
+        inventory.add(&catalog, "flashlight", 2),
+        Err(InventoryError::Full { item: String::from("flashlight"), needed: 2, free: 1 })
+    );
+    assert_eq!(inventory.slots_used(), 1);
+    assert_eq!(inventory.count("flashlight"), 0);
+
+    // Up to the limit is fine.
+    assert_eq!(inventory.add(&catalog, "battery", 5), Ok(()));
+    assert_eq!(inventory.weight(&catalog), 5.0);
+
+}
+
+#[test]
+fn test_inventory_remove_and_transfer() {
+
+    let catalog = catalog();
+    let mut inventory = Inventory::default();
+    inventory.add(&catalog, "battery", 7).unwrap();
+
+    // Removing takes from the last stack first, emptying slots as it goes.
+    assert_eq!(inventory.remove("battery", 3), Ok(()));
+    assert_eq!(inventory.stacks(), &[ItemStack::new("battery", 4)]);
+    assert_eq!(
+        inventory.remove("battery", 5),
+        Err(InventoryError::NotEnough { item: String::from("battery"), held: 4, wanted: 5 })
+    );
+    assert_eq!(inventory.remove("battery", 0), Err(InventoryError::ZeroCount));
+
+    // Transferring moves items from one inventory into another...
+    let mut chest = Inventory { slots: 1, ..Default::default() };
+    assert_eq!(inventory.transfer(&mut chest, &catalog, "battery", 3), Ok(()));
+    assert_eq!((inventory.count("battery"), chest.count("battery")), (1, 3));
+
+    // ...unless either side can't, leaving both as they were.
+    inventory.add(&catalog, "flashlight", 1).unwrap();
+    assert_eq!(
+        inventory.transfer(&mut chest, &catalog, "flashlight", 1),
+        Err(InventoryError::Full { item: String::from("flashlight"), needed: 1, free: 0 })
+    );
+    assert_eq!(
+        chest.transfer(&mut inventory, &catalog, "battery", 4),
+        Err(InventoryError::NotEnough { item: String::from("battery"), held: 3, wanted: 4 })
+    );
+    assert_eq!((inventory.count("flashlight"), chest.count("flashlight")), (1, 0));
+    assert_eq!((inventory.count("battery"), chest.count("battery")), (1, 3));
+
+    assert_eq!(chest.remove("battery", 3), Ok(()));
+    assert!(chest.is_empty());
+
+}
+
+#[test]
+fn test_inventory_serialization() {
+
+    let catalog = catalog();
+    let mut inventory = Inventory { slots: 8, max_weight: 12.5, ..Default::default() };
+    inventory.add(&catalog, "coin", 75).unwrap();
+    inventory.add(&catalog, "keycard", 1).unwrap();
+
+    // Inventories come back from saves exactly as they were.
+    let buffer = ron::ser::to_string(&inventory).unwrap();
+    let loaded: Inventory = ron::de::from_str(&buffer).unwrap();
+    assert_eq!(loaded, inventory);
+
+}
+
+
+/// A stack of items of the same kind, taking one inventory slot.
+#[derive(Visit, Reflect, Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ItemStack {
+
+    /// ID of the item, as defined in the `ItemCatalog`.
+    pub item:   String,
+
+    /// How many there are.
+    pub count:  u32,
+
+}
+
+impl ItemStack {
+    pub fn new(item: &str, count: u32) -> Self {
+        ItemStack { item: item.to_owned(), count }
+    }
+}
+
+/// Why items couldn't be added to, removed from or transferred between inventories.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InventoryError {
+
+    /// No item with this ID is defined.
+    UnknownItem(String),
+
+    /// Asked for none of an item.
+    ZeroCount,
+
+    /// Unique item is already held, or more than one was asked for.
+    Unique(String),
+
+    /// Not enough free slots: `needed` were needed, and only `free` were free.
+    Full { item: String, needed: u32, free: u32 },
+
+    /// Total `weight` would go past the weight `limit`.
+    Overweight { item: String, weight: f32, limit: f32 },
+
+    /// Only `held` were held of the `wanted` amount.
+    NotEnough { item: String, held: u32, wanted: u32 },
+
+}
+
+impl Display for InventoryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownItem(item)                     => write!(f, "item '{item}' is unknown"),
+            Self::ZeroCount                             => write!(f, "no items were asked for"),
+            Self::Unique(item)                          => write!(f, "item '{item}' is unique and can't be held twice"),
+            Self::Full { item, needed, free }           => {
+                write!(f, "no room for item '{item}': it needs {needed} slots and {free} are free")
+            },
+            Self::Overweight { item, weight, limit }    => {
+                write!(f, "item '{item}' is too heavy: it would weigh {weight} of a {limit} limit")
+            },
+            Self::NotEnough { item, held, wanted }      => {
+                write!(f, "not enough of item '{item}': {held} held, {wanted} wanted")
+            },
+        }
+    }
+}
+
+impl std::error::Error for InventoryError {}
+
+/// Stacks of items, in limited slots and up to a weight limit.
+#[derive(Visit, Reflect, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[visit(optional)]
+pub struct Inventory {
+
+    /// Most stacks held at once.
+    pub slots:      u32,
+
+    /// Heaviest the items held may weigh in total, in kilograms.
+    pub max_weight: f32,
+
+    /// Stacks held, in the order they were started.
+    #[reflect(hidden)]
+    stacks:         Vec<ItemStack>,
+
+}
+
+impl Inventory {
+
+    /// Stacks held, in the order they were started.
+    pub fn stacks(&self) -> &[ItemStack] {
+        &self.stacks
+    }
+
+    /// How many of `item` are held.
+    pub fn count(&self, item: &str) -> u32 {
+        self.stacks.iter().filter(|stack| stack.item == item).map(|stack| stack.count).sum()
+    }
+
+    pub fn slots_used(&self) -> u32 {
+        self.stacks.len() as u32
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.stacks.is_empty()
+    }
+
+    /// Total weight of the items held, in kilograms. Items missing from `catalog` weigh nothing.
+    pub fn weight(&self, catalog: &ItemCatalog) -> f32 {
+        self.stacks.iter()
+            .filter_map(|stack| catalog.get(&stack.item).map(|definition| definition.weight * stack.count as f32))
+            .sum()
+    }
+
+    /// Add `count` of `item`, topping up its stacks before starting new ones. Nothing is added unless all of it fits.
+    pub fn add(&mut self, catalog: &ItemCatalog, item: &str, count: u32) -> Result<(), InventoryError> {
+
+        let definition = catalog.get(item).ok_or_else(|| InventoryError::UnknownItem(item.to_owned()))?;
+        if count == 0 {
+            return Err(InventoryError::ZeroCount);
+        }
+        if definition.unique && (count > 1 || self.count(item) > 0) {
+            return Err(InventoryError::Unique(item.to_owned()));
+        }
+
+        let weight = self.weight(catalog) + definition.weight * count as f32;
+        if weight > self.max_weight {
+            return Err(InventoryError::Overweight { item: item.to_owned(), weight, limit: self.max_weight });
+        }
+
+        // Whatever doesn't fit on the stacks held starts new ones.
+        let size = definition.stack.max(1);
+        let room: u32 = self.stacks.iter()
+            .filter(|stack| stack.item == item)
+            .map(|stack| size.saturating_sub(stack.count))
+            .fold(0, u32::saturating_add);
+        let needed = count.saturating_sub(room).div_ceil(size);
+        let free = self.slots.saturating_sub(self.slots_used());
+        if needed > free {
+            return Err(InventoryError::Full { item: item.to_owned(), needed, free });
+        }
+
+        let mut left = count;
+        for stack in self.stacks.iter_mut().filter(|stack| stack.item == item) {
+            let put = size.saturating_sub(stack.count).min(left);
+            stack.count += put;
+            left -= put;
+        }
+        while left > 0 {
+            let put = left.min(size);
+            self.stacks.push(ItemStack::new(item, put));
+            left -= put;
+        }
+        Ok(())
+
+    }
+
+    /// Remove `count` of `item`, from its last stack first. Nothing is removed unless that many are held.
+    pub fn remove(&mut self, item: &str, count: u32) -> Result<(), InventoryError> {
+
+        if count == 0 {
+            return Err(InventoryError::ZeroCount);
+        }
+        let held = self.count(item);
+        if held < count {
+            return Err(InventoryError::NotEnough { item: item.to_owned(), held, wanted: count });
+        }
+
+        let mut left = count;
+        for stack in self.stacks.iter_mut().rev().filter(|stack| stack.item == item) {
+            let take = stack.count.min(left);
+            stack.count -= take;
+            left -= take;
+            if left == 0 {
+                break;
+            }
+        }
+        self.stacks.retain(|stack| stack.count > 0);
+        Ok(())
+
+    }
+
+    /// Move `count` of `item` into the inventory `to`. Nothing moves unless all of it can be removed from here and
+    /// added there.
+    pub fn transfer(
+        &mut self,
+        to: &mut Inventory,
+        catalog: &ItemCatalog,
+        item: &str,
+        count: u32
+    ) -> Result<(), InventoryError> {
+
+        if count == 0 {
+            return Err(InventoryError::ZeroCount);
+        }
+        let held = self.count(item);
+        if held < count {
+            return Err(InventoryError::NotEnough { item: item.to_owned(), held, wanted: count });
+        }
+
+        to.add(catalog, item, count)?;
+        self.remove(item, count)
+
+    }
+
+}
+
+impl Default for Inventory {
+    fn default() -> Self {
+        Inventory {
+            slots:      20,
+            max_weight: 30.0,
+            stacks:     Vec::new(),
+        }
+    }
+}
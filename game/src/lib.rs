@@ -14,8 +14,14 @@
 //! 
 
 
+pub mod checkpoint; /// Checkpoints the player respawns at.
+pub mod health;     /// Health of any entity.
+pub mod input;      /// Input subsystem.
+pub mod interaction; /// Things the player can interact with.
+pub mod inventory;  /// Items and inventories.
 pub mod player;     /// Player object and script.
 pub mod settings;   /// Player/game settings.
+pub mod spawn;      /// Spawn points the player is spawned at.
 pub mod ui;         /// Game User Interface.
 // pub mod eventline;  /// Events processor subroutine.
 mod utilities;      /// Game utilities.
@@ -29,9 +35,9 @@ use fyrox::{
         pool::Handle,
         reflect::prelude::*,
         visitor::prelude::*
-    }, event::{DeviceEvent, Event}, gui::{ message:: { MessageDirection, UiMessage }, text::{Text, TextMessage}, UiNode }, keyboard:: { PhysicalKey, KeyCode }, plugin::{ Plugin, PluginContext, PluginRegistrationContext }, scene::Scene
+    }, event::{DeviceEvent, Event, WindowEvent}, gui::{ message:: { MessageDirection, UiMessage }, text::{Text, TextMessage}, UiNode }, keyboard:: { PhysicalKey, KeyCode }, plugin::{ Plugin, PluginContext, PluginRegistrationContext }, scene:: { node::Node, Scene }
 };
-use std:: { future::{Future, IntoFuture}, path::Path };
+use std:: { future::{Future, IntoFuture}, io, path:: { Path, PathBuf } };
 use tracing:: { trace, trace_span, debug, debug_span, info, info_span, warn, warn_span, error, error_span, instrument };
 use crate::utilities::*;
 
@@ -52,6 +58,17 @@ pub struct Game {
     /// Active Scene.
     scene:          Handle<Scene>,
 
+    /// Node of the active scene's `Player` script, once it has started.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    player:         Handle<Node>,
+
+    /// Name of the spawn point to spawn the player at in scenes loaded from now on, or `None` for each scene's
+    /// default one. See `spawn::SpawnPoint`.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    spawn_point:    Option<String>,
+
     /// User Interfaces.
     ui:             ui::UiSubset,
 
@@ -64,6 +81,40 @@ pub struct Game {
     #[reflect(hidden)]
     gamepads:       Option<gilrs::Gilrs>,
 
+    /// Resolves input events into actions through the bindings in `settings`.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    input:          input::InputSystem,
+
+    /// Force feedback effects played by gameplay, felt on the player's gamepad while they're using it.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    rumble:         input::RumbleScheduler,
+
+    /// Gamepad `rumble_motors` were set up for, if any; they're `None` if it has no force feedback.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    rumble_gamepad: Option<gilrs::GamepadId>,
+
+    #[visit(skip)]
+    #[reflect(hidden)]
+    rumble_motors:  Option<input::rumble::RumbleMotors>,
+
+    /// The game was paused for the player's gamepad disconnecting, and unpauses once it reconnects.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    pad_paused:     bool,
+
+    /// Definitions of every item, read from `inventory::item::ITEMS_FILEPATH`.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    items:          inventory::item::ItemCatalog,
+
+    /// Size of the game window, in pixels, once it's known.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    window_size:    (f32, f32),
+
 }
 
 impl Game {
@@ -84,6 +135,94 @@ impl Game {
     //     }
     // }
 
+    /// Returns `true` while the game is paused, as it is while the pause menu has input. Scripts do nothing and the
+    /// scene's physics stands still until it unpauses.
+    pub fn is_paused(&self) -> bool {
+        self.input.contains(input::InputContextKind::PauseMenu)
+    }
+
+    /// Aspect ratio of the game window (width over height), or zero until its size is known.
+    pub fn aspect_ratio(&self) -> f32 {
+        if self.window_size.1 > 0.0 { self.window_size.0 / self.window_size.1 } else { 0.0 }
+    }
+
+    /// Spawn the player at the spawn point named `name` in scenes loaded from now on, or at each scene's default one
+    /// if `None`.
+    pub fn spawn_point_set(&mut self, name: Option<String>) {
+        self.spawn_point = name;
+    }
+
+    /// Spawn the player into the scene `scene` at the chosen spawn point, unless it has a player of its own.
+    ///
+    /// The player's camera gets its skybox once the skybox has loaded.
+    fn spawn_player(&self, scene: Handle<Scene>, context: &mut PluginContext) {
+
+        let graph = &context.scenes[scene].graph;
+        let own = graph.pair_iter().find(|(_, node)| node.try_get_script::<player::Player>().is_some());
+        if let Some((player, _)) = own {
+            debug!("scene has a player of its own: {:?}", player);
+            return;
+        }
+
+        let points = graph.pair_iter()
+            .filter_map(|(handle, node)| {
+                node.try_get_script::<spawn::SpawnPoint>().map(|point| (handle, point.default))
+            })
+            .collect::<Vec<_>>();
+        let names = points.iter().map(|(handle, default)| (graph[*handle].name(), *default)).collect::<Vec<_>>();
+        let (position, yaw) = match spawn::choose(&names, self.spawn_point.as_deref()) {
+            Some(index) => {
+                let point = &graph[points[index].0];
+                let look = point.look_vector();
+                info!("spawning player at {}", point.name());
+                (point.global_position(), look.x.atan2(look.z).to_degrees())
+            },
+            None => {
+                warn!("scene has no spawn point; spawning player at the origin");
+                (Default::default(), 0.0)
+            },
+        };
+
+        // The playermodel carries the player's script.
+        let player = player::Player::new(&mut context.scenes[scene], position, yaw);
+        let (playermodel, camera) = (player.playermodel, player.camera);
+        context.scenes[scene].graph[playermodel].add_script(player);
+
+        let resource_manager = context.resource_manager.clone();
+        context.task_pool.spawn_plugin_task(
+            async move { player::skybox::request(&resource_manager).await },
+            move |skybox, _game: &mut Game, context: &mut PluginContext| {
+                let camera = context.scenes.try_get_mut(scene)
+                    .and_then(|scene| scene.graph.try_get_mut(camera))
+                    .and_then(|camera| camera.cast_mut::<fyrox::scene::camera::Camera>());
+                if let Some(camera) = camera {
+                    camera.set_skybox(skybox);
+                }
+            }
+        );
+
+    }
+
+    /// Record resolved input actions, writing them to `path` when the game exits. See `input::recording`.
+    pub fn record_input(&mut self, path: PathBuf) {
+        self.input.start_recording(path);
+    }
+
+    /// Replay the input recording at `path` in place of live input.
+    pub fn replay_input(&mut self, path: &Path) -> io::Result<()> {
+        let recording = input::InputRecording::load(path)?;
+        info!("Replaying input from {}", path.display());
+        self.input.start_replay(recording);
+        Ok(())
+    }
+
+    // Write the input recording, if recording.
+    fn save_input_recording(&mut self) {
+        if let Err(why) = self.input.save_recording() {
+            error!("Unable to save input recording: {}", why);
+        }
+    }
+
     fn save(&self, context: &mut PluginContext<'_, '_>) -> VisitResult {
 
         let mut visitor = Visitor::new();
@@ -97,6 +236,135 @@ impl Game {
         context.async_scene_loader.request_raw(SAVE_FILEPATH);
     }
 
+    /// React to an action resolved by the input subsystem.
+    /// 
+    /// Opening and closing menus and overlays pushes and pops their input contexts, so gameplay stops receiving input
+    /// while they're open. Pausing shows the core menu, which claims input while it's shown.
+    fn on_action(&mut self, resolved: input::ResolvedAction, context: &mut PluginContext) {
+
+        if resolved.state != input::ActionState::Started {
+            return;
+        }
+
+        let top = self.input.top();
+        match resolved.action {
+
+            settings::input::BindingActions::Pause              => {
+                self.pad_paused = false;
+                if top == input::InputContextKind::Gameplay || top == input::InputContextKind::PauseMenu {
+                    self.ui.coremenu_toggle(&mut self.input, context);
+                }
+            },
+
+            settings::input::BindingActions::DeveloperConsole   => {
+                if top == input::InputContextKind::Gameplay {
+                    self.input.push(input::InputContext::new(input::InputContextKind::DeveloperConsole));
+                }
+                else if top == input::InputContextKind::DeveloperConsole {
+                    self.input.pop();
+                }
+            },
+
+            settings::input::BindingActions::Cancel             => {
+                if top == input::InputContextKind::PauseMenu {
+                    self.pad_paused = false;
+                    self.ui.coremenu_set(false, &mut self.input, context);
+                }
+                else {
+                    self.input.pop();
+                }
+            },
+
+            _                                                   => {}
+
+        }
+
+    }
+
+    /// React to a gamepad connecting or disconnecting, or the player switching input device.
+    /// 
+    /// Losing the gamepad the player is using pauses the game until it's back, unpausing it again unless the player
+    /// already has; see `UiSubset::reconnectprompt_set`.
+    fn on_device_change(&mut self, change: input::DeviceChange, context: &mut PluginContext) {
+
+        match change {
+
+            input::DeviceChange::Connected { gamepad, slot }                => {
+                info!("connected gamepad: {} (player slot {})", gamepad, slot);
+                self.detect_gamepad_family(gamepad);
+            },
+
+            input::DeviceChange::Reconnected { gamepad, slot }              => {
+                info!("reconnected gamepad: {} (player slot {})", gamepad, slot);
+                self.detect_gamepad_family(gamepad);
+                let paused = self.input.top() == input::InputContextKind::PauseMenu;
+                if std::mem::take(&mut self.pad_paused) && paused {
+                    self.ui.coremenu_set(false, &mut self.input, context);
+                }
+            },
+
+            input::DeviceChange::Disconnected { gamepad, slot, was_active } => {
+                info!("disconnected gamepad: {} (player slot {})", gamepad, slot);
+                if was_active && self.input.top() == input::InputContextKind::Gameplay {
+                    self.ui.coremenu_set(true, &mut self.input, context);
+                    self.pad_paused = true;
+                }
+            },
+
+            input::DeviceChange::Switched(device)                           => {
+                debug!("active input device: {:?}", device);
+            },
+
+        }
+
+    }
+
+    // Tell the input subsystem which family `gamepad` is, so prompts name its buttons.
+    fn detect_gamepad_family(&mut self, gamepad: gilrs::GamepadId) {
+        if let Some(gilrs) = &self.gamepads {
+            let each_gamepad = gilrs.gamepad(gamepad);
+            let family = input::ControllerFamily::detect(each_gamepad.vendor_id(), each_gamepad.name());
+            debug!("gamepad {} is of the {:?} family", gamepad, family);
+            self.input.set_gamepad_family(gamepad, family);
+        }
+    }
+
+    /// Drive the motors of the player's gamepad from the rumble effects playing.
+    /// 
+    /// Effects only play during gameplay, and only on the gamepad the player is using.
+    fn update_rumble(&mut self, dt: f32) {
+
+        if !self.input.gameplay_active() {
+            self.rumble.stop();
+        }
+        let levels = self.rumble.update(dt, &self.settings.input.gamepad);
+
+        let Some(gilrs) = &mut self.gamepads else { return };
+        let gamepad = match self.input.devices().active() {
+            input::InputDevice::Gamepad(gamepad)    => Some(gamepad),
+            input::InputDevice::KeyboardMouse       => None,
+        };
+
+        // Set up motors when the player switches gamepad.
+        if self.rumble_gamepad != gamepad {
+            self.rumble_gamepad = gamepad;
+            self.rumble_motors = gamepad
+                .filter(|gamepad| gilrs.gamepad(*gamepad).is_ff_supported())
+                .and_then(|gamepad| input::rumble::RumbleMotors::new(gilrs, gamepad)
+                    .inspect_err(|why| warn!("Unable to rumble gamepad {}: {}", gamepad, why))
+                    .ok()
+                );
+        }
+
+        if let Some(motors) = &self.rumble_motors {
+            if let Err(why) = motors.set(levels) {
+                warn!("Unable to rumble gamepad {}: {}", motors.gamepad, why);
+                self.rumble_motors = None;
+            }
+        }
+
+    }
+
     // TODO: Document.
     fn on_suspended(&self) {
 
@@ -112,9 +380,10 @@ impl Game {
     }
 
     // TODO: Document.
-    fn on_loopexiting(&self) {
+    fn on_loopexiting(&mut self) {
 
         // TODO: Deinit things and run save routines where necessary.
+        self.save_input_recording();
 
     }
 
@@ -133,7 +402,7 @@ impl Plugin for Game {
 
     #[instrument(skip(_context))]
     fn on_deinit(&mut self, _context: PluginContext) {
-        
+        self.save_input_recording();
     }
 
     #[instrument(
@@ -142,11 +411,15 @@ impl Plugin for Game {
     )]
     fn update(&mut self, context: &mut PluginContext) {
 
+        // Evaluate time-driven input triggers.
+        self.input.update(context.dt, &self.settings.input.bindings);
+
         // Run gamepad input updates.
         if let Some(gilrs) = &mut self.gamepads {
 
             while let Some(event) = gilrs.next_event() {
                 trace!("gilrs event by {id}: {ev:?}", id = event.id, ev = event.event);
+                self.input.on_gamepad_event(event.id, &event.event, &self.settings.input.bindings);
             };
 
             // Increment event counter.
@@ -154,7 +427,50 @@ impl Plugin for Game {
             gilrs.inc();
         }
 
-        // Run UI updates.
+        // Act on resolved input, then grab or release the cursor if the topmost input context changed.
+        for resolved in self.input.take_resolved() {
+            self.on_action(resolved, context);
+        }
+        for change in self.input.take_device_changes() {
+            self.on_device_change(change, context);
+        }
+        self.update_rumble(context.dt);
+
+        // Stop the scene's physics while paused, so nothing falls or keeps moving behind the pause menu.
+        let paused = self.is_paused();
+        if let Some(scene) = context.scenes.try_get_mut(self.scene) {
+            if *scene.graph.physics.enabled == paused {
+                scene.graph.physics.enabled.set_value_and_mark_modified(!paused);
+            }
+        }
+
+        if let Some(grab) = self.input.cursor_change() {
+            with_igc(context.graphics_context, |igc| {
+                if grab {
+                    let _ = igc.window.set_cursor_grab(fyrox::window::CursorGrabMode::Confined)
+                        // Some platforms have no support for cursor grab.
+                        .or_else(|_e| igc.window.set_cursor_grab(fyrox::window::CursorGrabMode::Locked));
+                }
+                else {
+                    let _ = igc.window.set_cursor_grab(fyrox::window::CursorGrabMode::None);
+                }
+                igc.window.set_cursor_visible(!grab);
+            });
+        }
+
+        // Run UI updates, prompting for the player's gamepad while it's disconnected, to respawn once dead, and to
+        // interact with whatever the player focuses.
+        self.ui.reconnectprompt_set(self.input.devices().awaiting_reconnect(), context);
+        let death = input::InputContext::new(input::InputContextKind::Death);
+        let respawn = (self.input.top() == input::InputContextKind::Death)
+            .then(|| death.bindings(&self.settings.input.bindings));
+        self.ui.respawnprompt_set(respawn, &self.input, context);
+        let interact = context.scenes.try_get(self.scene)
+            .and_then(|scene| {
+                let focus = scene.graph.try_get_script_of::<player::Player>(self.player)?.focus;
+                interaction::interactable(&scene.graph, focus).map(|interactable| interactable.prompt().to_owned())
+            });
+        self.ui.interactprompt_set(interact.as_deref(), &self.input, &self.settings.input.bindings, context);
         self.ui.update(context);
 
         // Retrieve initialized graphics context for updating.
@@ -182,6 +498,9 @@ impl Plugin for Game {
         mut _context: PluginContext,
     ) {
 
+        // Resolve keyboard and mouse input into actions.
+        self.input.on_os_event(_event, &self.settings.input.bindings);
+
         match _event {
 
             Event::NewEvents(cause) => {
@@ -192,6 +511,16 @@ impl Plugin for Game {
                 window_id, event
             }                       => {
 
+                // Resolve on-screen touch controls into actions.
+                if let WindowEvent::Touch(touch) = event {
+                    self.input.on_touch(touch, &self.settings.input.touch, &self.settings.input.bindings);
+                }
+
+                // Keep track of the window's size, for its aspect ratio.
+                if let WindowEvent::Resized(size) = event {
+                    self.window_size = (size.width as f32, size.height as f32);
+                }
+
             },
 
             // TODO: Remove after testing load/save functionality.
@@ -238,13 +567,22 @@ impl Plugin for Game {
         
     }
 
-    #[instrument(skip(_context))]
+    #[instrument(skip(context))]
     fn on_ui_message(
         &mut self,
-        _context: &mut PluginContext,
-        _message: &UiMessage,
+        context: &mut PluginContext,
+        message: &UiMessage,
     ) {
-        // Handle UI events here.
+
+        // Resuming from the core menu hides it, handing input back to gameplay.
+        let resume = self.ui.core_menu.as_ref().map(|core_menu| core_menu.button_resume);
+        if let Some(fyrox::gui::button::ButtonMessage::Click) = message.data() {
+            if resume == Some(message.destination()) {
+                self.pad_paused = false;
+                self.ui.coremenu_set(false, &mut self.input, context);
+            }
+        }
+
     }
 
     #[instrument(skip(_context))]
@@ -275,6 +613,7 @@ impl Plugin for Game {
         // Report and set the scene into `self`.
         info!("Scene ({scene:?}) loaded: {path}", scene = new_scene, path = path.display());
         self.scene = new_scene;
+        self.spawn_player(new_scene, context);
         if let Ok(mut visitor)= Visitor::load_from_memory(data) {
             // TODO: Take `data` and visit `self` fields.
         }
@@ -287,13 +626,43 @@ impl Plugin for Game {
         // TODO: Register scripts here.
         let script = &context.serialization_context.script_constructors;
         script.add::<player::Player>("Player");
+        script.add::<health::Health>("Health");
+        script.add::<checkpoint::Checkpoint>("Checkpoint");
+        script.add::<interaction::Interaction>("Interaction");
+        script.add::<spawn::SpawnPoint>("SpawnPoint");
 
     }
     
     #[instrument]
     fn register_property_editors(&self) -> fyrox::gui::inspector::editors::PropertyEditorDefinitionContainer 
     {
-        fyrox::gui::inspector::editors::PropertyEditorDefinitionContainer::empty()
+        use fyrox::gui::inspector::editors:: {
+            enumeration::EnumPropertyEditorDefinition,
+            inspectable::InspectablePropertyEditorDefinition,
+            PropertyEditorDefinitionContainer,
+        };
+
+        // Let the editor's inspector edit the fields of `Health` and `Interaction` scripts...
+        let container = PropertyEditorDefinitionContainer::empty();
+        container.insert(EnumPropertyEditorDefinition::<health::DeathAction>::new());
+        container.insert(InspectablePropertyEditorDefinition::<health::Lifepoints>::new());
+        container.insert(InspectablePropertyEditorDefinition::<health::damage::Resistances>::new());
+        container.insert(EnumPropertyEditorDefinition::<interaction::InteractAction>::new());
+
+        // ...and the tuning nested in the `Player` script.
+        container.insert(InspectablePropertyEditorDefinition::<player::controller::CharacterController>::new());
+        container.insert(InspectablePropertyEditorDefinition::<player::movement::MovementTuning>::new());
+        container.insert(InspectablePropertyEditorDefinition::<player::stamina::PlayerStamina>::new());
+        container.insert(InspectablePropertyEditorDefinition::<player::health::PlayerHealth>::new());
+        container.insert(EnumPropertyEditorDefinition::<player::perspective::PlayerPerspective>::new());
+        container.insert(InspectablePropertyEditorDefinition::<player::perspective::SpringArm>::new());
+        container.insert(InspectablePropertyEditorDefinition::<player::camera::CameraEffects>::new());
+        container.insert(InspectablePropertyEditorDefinition::<player::perspective::FovTransition>::new());
+        container.insert(InspectablePropertyEditorDefinition::<player::death::DeathFlow>::new());
+        container.insert(InspectablePropertyEditorDefinition::<player::fall::FallDamage>::new());
+        container.insert(InspectablePropertyEditorDefinition::<inventory::Inventory>::new());
+        container.insert(InspectablePropertyEditorDefinition::<player::effects::StatusEffects>::new());
+        container
     }
     
     #[instrument(skip(context, self))]
@@ -311,13 +680,28 @@ impl Plugin for Game {
 
         self.ui = ui::UiSubset::new(&mut context);
 
+        // Read item definitions; without them, nothing can be picked up.
+        match inventory::item::ItemCatalog::load(Path::new(inventory::item::ITEMS_FILEPATH)) {
+            Ok(items)   => {
+                info!("{} items defined", items.len());
+                self.items = items;
+            },
+            Err(why)    => error!("Unable to read items from {}: {}", inventory::item::ITEMS_FILEPATH, why),
+        }
+
         // Attempt to retrieve gamepads from the system.
         if let Ok(gilrs) = gilrs::Gilrs::new() {
             
-            // ? Indicate what gamepads are connected.
-            for (each_gamepad_id, _each_gamepad) in gilrs.gamepads() {
+            // ? Indicate what gamepads are connected, and assign them to player slots.
+            for (each_gamepad_id, each_gamepad) in gilrs.gamepads() {
                 info!("connected gamepad: {}", each_gamepad_id);
+                self.input.connect_gamepad(each_gamepad_id);
+                self.input.set_gamepad_family(
+                    each_gamepad_id,
+                    input::ControllerFamily::detect(each_gamepad.vendor_id(), each_gamepad.name())
+                );
             }
+            self.input.take_device_changes();
 
             self.gamepads = Some(gilrs);
 
@@ -0,0 +1,333 @@
+//!
+//! Camera effects.
+//!
+//! Layers of motion on top of where the player is looking: head bob while walking, a field of view kick while
+//! sprinting, shake from trauma dealt by gameplay, and smoothing of look input. Each works out offsets for `Player` to
+//! apply to its camera node, and all of them switch off for players who asked for reduced motion.
+//!
+//! Trauma is a share from `0.0` to `1.0` that gameplay adds to on hits, explosions and such, and which wears off over
+//! time. Shake grows with the square of trauma, so small knocks barely register while big ones rattle.
+//!
+
+use fyrox:: {
+    core:: { algebra::Vector3, visitor::prelude::*, reflect::prelude::* },
+};
+
+
+/// How quickly head bob strength follows changes in speed, as the share of the remaining change made per second.
+const BOB_EASE_SPEED: f32 = 8.0;
+
+
+#[cfg(test)]
+fn bob_extent(effects: &mut CameraEffects, speed: f32, reduced_motion: bool) -> f32 {
+    (0..100)
+        .map(|_| effects.update(0.05, speed, false, true, reduced_motion).position.norm())
+        .fold(0.0, f32::max)
+}
+
+#[test]
+fn test_cameraeffects_head_bob() {
+
+    // Standing still doesn't bob.
+    let mut effects = CameraEffects::default();
+    assert_eq!(bob_extent(&mut effects, 0.0, false), 0.0);
+
+    // Walking bobs, and running bobs more.
+    let walking = bob_extent(&mut CameraEffects::default(), 1.0, false);
+    let running = bob_extent(&mut CameraEffects::default(), 1.5, false);
+    assert!(walking > 0.0);
+    assert!(walking <= effects.head_bob + 1e-4);
+    assert!(running > walking);
+
+    // Leaving the ground settles it.
+    let mut effects = CameraEffects::default();
+    bob_extent(&mut effects, 1.0, false);
+    for _ in 0..100 {
+        effects.update(0.05, 1.0, false, false, false);
+    }
+    assert!(effects.update(0.05, 1.0, false, false, false).position.norm() < 1e-3);
+
+    // Reduced motion doesn't bob at all.
+    assert_eq!(bob_extent(&mut CameraEffects::default(), 1.5, true), 0.0);
+
+}
+
+#[test]
+fn test_cameraeffects_fov_kick() {
+
+    let mut effects = CameraEffects::default();
+
+    // Sprinting widens the field of view by up to `fov_kick`, easing in...
+    let first = effects.update(0.05, 1.5, true, true, false).fov;
+    assert!(first > 0.0 && first < effects.fov_kick);
+    for _ in 0..100 {
+        effects.update(0.05, 1.5, true, true, false);
+    }
+    assert!((effects.update(0.05, 1.5, true, true, false).fov - effects.fov_kick).abs() < 1e-3);
+
+    // ...and back out once stopped.
+    for _ in 0..100 {
+        effects.update(0.05, 0.0, false, true, false);
+    }
+    assert!(effects.update(0.05, 0.0, false, true, false).fov < 1e-3);
+
+    // Reduced motion doesn't kick.
+    let mut effects = CameraEffects::default();
+    assert_eq!(effects.update(0.05, 1.5, true, true, true).fov, 0.0);
+
+}
+
+#[test]
+fn test_cameraeffects_trauma_shake() {
+
+    let mut effects = CameraEffects::default();
+
+    // Without trauma, nothing shakes.
+    let calm = effects.update(0.05, 0.0, false, true, false);
+    assert_eq!((calm.pitch, calm.yaw, calm.roll), (0.0, 0.0, 0.0));
+
+    // Trauma adds up to a whole, and shakes by no more than `shake_angle`.
+    effects.add_trauma(0.6);
+    effects.add_trauma(0.6);
+    assert_eq!(effects.trauma(), 1.0);
+    let shaken = (0..20)
+        .map(|_| effects.update(0.01, 0.0, false, true, false))
+        .map(|offsets| offsets.pitch.abs().max(offsets.yaw.abs()).max(offsets.roll.abs()))
+        .fold(0.0, f32::max);
+    assert!(shaken > 0.0 && shaken <= effects.shake_angle);
+
+    // It wears off at `trauma_decay` per second.
+    for _ in 0..10 {
+        effects.update(0.1, 0.0, false, true, false);
+    }
+    assert_eq!(effects.trauma(), 0.0);
+
+    // Reduced motion never shakes, though trauma is still kept track of.
+    effects.add_trauma(1.0);
+    let still = effects.update(0.01, 0.0, false, true, true);
+    assert_eq!((still.pitch, still.yaw, still.roll), (0.0, 0.0, 0.0));
+    assert!(effects.trauma() > 0.0);
+
+}
+
+#[test]
+fn test_cameraeffects_look_smoothing() {
+
+    // Without smoothing, looking goes straight where it's asked.
+    let mut effects = CameraEffects::default();
+    assert_eq!(effects.look(0.05, 10.0, 90.0, false), (10.0, 90.0));
+
+    // With smoothing, it eases towards it...
+    let mut effects = CameraEffects { look_smoothing: 0.1, ..Default::default() };
+    effects.look(0.05, 0.0, 0.0, false);
+    let (pitch, yaw) = effects.look(0.05, 10.0, 90.0, false);
+    assert!(pitch > 0.0 && pitch < 10.0);
+    assert!(yaw > 0.0 && yaw < 90.0);
+    for _ in 0..100 {
+        effects.look(0.05, 10.0, 90.0, false);
+    }
+    let (pitch, yaw) = effects.look(0.05, 10.0, 90.0, false);
+    assert!((pitch - 10.0).abs() < 1e-3 && (yaw - 90.0).abs() < 1e-3);
+
+    // ...unless motion is reduced.
+    assert_eq!(effects.look(0.05, -45.0, 0.0, true), (-45.0, 0.0));
+
+}
+
+
+/// Offsets for the camera from its place and angles without effects.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct CameraOffsets {
+
+    /// Moved by, in metres, relative to the camera's parent.
+    pub position:   Vector3<f32>,
+
+    /// Turned up or down by, in degrees.
+    pub pitch:      f32,
+
+    /// Turned left or right by, in degrees.
+    pub yaw:        f32,
+
+    /// Tilted by, in degrees.
+    pub roll:       f32,
+
+    /// Field of view widened by, in degrees.
+    pub fov:        f32,
+
+}
+
+/// Camera effect parameters and state carried between update ticks.
+#[derive(Visit, Reflect, Debug, Clone)]
+#[visit(optional)]
+pub struct CameraEffects {
+
+    /// Height of head bob at walking speed, in metres. Zero switches it off.
+    pub head_bob:           f32,
+
+    /// Distance covered per step, in metres; head bob bobs once a step and sways once every two.
+    pub head_bob_stride:    f32,
+
+    /// Field of view widening while sprinting, in degrees. Zero switches it off.
+    pub fov_kick:           f32,
+
+    /// How quickly the field of view kick eases in and out, as the share of the remaining change made per second.
+    pub fov_kick_speed:     f32,
+
+    /// Largest angle shaken by at full trauma, in degrees. Zero switches shake off.
+    pub shake_angle:        f32,
+
+    /// How quickly the camera shakes, in shakes per second.
+    pub shake_frequency:    f32,
+
+    /// Trauma wearing off per second.
+    pub trauma_decay:       f32,
+
+    /// Seconds looking takes to catch up with look input. Zero switches smoothing off.
+    pub look_smoothing:     f32,
+
+    /// Distance into the current pair of steps, in radians.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    bob_phase:              f32,
+
+    /// Strength of head bob, eased towards speed as a share of walking speed.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    bob_weight:             f32,
+
+    /// Current field of view kick, in degrees.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    kick:                   f32,
+
+    /// Current trauma, from `0.0` to `1.0`.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    trauma:                 f32,
+
+    /// Seconds of shake so far, to sample its noise at.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    time:                   f32,
+
+    /// Smoothed pitch and yaw, in degrees, once looking has started.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    look:                   Option<(f32, f32)>,
+
+}
+
+impl CameraEffects {
+
+    /// Advance by `dt` seconds at `speed`, as a share of walking speed, while `sprinting` or not and `grounded` or
+    /// not. Returns the offsets to apply to the camera.
+    ///
+    /// With `reduced_motion`, nothing bobs, kicks or shakes, and the offsets are all zero.
+    pub fn update(
+        &mut self,
+        dt: f32,
+        speed: f32,
+        sprinting: bool,
+        grounded: bool,
+        reduced_motion: bool
+    ) -> CameraOffsets {
+
+        self.trauma = (self.trauma - self.trauma_decay * dt).max(0.0);
+        self.time += dt;
+
+        if reduced_motion {
+            self.bob_weight = 0.0;
+            self.kick = 0.0;
+            return CameraOffsets::default();
+        }
+
+        // Head bob eases in and out with speed, and is stepped through by distance covered.
+        let speed = speed.max(0.0);
+        let bob_target = if grounded { speed } else { 0.0 };
+        self.bob_weight += (bob_target - self.bob_weight) * ease(BOB_EASE_SPEED, dt);
+        if self.head_bob_stride > 0.0 {
+            self.bob_phase = (self.bob_phase + speed * dt / self.head_bob_stride * std::f32::consts::PI)
+                % std::f32::consts::TAU;
+        }
+        let bob = self.head_bob * self.bob_weight;
+        let position = Vector3::new(
+            bob * 0.5 * self.bob_phase.sin(),
+            bob * (self.bob_phase.sin().abs() - 0.5),
+            0.0
+        );
+
+        // Sprinting kicks the field of view out, easing in and out.
+        let kick_target = if sprinting { self.fov_kick } else { 0.0 };
+        self.kick += (kick_target - self.kick) * ease(self.fov_kick_speed, dt);
+
+        // Shake by the square of trauma, along noise that differs per axis.
+        let shake = self.shake_angle * self.trauma * self.trauma;
+        let noise = |seed: f32| {
+            let t = self.time * self.shake_frequency + seed;
+            (t.sin() * 0.5 + (t * 2.3).sin() * 0.3 + (t * 4.7).sin() * 0.2).clamp(-1.0, 1.0)
+        };
+
+        CameraOffsets {
+            position:   if bob > 0.0 { position } else { Vector3::zeros() },
+            pitch:      shake * noise(0.0),
+            yaw:        shake * noise(17.0),
+            roll:       shake * noise(41.0),
+            fov:        self.kick,
+        }
+
+    }
+
+    /// Smoothed `pitch` and `yaw` to look at after `dt` seconds, easing towards those asked for by look input.
+    ///
+    /// With `reduced_motion` or no `look_smoothing`, looks straight where asked.
+    pub fn look(&mut self, dt: f32, pitch: f32, yaw: f32, reduced_motion: bool) -> (f32, f32) {
+
+        let look = match self.look {
+            Some((from_pitch, from_yaw)) if !reduced_motion && self.look_smoothing > 0.0 => {
+                let share = 1.0 - (-dt / self.look_smoothing).exp();
+                (from_pitch + (pitch - from_pitch) * share, from_yaw + (yaw - from_yaw) * share)
+            },
+            _ => (pitch, yaw),
+        };
+        self.look = Some(look);
+        look
+
+    }
+
+    /// Add `amount` of trauma, shaking the camera. Trauma adds up to no more than `1.0`.
+    pub fn add_trauma(&mut self, amount: f32) {
+        self.trauma = (self.trauma + amount.max(0.0)).min(1.0);
+    }
+
+    /// Current trauma, from `0.0` to `1.0`.
+    pub fn trauma(&self) -> f32 {
+        self.trauma
+    }
+
+}
+
+impl Default for CameraEffects {
+    fn default() -> Self {
+        CameraEffects {
+            head_bob:           0.04,
+            head_bob_stride:    1.6,
+            fov_kick:           8.0,
+            fov_kick_speed:     8.0,
+            shake_angle:        4.0,
+            shake_frequency:    25.0,
+            trauma_decay:       1.0,
+            look_smoothing:     0.0,
+            bob_phase:          0.0,
+            bob_weight:         0.0,
+            kick:               0.0,
+            trauma:             0.0,
+            time:               0.0,
+            look:               None,
+        }
+    }
+}
+
+// Share of the remaining change made over `dt` seconds at `rate` per second.
+fn ease(rate: f32, dt: f32) -> f32 {
+    1.0 - (-rate.max(0.0) * dt).exp()
+}
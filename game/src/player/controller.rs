@@ -0,0 +1,337 @@
+//!
+//! Character controller.
+//!
+//! Decides how the player's body moves vertically and what stops it horizontally, given what casts from the body
+//! found around it: ground beneath the feet, and obstacles ahead at the feet and at step height. Casting is left to
+//! `Player`, so everything here is arithmetic on the hits and can be tested without a physics world.
+//!
+
+use fyrox:: {
+    core:: { algebra::Vector3, visitor::prelude::*, reflect::prelude::* },
+};
+
+
+
+#[test]
+fn test_charactercontroller_ground_and_gravity() {
+
+    let mut controller = CharacterController::default();
+    let flat = Some(GroundHit { distance: 0.0, normal: Vector3::y() });
+
+    // Standing on flat ground doesn't fall.
+    assert_eq!(controller.update(0.1, flat, false), 0.0);
+    assert!(controller.is_grounded());
+
+    // Ground slightly below the feet is snapped down onto.
+    let below = Some(GroundHit { distance: 0.05, normal: Vector3::y() });
+    assert!((controller.update(0.1, below, false) - -0.5).abs() < 1e-4);
+    assert!(controller.is_grounded());
+
+    // Without ground, speed builds with gravity up to the terminal speed.
+    assert!((controller.update(0.1, None, false) - -controller.gravity * 0.1).abs() < 1e-4);
+    assert!(!controller.is_grounded());
+    for _ in 0..100 {
+        controller.update(0.1, None, false);
+    }
+    assert_eq!(controller.update(0.1, None, false), -controller.terminal_speed);
+
+    // Slopes steeper than the limit aren't ground.
+    let mut controller = CharacterController::default();
+    let steep = Vector3::new(1.0, 0.5, 0.0).normalize();
+    assert!(!controller.walkable(&steep));
+    controller.update(0.1, Some(GroundHit { distance: 0.0, normal: steep }), false);
+    assert!(!controller.is_grounded());
+
+}
+
+#[test]
+fn test_charactercontroller_jump_and_coyote_time() {
+
+    let flat = Some(GroundHit { distance: 0.0, normal: Vector3::y() });
+
+    // Jumping from the ground leaves it at the jump speed, once per press.
+    let mut controller = CharacterController::default();
+    controller.update(0.1, flat, false);
+    assert_eq!(controller.update(0.1, flat, true), controller.jump_speed);
+    assert!(!controller.is_grounded());
+    assert!(controller.update(0.1, flat, true) < controller.jump_speed);
+
+    // Landing while still holding jump doesn't jump again.
+    let mut controller = CharacterController::default();
+    assert_eq!(controller.update(0.1, flat, true), controller.jump_speed);
+    for _ in 0..10 {
+        controller.update(0.1, None, true);
+    }
+    assert_eq!(controller.update(0.1, flat, true), 0.0);
+    assert!(controller.is_grounded());
+
+    // Just after walking off a ledge jumps still count...
+    let mut controller = CharacterController::default();
+    controller.update(0.1, flat, false);
+    controller.update(controller.coyote_time * 0.5, None, false);
+    assert_eq!(controller.update(0.0, None, true), controller.jump_speed);
+
+    // ...but not twice, and not once coyote time is over.
+    controller.update(0.1, None, false);
+    assert!(controller.update(0.1, None, true) < controller.jump_speed);
+
+    let mut controller = CharacterController::default();
+    controller.update(0.1, flat, false);
+    controller.update(controller.coyote_time * 2.0, None, false);
+    assert!(controller.update(0.0, None, true) < 0.0);
+
+}
+
+#[test]
+fn test_charactercontroller_landing() {
+
+    let mut controller = CharacterController::default();
+    let flat = Some(GroundHit { distance: 0.0, normal: Vector3::y() });
+
+    // Standing around never lands.
+    controller.update(0.1, flat, false);
+    assert_eq!(controller.landing(), None);
+    controller.update(0.1, flat, false);
+    assert_eq!(controller.landing(), None);
+
+    // Falling lands at the speed it was falling at, on the tick it touches down only.
+    for _ in 0..10 {
+        controller.update(0.1, None, false);
+        assert_eq!(controller.landing(), None);
+    }
+    let falling = -controller.vertical_speed();
+    controller.update(0.1, flat, false);
+    assert_eq!(controller.landing(), Some(falling));
+    controller.update(0.1, flat, false);
+    assert_eq!(controller.landing(), None);
+
+}
+
+#[test]
+fn test_charactercontroller_obstacles() {
+
+    let controller = CharacterController::default();
+    let wall = Some(Vector3::new(0.0, 0.0, -1.0));
+    let ramp = Some(Vector3::new(0.0, 2.0, -1.0).normalize());
+
+    // Nothing ahead, or a walkable ramp, doesn't stop movement.
+    assert_eq!(controller.obstacle(None, None, true), Obstacle::None);
+    assert_eq!(controller.obstacle(ramp, None, true), Obstacle::None);
+
+    // Low ledges are stepped onto from the ground, and block in the air.
+    assert_eq!(controller.obstacle(wall, None, true), Obstacle::Step);
+    assert_eq!(controller.obstacle(wall, None, false), Obstacle::Wall);
+
+    // Anything tall blocks.
+    assert_eq!(controller.obstacle(wall, wall, true), Obstacle::Wall);
+    assert_eq!(controller.obstacle(None, wall, true), Obstacle::Wall);
+
+}
+
+
+/// Ground found beneath the character.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GroundHit {
+
+    /// Distance from the character's feet down to the ground.
+    pub distance:   f32,
+
+    /// Surface normal of the ground, of unit length.
+    pub normal:     Vector3<f32>,
+
+}
+
+/// What an obstacle ahead does to movement towards it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Obstacle {
+
+    /// Nothing in the way.
+    None,
+
+    /// A ledge low enough to step up onto.
+    Step,
+
+    /// Blocks movement.
+    Wall,
+
+}
+
+/// Character controller parameters and state carried between update ticks.
+#[derive(Visit, Reflect, Debug, Clone)]
+#[visit(optional)]
+pub struct CharacterController {
+
+    /// Downward acceleration while airborne, in metres per second squared.
+    pub gravity:            f32,
+
+    /// Fastest fall, in metres per second.
+    pub terminal_speed:     f32,
+
+    /// Upward speed when jumping, in metres per second.
+    pub jump_speed:         f32,
+
+    /// Seconds after walking off a ledge during which jumping still works.
+    pub coyote_time:        f32,
+
+    /// Tallest ledge stepped up onto without jumping, in metres.
+    pub step_height:        f32,
+
+    /// Steepest slope that can be stood on, in degrees. Steeper surfaces are slid down and block like walls.
+    pub max_slope:          f32,
+
+    /// How far below the feet ground is still stood on, in metres.
+    pub ground_distance:    f32,
+
+    /// How far beyond the body obstacles block movement, in metres.
+    pub wall_distance:      f32,
+
+    /// Standing on walkable ground.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    grounded:               bool,
+
+    /// Seconds since last standing on the ground.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    airborne:               f32,
+
+    /// Jumped since last standing on the ground.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    jumped:                 bool,
+
+    /// Jump was held on the last update tick.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    jump_held:              bool,
+
+    /// Vertical speed, in metres per second.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    vertical_speed:         f32,
+
+    /// Downward speed landed at on the last update tick, in metres per second.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    landing:                Option<f32>,
+
+}
+
+impl CharacterController {
+
+    /// Advance by `dt` seconds with `ground` beneath the feet, if any, while `jump` is held or not. Returns the
+    /// vertical speed to move the body at, in metres per second.
+    ///
+    /// Ground counts while it's walkable and within `ground_distance`, and the body is snapped down onto it. Off the
+    /// ground, gravity applies. Jumps start on the press, from the ground or within `coyote_time` of leaving it.
+    pub fn update(&mut self, dt: f32, ground: Option<GroundHit>, jump: bool) -> f32 {
+
+        let pressed = jump && !self.jump_held;
+        self.jump_held = jump;
+
+        let ground = ground.filter(|ground| {
+            self.vertical_speed <= 0.0 && ground.distance <= self.ground_distance && self.walkable(&ground.normal)
+        });
+
+        if ground.is_some() {
+            self.landing = Some(-self.vertical_speed).filter(|speed| !self.grounded && *speed > 0.0);
+            self.grounded = true;
+            self.airborne = 0.0;
+            self.jumped = false;
+            self.vertical_speed = 0.0;
+        }
+        else {
+            self.landing = None;
+            self.grounded = false;
+            self.airborne += dt;
+            self.vertical_speed = (self.vertical_speed - self.gravity * dt).max(-self.terminal_speed);
+        }
+
+        if pressed && self.can_jump() {
+            self.grounded = false;
+            self.jumped = true;
+            self.vertical_speed = self.jump_speed;
+            return self.vertical_speed;
+        }
+
+        match ground {
+            Some(ground) if dt > 0.0    => -ground.distance.max(0.0) / dt,
+            _                           => self.vertical_speed,
+        }
+
+    }
+
+    /// Forget the state carried between update ticks, as after the character was moved somewhere else.
+    pub fn reset(&mut self) {
+        self.grounded = false;
+        self.airborne = 0.0;
+        self.jumped = false;
+        self.vertical_speed = 0.0;
+        self.landing = None;
+    }
+
+    /// Returns `true` if a jump would start now.
+    pub fn can_jump(&self) -> bool {
+        !self.jumped && (self.grounded || self.airborne <= self.coyote_time)
+    }
+
+    /// Returns `true` while standing on walkable ground.
+    pub fn is_grounded(&self) -> bool {
+        self.grounded
+    }
+
+    /// Vertical speed, in metres per second.
+    pub fn vertical_speed(&self) -> f32 {
+        self.vertical_speed
+    }
+
+    /// Downward speed the character landed at on the last update tick, in metres per second, if it landed then.
+    pub fn landing(&self) -> Option<f32> {
+        self.landing
+    }
+
+    /// Returns `true` if a surface with unit `normal` is gentle enough to stand on.
+    pub fn walkable(&self, normal: &Vector3<f32>) -> bool {
+        normal.y >= self.max_slope.to_radians().cos()
+    }
+
+    /// What an obstacle ahead does to movement, given surface normals hit by casts ahead at the feet and at
+    /// `step_height`, and whether the character is `grounded`.
+    pub fn obstacle(&self, feet: Option<Vector3<f32>>, step: Option<Vector3<f32>>, grounded: bool) -> Obstacle {
+
+        let blocking = |normal: Option<Vector3<f32>>| normal.is_some_and(|normal| !self.walkable(&normal));
+
+        if blocking(step) {
+            Obstacle::Wall
+        }
+        else if blocking(feet) {
+            if grounded { Obstacle::Step } else { Obstacle::Wall }
+        }
+        else {
+            Obstacle::None
+        }
+
+    }
+
+}
+
+impl Default for CharacterController {
+    fn default() -> Self {
+        CharacterController {
+            gravity:            20.0,
+            terminal_speed:     50.0,
+            jump_speed:         6.0,
+            coyote_time:        0.12,
+            step_height:        0.25,
+            max_slope:          45.0,
+            ground_distance:    0.08,
+            wall_distance:      0.05,
+            grounded:           false,
+            airborne:           0.0,
+            jumped:             false,
+            jump_held:          false,
+            vertical_speed:     0.0,
+            landing:            None,
+        }
+    }
+}
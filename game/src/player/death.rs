@@ -0,0 +1,212 @@
+//!
+//! Death and respawning.
+//!
+//! When the player's lifepoints run out, they're *dying* for `dying_time` seconds while the death state plays out,
+//! then *dead* until they ask to respawn. Respawning restores health and stamina, less any penalties for dying.
+//! Where they respawn, and how input and prompts follow along, is up to `Player`.
+//!
+
+use fyrox:: {
+    core:: { visitor::prelude::*, reflect::prelude::* },
+};
+use super:: { health::PlayerHealth, stamina::PlayerStamina };
+
+
+
+#[test]
+fn test_deathflow_phases() {
+
+    let mut death = DeathFlow::default();
+    assert_eq!(death.phase(), DeathPhase::Alive);
+    assert_eq!(death.progress(), None);
+
+    // Respawning needs dying first.
+    assert!(!death.respawn());
+
+    // Dying plays out over `dying_time`, and dying again doesn't start it over...
+    death.die();
+    assert!(!death.update(death.dying_time * 0.5));
+    death.die();
+    assert_eq!(death.progress(), Some(0.5));
+    assert!(!death.respawn());
+
+    // ...then the player is dead, once, until they respawn.
+    assert!(death.update(death.dying_time * 0.5));
+    assert_eq!(death.phase(), DeathPhase::Dead);
+    assert!(!death.update(1.0));
+    assert_eq!(death.progress(), Some(1.0));
+    assert!(death.respawn());
+    assert_eq!(death.phase(), DeathPhase::Alive);
+
+}
+
+#[test]
+fn test_deathflow_penalties() {
+
+    let mut health = PlayerHealth::default();
+    let mut stamina = PlayerStamina::default();
+    let death = DeathFlow { health_restored: 0.5, capacity_penalty: 30, capacity_floor: 10, ..Default::default() };
+
+    // Respawning takes capacity as a penalty, and restores a share of what's left.
+    health.pool.lifepoints_sub(u16::MAX);
+    stamina.staminapoints_drain(u16::MAX);
+    death.penalise(&mut health, &mut stamina);
+    assert_eq!(*health.capacity(), 20);
+    assert_eq!(*health.lifepoints(), 10);
+    assert_eq!(stamina.staminapoints, 100);
+    assert!(!stamina.is_exhausted());
+
+    // Capacity never drops below the floor, and there's always at least a lifepoint.
+    let death = DeathFlow { health_restored: 0.0, ..death };
+    health.pool.lifepoints_sub(u16::MAX);
+    death.penalise(&mut health, &mut stamina);
+    assert_eq!(*health.capacity(), 10);
+    assert_eq!(*health.lifepoints(), 1);
+    assert!(health.is_alive());
+
+}
+
+#[test]
+fn test_deathflow_follows_health() {
+
+    let mut health = PlayerHealth::default();
+    let mut death = DeathFlow::default();
+
+    // Living players stay alive...
+    death.follow(&health);
+    assert_eq!(death.phase(), DeathPhase::Alive);
+
+    // ...but those out of lifepoints start dying, even without having died, as when loaded from a save made while
+    // dying or dead.
+    health.pool.lifepoints_sub(u16::MAX);
+    death.follow(&health);
+    assert_eq!(death.progress(), Some(0.0));
+    assert!(death.update(death.dying_time));
+    assert!(death.respawn());
+
+}
+
+
+/// Where the player is in dying and respawning.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DeathPhase {
+
+    /// Not dead.
+    Alive,
+
+    /// The death state is playing, for `elapsed` seconds so far.
+    Dying { elapsed: f32 },
+
+    /// Waiting for the player to respawn.
+    Dead,
+
+}
+
+/// Death and respawn parameters, and the phase carried between update ticks.
+#[derive(Visit, Reflect, Debug, Clone)]
+#[visit(optional)]
+pub struct DeathFlow {
+
+    /// Seconds the death state plays for before the player may respawn.
+    pub dying_time:         f32,
+
+    /// Share of capacity lifepoints are restored to on respawning, from `0.0` (a single lifepoint) to `1.0`.
+    pub health_restored:    f32,
+
+    /// Share of capacity stamina is restored to on respawning, from `0.0` to `1.0`.
+    pub stamina_restored:   f32,
+
+    /// Health capacity lost each time the player dies.
+    pub capacity_penalty:   u16,
+
+    /// Health capacity the penalty never takes the player below.
+    pub capacity_floor:     u16,
+
+    /// Current phase.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    phase:                  DeathPhase,
+
+}
+
+impl DeathFlow {
+
+    /// Start dying, if alive.
+    pub fn die(&mut self) {
+        if self.phase == DeathPhase::Alive {
+            self.phase = DeathPhase::Dying { elapsed: 0.0 };
+        }
+    }
+
+    /// Start dying if `health` has run out while alive. The phase isn't saved, so this picks dying back up after
+    /// loading a game saved while dying or dead.
+    pub fn follow(&mut self, health: &PlayerHealth) {
+        if health.is_dead() {
+            self.die();
+        }
+    }
+
+    /// Advance by `dt` seconds. Returns `true` on the tick dying finishes and the player is dead.
+    pub fn update(&mut self, dt: f32) -> bool {
+        if let DeathPhase::Dying { elapsed } = self.phase {
+            let elapsed = elapsed + dt;
+            if elapsed >= self.dying_time {
+                self.phase = DeathPhase::Dead;
+                return true;
+            }
+            self.phase = DeathPhase::Dying { elapsed };
+        }
+        false
+    }
+
+    /// Respawn, if dead. Returns `true` if the player should respawn now.
+    pub fn respawn(&mut self) -> bool {
+        if self.phase == DeathPhase::Dead {
+            self.phase = DeathPhase::Alive;
+            true
+        }
+        else {
+            false
+        }
+    }
+
+    pub fn phase(&self) -> DeathPhase {
+        self.phase
+    }
+
+    /// How far the death state has played, from `0.0` to `1.0`, or `None` while alive.
+    pub fn progress(&self) -> Option<f32> {
+        match self.phase {
+            DeathPhase::Alive               => None,
+            DeathPhase::Dying { elapsed }   => Some((elapsed / self.dying_time.max(f32::EPSILON)).min(1.0)),
+            DeathPhase::Dead                => Some(1.0),
+        }
+    }
+
+    /// Apply the penalties for dying to `health`, then restore it and `stamina` as configured.
+    pub fn penalise(&self, health: &mut PlayerHealth, stamina: &mut PlayerStamina) {
+
+        let capacity = health.capacity().saturating_sub(self.capacity_penalty).max(self.capacity_floor);
+        let _ = health.capacity_change(capacity.max(1));
+        health.revive(self.health_restored);
+
+        let restored = *stamina.capacity() as f32 * self.stamina_restored.clamp(0.0, 1.0);
+        stamina.staminapoints_drain(u16::MAX);
+        stamina.staminapoints_regen(restored.round() as u16);
+
+    }
+
+}
+
+impl Default for DeathFlow {
+    fn default() -> Self {
+        DeathFlow {
+            dying_time:         2.0,
+            health_restored:    1.0,
+            stamina_restored:   1.0,
+            capacity_penalty:   0,
+            capacity_floor:     10,
+            phase:              DeathPhase::Alive,
+        }
+    }
+}
@@ -0,0 +1,346 @@
+//!
+//! Status effects.
+//!
+//! Poison, regeneration, slow and haste are timed modifiers on the player. Poison and regeneration hurt and heal by
+//! their magnitude each tick, every `tick_interval` seconds; slow and haste scale movement speed while they last.
+//! Applying an effect that's already active follows the application's `Stacking` rule. Active effects are saved
+//! along with the player, time left and all.
+//!
+
+use fyrox:: {
+    core:: { visitor::prelude::*, reflect::prelude::* },
+};
+
+
+
+#[test]
+fn test_statuseffects_stacking() {
+
+    let mut effects = StatusEffects { max_stacks: 3, ..Default::default() };
+
+    // Stacking adds stacks up to the limit, lasting as long as the longest application...
+    assert!(effects.apply(StatusEffect::new(StatusKind::Poison, 1.0, 5.0, Stacking::Stack)));
+    assert!(effects.apply(StatusEffect::new(StatusKind::Poison, 1.0, 2.0, Stacking::Stack)));
+    assert_eq!(effects.get(StatusKind::Poison).map(|poison| (poison.stacks(), poison.remaining())), Some((2, 5.0)));
+    effects.apply(StatusEffect::new(StatusKind::Poison, 1.0, 8.0, Stacking::Stack));
+    effects.apply(StatusEffect::new(StatusKind::Poison, 1.0, 8.0, Stacking::Stack));
+    assert_eq!(effects.get(StatusKind::Poison).map(|poison| (poison.stacks(), poison.remaining())), Some((3, 8.0)));
+
+    // ...refreshing starts it over at the stronger magnitude...
+    effects.update(3.0);
+    assert!(effects.apply(StatusEffect::new(StatusKind::Poison, 2.0, 4.0, Stacking::Refresh)));
+    let poison = effects.get(StatusKind::Poison).unwrap();
+    assert_eq!((poison.stacks(), poison.remaining(), poison.magnitude), (3, 4.0, 2.0));
+
+    // ...and ignoring leaves it be.
+    assert!(!effects.apply(StatusEffect::new(StatusKind::Poison, 9.0, 9.0, Stacking::Ignore)));
+    assert_eq!(effects.get(StatusKind::Poison).unwrap().magnitude, 2.0);
+
+    // Effects of other kinds apply alongside, whatever their rule.
+    assert!(effects.apply(StatusEffect::haste(0.5, 1.0)));
+    assert!(effects.is_active(StatusKind::Haste));
+    effects.clear();
+    assert!(!effects.is_active(StatusKind::Poison));
+
+}
+
+#[test]
+fn test_statuseffects_ticks() {
+
+    let mut effects = StatusEffects::default();
+
+    // Poison hurts by its magnitude per stack each tick, for as long as it lasts...
+    effects.apply(StatusEffect::poison(2.0, 3.0));
+    effects.apply(StatusEffect::poison(2.0, 3.0));
+    let mut damage = 0;
+    for _ in 0..8 {
+        damage += effects.update(0.5).damage;
+    }
+    assert_eq!(damage, 12);
+    assert!(!effects.is_active(StatusKind::Poison));
+
+    // ...while regeneration heals, carrying fractions of lifepoints between ticks.
+    effects.apply(StatusEffect::regeneration(0.5, 10.0));
+    assert_eq!(effects.update(0.75), StatusTick::default());
+    assert_eq!(effects.update(0.25), StatusTick::default());
+    assert_eq!(effects.update(1.0), StatusTick { damage: 0, heal: 1 });
+
+}
+
+#[test]
+fn test_statuseffects_speed() {
+
+    let mut effects = StatusEffects::default();
+    assert_eq!(effects.speed_multiplier(), 1.0);
+
+    // Slow and haste scale speed together, never slower than the floor...
+    effects.apply(StatusEffect::slow(0.5, 2.0));
+    assert_eq!(effects.speed_multiplier(), 0.5);
+    effects.apply(StatusEffect::haste(0.5, 1.0));
+    assert_eq!(effects.speed_multiplier(), 0.75);
+    effects.apply(StatusEffect::new(StatusKind::Slow, 1.0, 2.0, Stacking::Refresh));
+    assert_eq!(effects.speed_multiplier(), effects.slowest);
+
+    // ...until they wear off.
+    effects.update(1.0);
+    assert_eq!(effects.speed_multiplier(), effects.slowest);
+    effects.update(1.0);
+    assert_eq!(effects.speed_multiplier(), 1.0);
+
+}
+
+
+/// Kinds of status effect.
+#[derive(Visit, Reflect, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StatusKind {
+
+    /// Deals its magnitude in poison damage each tick.
+    #[default]
+    Poison,
+
+    /// Heals its magnitude in lifepoints each tick.
+    Regeneration,
+
+    /// Slows movement by its magnitude, as a share of speed.
+    Slow,
+
+    /// Speeds movement up by its magnitude, as a share of speed.
+    Haste,
+
+}
+
+/// What applying a status effect that's already active does.
+#[derive(Visit, Reflect, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Stacking {
+
+    /// Start it over, at the stronger of the two magnitudes.
+    #[default]
+    Refresh,
+
+    /// Add a stack, multiplying its magnitude, and last as long as the longer of the two.
+    Stack,
+
+    /// Leave it be.
+    Ignore,
+
+}
+
+/// A status effect, as applied or active.
+#[derive(Visit, Reflect, Debug, Clone, PartialEq, Default)]
+#[visit(optional)]
+pub struct StatusEffect {
+
+    pub kind:       StatusKind,
+
+    /// Lifepoints per tick for poison and regeneration; share of speed for slow and haste.
+    pub magnitude:  f32,
+
+    /// Seconds it lasts.
+    pub duration:   f32,
+
+    /// What applying it does while it's already active.
+    pub stacking:   Stacking,
+
+    /// Times it's been stacked.
+    #[reflect(hidden)]
+    stacks:         u32,
+
+    /// Seconds left.
+    #[reflect(hidden)]
+    remaining:      f32,
+
+    /// Seconds since the last tick.
+    #[reflect(hidden)]
+    elapsed:        f32,
+
+    /// Fraction of a lifepoint carried over to the next tick.
+    #[reflect(hidden)]
+    carry:          f32,
+
+}
+
+impl StatusEffect {
+
+    pub fn new(kind: StatusKind, magnitude: f32, duration: f32, stacking: Stacking) -> Self {
+        StatusEffect { kind, magnitude, duration, stacking, stacks: 1, remaining: duration, elapsed: 0.0, carry: 0.0 }
+    }
+
+    /// Poison dealing `lifepoints` per tick for `duration` seconds, stacking.
+    pub fn poison(lifepoints: f32, duration: f32) -> Self {
+        Self::new(StatusKind::Poison, lifepoints, duration, Stacking::Stack)
+    }
+
+    /// Regeneration healing `lifepoints` per tick for `duration` seconds, refreshing.
+    pub fn regeneration(lifepoints: f32, duration: f32) -> Self {
+        Self::new(StatusKind::Regeneration, lifepoints, duration, Stacking::Refresh)
+    }
+
+    /// Slow taking `share` off speed for `duration` seconds, refreshing.
+    pub fn slow(share: f32, duration: f32) -> Self {
+        Self::new(StatusKind::Slow, share, duration, Stacking::Refresh)
+    }
+
+    /// Haste adding `share` to speed for `duration` seconds, refreshing.
+    pub fn haste(share: f32, duration: f32) -> Self {
+        Self::new(StatusKind::Haste, share, duration, Stacking::Refresh)
+    }
+
+    pub fn stacks(&self) -> u32 {
+        self.stacks
+    }
+
+    /// Seconds left.
+    pub fn remaining(&self) -> f32 {
+        self.remaining
+    }
+
+    /// Magnitude of all its stacks together.
+    pub fn strength(&self) -> f32 {
+        self.magnitude * self.stacks as f32
+    }
+
+    // Advance by `dt` seconds. Returns the ticks every `interval` seconds that fell within them.
+    fn advance(&mut self, dt: f32, interval: f32) -> u32 {
+        self.elapsed += dt.min(self.remaining.max(0.0));
+        self.remaining -= dt;
+        if interval <= 0.0 {
+            return 0;
+        }
+        let ticks = (self.elapsed / interval).floor();
+        self.elapsed -= ticks * interval;
+        ticks as u32
+    }
+
+}
+
+/// Lifepoints status effects dealt and healed over an update.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct StatusTick {
+    pub damage: u16,
+    pub heal:   u16,
+}
+
+/// Status effects active on the player, and how they tick and combine.
+#[derive(Visit, Reflect, Debug, Clone)]
+#[visit(optional)]
+pub struct StatusEffects {
+
+    /// Seconds between poison and regeneration ticks.
+    pub tick_interval:  f32,
+
+    /// Most stacks an effect builds up to.
+    pub max_stacks:     u32,
+
+    /// Slowest effects make movement, as a share of speed.
+    pub slowest:        f32,
+
+    /// Active effects, at most one of each kind.
+    #[reflect(hidden)]
+    active:             Vec<StatusEffect>,
+
+}
+
+impl StatusEffects {
+
+    /// Apply `effect`. If one of its kind is already active, its `stacking` rule decides what happens. Returns
+    /// `false` if it was ignored.
+    pub fn apply(&mut self, effect: StatusEffect) -> bool {
+
+        let Some(active) = self.active.iter_mut().find(|active| active.kind == effect.kind) else {
+            self.active.push(StatusEffect::new(effect.kind, effect.magnitude, effect.duration, effect.stacking));
+            return true;
+        };
+
+        match effect.stacking {
+            Stacking::Refresh   => {
+                active.magnitude = active.magnitude.max(effect.magnitude);
+                active.duration = effect.duration;
+                active.remaining = effect.duration;
+                true
+            },
+            Stacking::Stack     => {
+                active.stacks = (active.stacks + 1).min(self.max_stacks.max(1));
+                active.duration = active.duration.max(effect.duration);
+                active.remaining = active.remaining.max(effect.duration);
+                true
+            },
+            Stacking::Ignore    => false,
+        }
+
+    }
+
+    /// Advance by `dt` seconds, ticking poison and regeneration and wearing effects off. Returns the lifepoints to deal
+    /// and heal.
+    pub fn update(&mut self, dt: f32) -> StatusTick {
+
+        let mut tick = StatusTick::default();
+        for effect in self.active.iter_mut() {
+
+            let ticks = effect.advance(dt, self.tick_interval);
+            if !matches!(effect.kind, StatusKind::Poison | StatusKind::Regeneration) {
+                continue;
+            }
+
+            let amount = effect.strength().max(0.0) * ticks as f32 + effect.carry;
+            let whole = amount.floor();
+            effect.carry = amount - whole;
+            let whole = whole.min(u16::MAX as f32) as u16;
+            match effect.kind {
+                StatusKind::Poison  => tick.damage = tick.damage.saturating_add(whole),
+                _                   => tick.heal = tick.heal.saturating_add(whole),
+            }
+
+        }
+        self.active.retain(|effect| effect.remaining > 0.0);
+        tick
+
+    }
+
+    /// Multiplier on movement speed from slow and haste, no lower than `slowest`.
+    pub fn speed_multiplier(&self) -> f32 {
+        self.active.iter()
+            .map(|effect| match effect.kind {
+                StatusKind::Slow    => (1.0 - effect.strength()).max(0.0),
+                StatusKind::Haste   => 1.0 + effect.strength().max(0.0),
+                _                   => 1.0,
+            })
+            .product::<f32>()
+            .max(self.slowest)
+    }
+
+    /// The active effect of `kind`, if there's one.
+    pub fn get(&self, kind: StatusKind) -> Option<&StatusEffect> {
+        self.active.iter().find(|effect| effect.kind == kind)
+    }
+
+    pub fn is_active(&self, kind: StatusKind) -> bool {
+        self.get(kind).is_some()
+    }
+
+    /// Active effects.
+    pub fn active(&self) -> &[StatusEffect] {
+        &self.active
+    }
+
+    /// End the effect of `kind`, if it's active.
+    pub fn remove(&mut self, kind: StatusKind) {
+        self.active.retain(|effect| effect.kind != kind);
+    }
+
+    /// End every effect.
+    pub fn clear(&mut self) {
+        self.active.clear();
+    }
+
+}
+
+impl Default for StatusEffects {
+    fn default() -> Self {
+        StatusEffects {
+            tick_interval:  1.0,
+            max_stacks:     5,
+            slowest:        0.2,
+            active:         Vec::new(),
+        }
+    }
+}
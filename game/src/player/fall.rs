@@ -0,0 +1,100 @@
+//!
+//! Fall damage.
+//!
+//! Landing faster than a safe speed hurts. Damage grows along a curve from nothing at `safe_speed` to the player's
+//! whole health capacity at `lethal_speed`, and is dealt through `PlayerHealth` as `DamageType::Fall`, so fall
+//! resistance and invulnerability frames apply.
+//!
+
+use fyrox:: {
+    core:: { visitor::prelude::*, reflect::prelude::* },
+};
+
+
+
+#[test]
+fn test_falldamage_thresholds() {
+
+    let fall = FallDamage::default();
+
+    // Landing at or below the safe speed is harmless...
+    assert_eq!(fall.damage(0.0, 100), 0);
+    assert_eq!(fall.damage(fall.safe_speed - 0.01, 100), 0);
+    assert_eq!(fall.damage(fall.safe_speed, 100), 0);
+
+    // ...and just past it hurts, if only a little.
+    assert_eq!(fall.damage(fall.safe_speed + 0.01, 100), 1);
+
+    // At the lethal speed and beyond, landing takes the whole capacity.
+    assert_eq!(fall.damage(fall.lethal_speed - 0.01, 100), 100);
+    assert_eq!(fall.damage(fall.lethal_speed, 100), 100);
+    assert_eq!(fall.damage(fall.lethal_speed * 10.0, 100), 100);
+    assert_eq!(fall.damage(f32::INFINITY, u16::MAX), u16::MAX);
+
+}
+
+#[test]
+fn test_falldamage_curve() {
+
+    // A linear curve is halfway at half the speed between safe and lethal...
+    let linear = FallDamage { safe_speed: 10.0, lethal_speed: 20.0, curve: 1.0 };
+    assert_eq!(linear.damage(15.0, 100), 50);
+
+    // ...while steeper curves forgive short falls more.
+    let steep = FallDamage { curve: 2.0, ..linear };
+    assert_eq!(steep.damage(15.0, 100), 25);
+    assert!(steep.damage(12.0, 100) < linear.damage(12.0, 100));
+
+    // A lethal speed no faster than the safe speed is lethal past the safe speed.
+    let cliff = FallDamage { lethal_speed: 5.0, ..linear };
+    assert_eq!(cliff.damage(10.0, 100), 0);
+    assert_eq!(cliff.damage(10.5, 100), 100);
+
+}
+
+
+/// Fall damage parameters.
+#[derive(Visit, Reflect, Debug, Clone, Copy, PartialEq)]
+#[visit(optional)]
+pub struct FallDamage {
+
+    /// Fastest landing without damage, in metres per second.
+    pub safe_speed:     f32,
+
+    /// Landing speed that takes the whole health capacity, in metres per second.
+    pub lethal_speed:   f32,
+
+    /// Exponent of the damage curve between the safe and lethal speeds. `1.0` is linear; higher forgives short falls
+    /// more and punishes long falls harder.
+    pub curve:          f32,
+
+}
+
+impl FallDamage {
+
+    /// Lifepoints landing at downward `speed` takes from a player with health `capacity`, before resistances.
+    /// Anything past the safe speed takes at least one lifepoint.
+    pub fn damage(&self, speed: f32, capacity: u16) -> u16 {
+
+        if speed.is_nan() || speed <= self.safe_speed {
+            return 0;
+        }
+
+        let span = self.lethal_speed - self.safe_speed;
+        let share = if span > 0.0 { ((speed - self.safe_speed) / span).min(1.0) } else { 1.0 };
+        let damage = (capacity as f32 * share.powf(self.curve.max(0.0))).ceil();
+        (damage.min(capacity as f32) as u16).max(1)
+
+    }
+
+}
+
+impl Default for FallDamage {
+    fn default() -> Self {
+        FallDamage {
+            safe_speed:     12.0,
+            lethal_speed:   30.0,
+            curve:          2.0,
+        }
+    }
+}
@@ -4,6 +4,7 @@ use fyrox:: {
     core:: { visitor::prelude::*, reflect::prelude::*, type_traits::prelude::* },
     
 };
+pub use crate::health:: { Lifepoints, damage:: { Damage, DamageType, Resistances } };
 
 
 
@@ -13,42 +14,245 @@ fn test_playerhealth_safety() {
     let mut playerhealth = PlayerHealth::default();
 
     // LP and CAP start at 50.
-    assert_eq!(playerhealth.lifepoints, 50);
-    assert_eq!(playerhealth.capacity, 50);
+    assert_eq!(*playerhealth.lifepoints(), 50);
+    assert_eq!(*playerhealth.capacity(), 50);
 
     // You can't add more LP than your CAP.
-    assert_eq!(playerhealth.lifepoints_add(10), &50);
+    assert_eq!(playerhealth.pool.lifepoints_add(10), &50);
 
     // Capacity is modified through `.capacity_change()`
     assert_eq!(playerhealth.capacity_change(72), Ok(72));
 
     // LP does not change with CAP changes; Never overflows.
-    assert_eq!(playerhealth.lifepoints, 50);
-    assert_eq!(playerhealth.lifepoints_add(u16::MAX), &72);
+    assert_eq!(*playerhealth.lifepoints(), 50);
+    assert_eq!(playerhealth.pool.lifepoints_add(u16::MAX), &72);
 
     // Player is alive as long as LP > 0.
     assert!(playerhealth.is_alive());
 
     // Subtracting never overflows either.
-    assert_eq!(playerhealth.lifepoints_sub(72), &0);
-    assert_eq!(playerhealth.lifepoints_sub(1), &0);
+    assert_eq!(playerhealth.pool.lifepoints_sub(72), &0);
+    assert_eq!(playerhealth.pool.lifepoints_sub(1), &0);
 
     // Zero LP equates death.
     assert!(playerhealth.is_dead());
 
 }
 
+#[test]
+fn test_playerhealth_damage_resistances() {
+
+    let mut playerhealth = PlayerHealth::default();
+    playerhealth.iframes = 0.0;
+
+    // Without resistances, damage is taken in full.
+    assert_eq!(playerhealth.damage(Damage::new(10, DamageType::Physical)), 10);
+    assert_eq!(*playerhealth.lifepoints(), 40);
+
+    // Armour takes a flat amount off physical damage only, and never heals.
+    playerhealth.pool.resistances.armour = 4;
+    assert_eq!(playerhealth.damage(Damage::new(10, DamageType::Physical)), 6);
+    assert_eq!(playerhealth.damage(Damage::new(3, DamageType::Physical)), 0);
+    assert_eq!(playerhealth.damage(Damage::new(10, DamageType::Fire)), 10);
+    assert_eq!(*playerhealth.lifepoints(), 24);
+
+    // Resistances take a share off their damage type, after armour, rounding to the nearest point.
+    playerhealth.pool.resistances.physical = 0.5;
+    playerhealth.pool.resistances.fire = 0.25;
+    assert_eq!(playerhealth.damage(Damage::new(9, DamageType::Physical)), 3);
+    assert_eq!(playerhealth.damage(Damage::new(10, DamageType::Fire)), 8);
+
+    // Full resistance is immunity, and resistances are kept between none and full.
+    playerhealth.pool.resistances.poison = 1.0;
+    playerhealth.pool.resistances.fall = -2.0;
+    assert_eq!(playerhealth.damage(Damage::new(u16::MAX, DamageType::Poison)), 0);
+    assert_eq!(playerhealth.damage(Damage::new(5, DamageType::Fall)), 5);
+    assert_eq!(*playerhealth.lifepoints(), 8);
+
+    // Damage never takes more than is left.
+    assert_eq!(playerhealth.damage(Damage::new(u16::MAX, DamageType::Fire)), 8);
+    assert!(playerhealth.is_dead());
+    assert_eq!(playerhealth.damage(Damage::new(10, DamageType::Fire)), 0);
+
+}
+
+#[test]
+fn test_playerhealth_iframes() {
+
+    let mut playerhealth = PlayerHealth::default();
+
+    // A hit makes the player invulnerable for `iframes` seconds...
+    assert_eq!(playerhealth.damage(Damage::new(5, DamageType::Physical)), 5);
+    assert!(playerhealth.is_invulnerable());
+    assert_eq!(playerhealth.damage(Damage::new(5, DamageType::Physical)), 0);
+
+    // ...except to damage over time, like poison, which doesn't make them invulnerable either.
+    assert_eq!(playerhealth.damage(Damage::new(2, DamageType::Poison)), 2);
+    let mut poisoned = PlayerHealth::default();
+    poisoned.damage(Damage::new(2, DamageType::Poison));
+    assert!(!poisoned.is_invulnerable());
+
+    // Once they've passed, hits land again.
+    playerhealth.update(playerhealth.iframes * 0.5);
+    assert_eq!(playerhealth.damage(Damage::new(5, DamageType::Physical)), 0);
+    playerhealth.update(playerhealth.iframes * 0.5);
+    assert!(!playerhealth.is_invulnerable());
+    assert_eq!(playerhealth.damage(Damage::new(5, DamageType::Physical)), 5);
+
+    // Hits that are fully resisted don't make the player invulnerable.
+    let mut armoured = PlayerHealth::default();
+    armoured.pool.resistances.armour = 10;
+    armoured.damage(Damage::new(5, DamageType::Physical));
+    assert!(!armoured.is_invulnerable());
+
+}
+
+#[test]
+fn test_playerhealth_regen() {
+
+    let mut playerhealth = PlayerHealth::default();
+    playerhealth.regen_delay = 2.0;
+    playerhealth.regen_rate = 4.0;
+    playerhealth.regen_limit = 0.8;
+
+    // Nothing regenerates until `regen_delay` seconds after the last damage...
+    playerhealth.damage(Damage::new(30, DamageType::Physical));
+    playerhealth.update(1.5);
+    assert_eq!(*playerhealth.lifepoints(), 20);
+
+    // ...and damage starts the wait over.
+    playerhealth.update(playerhealth.iframes);
+    playerhealth.damage(Damage::new(2, DamageType::Poison));
+    playerhealth.update(1.5);
+    assert_eq!(*playerhealth.lifepoints(), 18);
+
+    // Then it regenerates at `regen_rate` per second, carrying fractions between ticks...
+    playerhealth.update(0.5);
+    for _ in 0..4 {
+        playerhealth.update(0.125);
+    }
+    assert_eq!(*playerhealth.lifepoints(), 20);
+
+    // ...up to `regen_limit` of capacity, and never past it.
+    playerhealth.update(60.0);
+    assert_eq!(*playerhealth.lifepoints(), 40);
+    playerhealth.pool.lifepoints_add(5);
+    playerhealth.update(60.0);
+    assert_eq!(*playerhealth.lifepoints(), 45);
+
+    // The dead don't regenerate.
+    playerhealth.pool.lifepoints_sub(u16::MAX);
+    playerhealth.update(60.0);
+    assert!(playerhealth.is_dead());
+
+}
+
+#[test]
+fn test_playerhealth_events() {
+
+    let mut playerhealth = PlayerHealth::default();
+    playerhealth.iframes = 0.0;
+
+    // Damage and healing each emit an event for the lifepoints that actually changed...
+    playerhealth.damage(Damage::new(20, DamageType::Fire));
+    playerhealth.heal(5);
+    playerhealth.heal(100);
+    assert_eq!(playerhealth.events_take(), vec![
+        HealthEvent::Damaged { amount: 20, kind: DamageType::Fire },
+        HealthEvent::Healed { amount: 5 },
+        HealthEvent::Healed { amount: 15 },
+    ]);
+
+    // ...and none when nothing changed.
+    playerhealth.heal(10);
+    playerhealth.pool.resistances.fire = 1.0;
+    playerhealth.damage(Damage::new(20, DamageType::Fire));
+    assert!(playerhealth.events_take().is_empty());
+
+    // Running out dies once, after the damage that did it; the dead can't be hurt or healed.
+    playerhealth.damage(Damage::new(u16::MAX, DamageType::Fall));
+    playerhealth.damage(Damage::new(1, DamageType::Fall));
+    assert_eq!(playerhealth.heal(10), 0);
+    assert_eq!(playerhealth.events_take(), vec![
+        HealthEvent::Damaged { amount: 50, kind: DamageType::Fall },
+        HealthEvent::Died { kind: DamageType::Fall },
+    ]);
+
+    // Events are taken once.
+    assert!(playerhealth.events_take().is_empty());
+
+}
+
+#[test]
+fn test_playerhealth_revive() {
+
+    let mut playerhealth = PlayerHealth::default();
+    playerhealth.damage(Damage::new(u16::MAX, DamageType::Physical));
+    playerhealth.update(playerhealth.iframes);
+
+    // Reviving restores a share of capacity, rounded, but always at least a lifepoint...
+    assert_eq!(playerhealth.revive(0.5), &25);
+    playerhealth.pool.lifepoints_sub(u16::MAX);
+    assert_eq!(playerhealth.revive(0.0), &1);
+    assert_eq!(playerhealth.revive(2.0), &50);
+
+    // ...and protects the revived like a hit does.
+    assert!(playerhealth.is_invulnerable());
+    assert_eq!(playerhealth.damage(Damage::new(10, DamageType::Physical)), 0);
+
+}
+
 
-#[derive(Visit, Reflect, Debug, Clone, TypeUuidProvider, ComponentProvider)]
+/// Things that happened to health, for other systems like UI, audio and rumble to react to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HealthEvent {
+
+    /// Lost `amount` lifepoints to damage of `kind`.
+    Damaged { amount: u16, kind: DamageType },
+
+    /// Gained `amount` lifepoints, by healing or regeneration.
+    Healed { amount: u16 },
+
+    /// Ran out of lifepoints to damage of `kind`.
+    Died { kind: DamageType },
+
+}
+
+
+#[derive(Reflect, Debug, Clone, TypeUuidProvider, ComponentProvider)]
 #[type_uuid(id = "18278f64-52a5-44d2-bfb7-b7ecdb0a9924")]
-#[visit(optional)]
 pub struct PlayerHealth {
 
-    /// Current health points.
-    pub lifepoints: u16,
+    /// Lifepoints, capacity and resistances. Changing lifepoints through it skips invulnerability and events.
+    pub pool:           Lifepoints,
+
+    /// Seconds of invulnerability after a hit.
+    pub iframes:        f32,
+
+    /// Seconds after the last damage before lifepoints regenerate.
+    pub regen_delay:    f32,
+
+    /// Lifepoints regenerated per second. Zero switches regeneration off.
+    pub regen_rate:     f32,
+
+    /// Share of capacity regeneration stops at, from `0.0` to `1.0`.
+    pub regen_limit:    f32,
+
+    /// Seconds of invulnerability left.
+    #[reflect(hidden)]
+    invulnerable:       f32,
+
+    /// Seconds since last taking damage.
+    #[reflect(hidden)]
+    since_damage:       f32,
+
+    /// Fraction of a lifepoint regenerated, carried between update ticks.
+    #[reflect(hidden)]
+    carry:              f32,
 
-    /// Capacity of health points, as in, the maximum amount of points.
-    capacity:   u16,
+    /// Events since the last `.events_take()`.
+    #[reflect(hidden)]
+    events:             Vec<HealthEvent>,
 
 }
 
@@ -58,75 +262,154 @@ impl PlayerHealth {
         Self::default()
     }
 
-    /// Return health capacity.
-    pub fn capacity(&self) -> &u16 {
-        &self.capacity
-    }
+    /// Deal `damage`, reduced by resistances. Returns the lifepoints actually lost.
+    ///
+    /// Nothing is lost while dead, or while invulnerable to the damage type. Damage that lands emits `Damaged`, and
+    /// `Died` if it ran lifepoints out; unless it's damage over time, it also makes the player invulnerable for
+    /// `iframes` seconds. Unlike `.pool.lifepoints_sub()`, this is how gameplay should hurt the player.
+    pub fn damage(&mut self, damage: Damage) -> u16 {
+
+        if self.is_dead() || (damage.kind.uses_iframes() && self.is_invulnerable()) {
+            return 0;
+        }
+
+        let lost = self.pool.damage(damage);
+        if lost == 0 {
+            return 0;
+        }
+
+        self.since_damage = 0.0;
+        self.carry = 0.0;
+        if damage.kind.uses_iframes() {
+            self.invulnerable = self.iframes;
+        }
+        self.events.push(HealthEvent::Damaged { amount: lost, kind: damage.kind });
+        if self.is_dead() {
+            self.events.push(HealthEvent::Died { kind: damage.kind });
+        }
+        lost
 
-    /// Return lifepoints at the current frame.
-    pub fn lifepoints(&self) -> &u16 {
-        &self.lifepoints
     }
 
-    /// Change health capacity to the `new` value provided.
-    /// Returns a `Result` which indicates if the provided `new` integer is valid, or in other words, anything besides 
-    /// zero. A value of zero is illogical and this function returns an `Err` if zero is provided.
-    /// 
-    /// ```lifepoints = new```
-    pub fn capacity_change(&mut self, new: u16) -> Result<u16, ()> {
-        if new >= 1 {
-            self.capacity = new;
-            Ok(self.capacity)
+    /// Heal `amount` lifepoints, up to capacity. Returns the lifepoints actually gained, emitting `Healed` if any.
+    /// The dead can't be healed.
+    pub fn heal(&mut self, amount: u16) -> u16 {
+
+        if self.is_dead() {
+            return 0;
         }
-        else {
-            Err(())
+
+        let gained = self.pool.heal(amount);
+        if gained > 0 {
+            self.events.push(HealthEvent::Healed { amount: gained });
         }
+        gained
+
     }
 
-    /// Add the amount of lifepoints in `add`. Returns the new value.
-    /// This function saturates at the bounds of the integer instead of overflowing.
-    /// 
-    /// ```lifepoints = lifepoints + add ```
-    pub fn lifepoints_add(&mut self, add: u16) -> &u16 {
-        self.lifepoints = self.lifepoints.saturating_add(add);
-        if self.capacity < self.lifepoints {
-            self.lifepoints = self.capacity;
+    /// Advance by `dt` seconds: invulnerability wears off, and once `regen_delay` has passed since the last damage,
+    /// lifepoints regenerate at `regen_rate` up to `regen_limit` of capacity.
+    pub fn update(&mut self, dt: f32) {
+
+        self.invulnerable = (self.invulnerable - dt).max(0.0);
+        self.since_damage += dt;
+
+        let limit = (*self.pool.capacity() as f32 * self.regen_limit.clamp(0.0, 1.0)) as u16;
+        if self.is_dead() || self.pool.lifepoints >= limit || self.since_damage < self.regen_delay {
+            self.carry = 0.0;
+            return;
+        }
+
+        // Only the time since the delay ran out counts towards regeneration.
+        let regenerating = dt.min(self.since_damage - self.regen_delay);
+        self.carry += self.regen_rate.max(0.0) * regenerating;
+        let whole = self.carry.trunc();
+        self.carry -= whole;
+        let points = (whole.min(u16::MAX as f32) as u16).min(limit - self.pool.lifepoints);
+        if points > 0 {
+            self.heal(points);
         }
-        &self.lifepoints
+
+    }
+
+    /// Bring the player back with `share` of capacity, from `0.0` (a single lifepoint) to `1.0`, whether dead or not.
+    /// Returns the new value. Regeneration waits as after damage, and the player is invulnerable for `iframes`.
+    pub fn revive(&mut self, share: f32) -> &u16 {
+        let capacity = *self.pool.capacity();
+        self.pool.lifepoints = ((capacity as f32 * share.clamp(0.0, 1.0)).round() as u16).clamp(1, capacity);
+        self.invulnerable = self.iframes;
+        self.since_damage = 0.0;
+        self.carry = 0.0;
+        self.pool.lifepoints()
+    }
+
+    /// Take the events since the last call, oldest first.
+    pub fn events_take(&mut self) -> Vec<HealthEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// Returns `true` while invulnerability frames after a hit last.
+    pub fn is_invulnerable(&self) -> bool {
+        self.invulnerable > 0.0
     }
 
-    /// Subtract the amount of lifepoints in `subtract`. Returns the new value.
-    /// This function saturates at the bounds of the integer instead of overflowing.
-    /// 
-    /// ```lifepoints = lifepoints - subtract```
-    pub fn lifepoints_sub(&mut self, subtract: u16) -> &u16 {
-        self.lifepoints = self.lifepoints.saturating_sub(subtract);
-        &self.lifepoints
+    /// Return lifepoints at the current frame.
+    pub fn lifepoints(&self) -> &u16 {
+        self.pool.lifepoints()
+    }
+
+    /// Return health capacity.
+    pub fn capacity(&self) -> &u16 {
+        self.pool.capacity()
+    }
+
+    /// Change health capacity to the `new` value provided. Returns `Err` if `new` is zero, as a capacity of zero is
+    /// illogical. Lifepoints don't change with capacity.
+    pub fn capacity_change(&mut self, new: u16) -> Result<u16, ()> {
+        self.pool.capacity_change(new)
     }
 
     pub fn is_alive(&self) -> bool {
-        if self.lifepoints != 0 { true }
-        else { false }
+        self.pool.is_alive()
     }
 
     pub fn is_dead(&self) -> bool {
-        if self.lifepoints == 0 { true }
-        else { false }
+        self.pool.is_dead()
     }
 
 }
 
+// Lifepoints are visited flat, as they were saved before `pool` held them, so older scenes and saves keep them.
+impl Visit for PlayerHealth {
+    fn visit(&mut self, name: &str, visitor: &mut Visitor) -> VisitResult {
+        let mut region = visitor.enter_region(name)?;
+        self.pool.visit_flat(&mut region);
+        let _ = self.iframes.visit("Iframes", &mut region);
+        let _ = self.regen_delay.visit("RegenDelay", &mut region);
+        let _ = self.regen_rate.visit("RegenRate", &mut region);
+        let _ = self.regen_limit.visit("RegenLimit", &mut region);
+        Ok(())
+    }
+}
+
 impl Default for PlayerHealth {
     fn default() -> Self {
         PlayerHealth {
-            lifepoints:     50,
-            capacity:       50
+            pool:           Lifepoints::new(50),
+            iframes:        0.5,
+            regen_delay:    5.0,
+            regen_rate:     1.0,
+            regen_limit:    1.0,
+            invulnerable:   0.0,
+            since_damage:   0.0,
+            carry:          0.0,
+            events:         Vec::new(),
         }
     }
 }
 
 impl Display for PlayerHealth {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_fmt(format_args!("{}/{}", self.lifepoints.to_string(), self.capacity.to_string()))
+        self.pool.fmt(f)
     }
 }
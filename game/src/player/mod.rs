@@ -4,25 +4,84 @@
 //! 
 
 use fyrox::{
-    asset::manager::ResourceManager, core::{
+    core::{
         algebra::*,
         pool::Handle,
         reflect::prelude::*,
         type_traits::prelude::*,
         variable::InheritableVariable,
         visitor::prelude::*
-    }, engine, event::*, gui::window, keyboard::{ KeyCode, PhysicalKey }, scene::{ self, camera::CameraBuilder, node::Node, Scene }, script::{ ScriptContext, ScriptMessage, ScriptTrait }
+    }, engine, gui::window, scene::{
+        self,
+        camera:: { Camera, CameraBuilder, PerspectiveProjection, Projection },
+        collider:: { Collider, ColliderShape },
+        graph:: { Graph, physics:: { Intersection, RayCastOptions } },
+        node::Node,
+        rigidbody::RigidBody,
+        Scene
+    }, script::{ ScriptContext, ScriptMessage, ScriptTrait }
 };
 // use strum_macros::*;
 use tracing::{ trace, info, warn, error, instrument };
 use std::fmt::{ Display, Debug };
 use crate::utilities::*;
+use crate::input:: { InputContext, InputContextKind, Rumble };
+use crate::interaction;
+use crate::settings::input:: { BindingActions, LinearDirectionSetting };
+use controller:: { GroundHit, Obstacle };
+use death::DeathPhase;
+use health:: { Damage, DamageType, HealthEvent };
 
+
+/// Half height of the capsule collider `Player::new()` builds, between the centres of its end caps.
+const CAPSULE_HALF_HEIGHT: f32 = 0.25;
+
+/// Radius of the capsule collider `Player::new()` builds.
+const CAPSULE_RADIUS: f32 = 0.2;
+
+/// Height above the bottom of the player's capsule at which obstacles ahead are first looked for, so the ground
+/// itself isn't one.
+const ANKLE_HEIGHT: f32 = 0.05;
+
+/// Furthest from the camera interactables are looked for, in metres. Each is only reachable within its own range.
+const INTERACT_REACH: f32 = 8.0;
+
+pub mod camera;
+pub mod controller;
+pub mod death;
+pub mod effects;
+pub mod fall;
 pub mod health;
+pub mod movement;
 pub mod skybox;
+pub mod stamina;
 pub mod perspective;
 
 
+#[test]
+fn test_player_paused() {
+
+    let mut player = Player::default();
+    player.effects.apply(effects::StatusEffect::poison(2.0, 10.0));
+    player.controller.update(0.1, None, false);
+    let falling = player.controller.vertical_speed();
+    let lifepoints = *player.health.lifepoints();
+
+    // While paused, ticking changes nothing and stops the update before the player falls any further...
+    for _ in 0..20 {
+        assert!(!player.tick(0.5, true));
+    }
+    assert_eq!(player.controller.vertical_speed(), falling);
+    assert_eq!(*player.health.lifepoints(), lifepoints);
+
+    // ...and once unpaused, poison picks back up where it left off.
+    assert!(player.tick(0.5, false));
+    assert!(player.tick(0.5, false));
+    assert_eq!(*player.health.lifepoints(), lifepoints - 2);
+
+}
+
+
 #[derive(Visit, Reflect, Debug, Clone, TypeUuidProvider, ComponentProvider, Default)]
 #[type_uuid(id = "bb240c15-d2dd-4e24-a832-e0af513e4fcf")]
 #[visit(optional)]
@@ -33,6 +92,9 @@ pub struct Player {
     // #[reflect(hidden)]
     pub health:                     health::PlayerHealth,
 
+    // ? Player stamina, drained by sprinting.
+    pub stamina:                    stamina::PlayerStamina,
+
     // ? Player movement activated by controls.
     #[reflect(hidden)]
     #[visit(skip)]
@@ -66,44 +128,119 @@ pub struct Player {
     #[visit(skip)]
     pub do_interact:                bool,
 
+    // ? Interactable the player is looking at and within reach of, if any; see `crate::interaction`.
+    #[reflect(hidden)]
+    #[visit(skip)]
+    pub focus:                      Handle<Node>,
+
+    // ? *Jump Button*, held down.
+    #[reflect(hidden)]
+    #[visit(skip)]
+    pub do_jump:                    bool,
+
+    // ? *Sprint* and *Crouch Buttons*, held down.
+    #[reflect(hidden)]
+    #[visit(skip)]
+    pub do_sprint:                  bool,
+    #[reflect(hidden)]
+    #[visit(skip)]
+    pub do_crouch:                  bool,
+
+    // ? Standing half height of the capsule collider while crouched, to stand back up to.
+    #[reflect(hidden)]
+    pub crouched:                   Option<f32>,
+
+    // ? Poison, regeneration, slow and haste the player is under.
+    pub effects:                    effects::StatusEffects,
+
+    // ? Items the player carries.
+    pub inventory:                  crate::inventory::Inventory,
+
+    // ? Dying and respawning, the last activated checkpoint respawned at, and where the player respawns without one.
+    pub death:                      death::DeathFlow,
+    pub checkpoint:                 Handle<Node>,
+    #[reflect(hidden)]
+    pub spawn:                      Option<Vector3<f32>>,
+
+    // ? Gravity, jumping, stepping and slope limits of the player's movement.
+    pub controller:                 controller::CharacterController,
+
+    // ? How hard landing from a fall hurts.
+    pub fall_damage:                fall::FallDamage,
+
+    // ? Walking speed, acceleration and friction of the player's movement.
+    pub movement:                   movement::MovementTuning,
+
+    // ? Mouse look motion, carried between update ticks.
+    #[reflect(hidden)]
+    #[visit(skip)]
+    pub look:                       crate::input::MouseLook,
+
     // ? Player camera perspective.
     pub perspective:                perspective::PlayerPerspective,
     pub camera:                     Handle<Node>,
 
-    // ? Accept/ignore input if game window is focused/unfocused.
-    pub input_focus:                bool,   // Whether game window has focus from the player.
-    pub input_block:                bool,   // Block input for keybinding or GUI?
+    // ? Perspective last switched away from, to switch back to, and the *Toggle Perspective Button*, held down.
+    #[reflect(hidden)]
+    pub perspective_alternate:      Option<perspective::PlayerPerspective>,
+    #[reflect(hidden)]
+    #[visit(skip)]
+    pub do_toggle_perspective:      bool,
+
+    // ? Keeps the third-person camera from clipping through walls behind the player.
+    pub spring_arm:                 perspective::SpringArm,
+
+    // ? Head bob, FOV kick, shake and look smoothing layered over the camera.
+    pub camera_effects:             camera::CameraEffects,
+
+    // ? Eases the camera's FOV through changes, and the player's FOV setting last applied to the perspective.
+    pub fov_transition:             perspective::FovTransition,
+    #[reflect(hidden)]
+    #[visit(skip)]
+    pub fov_setting:                Option<f32>,
 
     // ? Player model (in case we want 3rd person view...)
     pub playermodel:                Handle<Node>,
 
+    // ? The camera and playermodel were found on starting.
+    #[reflect(hidden)]
+    #[visit(skip)]
+    pub valid:                      bool,
+
 }
 
 impl Player {
 
-    #[instrument(skip(resource_manager))]
-    pub async fn new(scene: &mut Scene, resource_manager: &ResourceManager) -> Self {
+    /// Build the playermodel in `scene` with its feet at `position`, along with its camera and capsule collider, and
+    /// the player facing `yaw` degrees. The script is for the caller to add to a node. The camera has no skybox until
+    /// one is set; see `skybox::request()`.
+    #[instrument(skip(scene))]
+    pub fn new(scene: &mut Scene, position: Vector3<f32>, yaw: f32) -> Self {
         
-        // Set up *skybox Future*, camera, perspective and rigid body model.
-        let skybox = skybox::request(resource_manager);
+        // Set up camera, perspective and rigid body model.
         let camera: Handle<Node>;
-        let perspective = perspective::PlayerPerspective::default();
+        let mut perspective = perspective::PlayerPerspective::default();
+        perspective.set_yaw(yaw);
         let playermodel = fyrox::scene::rigidbody::RigidBodyBuilder::new(
 
             fyrox::scene::base::BaseBuilder::new()
+            .with_local_transform(
+                scene::transform::TransformBuilder::new()
+                    .with_local_position(position + Vector3::y() * (CAPSULE_HALF_HEIGHT + CAPSULE_RADIUS))
+                    .build()
+            )
             .with_children(&[
                 {
                     camera = CameraBuilder::new(
                         scene::base::BaseBuilder::new()
                     )
-                    .with_skybox(skybox.await)
-                    .with_fov(perspective.fov().unwrap()) // * FOV should always be `FirstPerson` by default.
+                    .with_fov(perspective.fov().unwrap().to_radians()) // * FOV should always be `FirstPerson` by default.
                     .build(&mut scene.graph);
                     camera
                 },
                 // Add capsule collider for the rigid body.
                 scene::collider::ColliderBuilder::new(scene::base::BaseBuilder::new())
-                .with_shape(scene::collider::ColliderShape::capsule_y(0.25, 0.2))
+                .with_shape(scene::collider::ColliderShape::capsule_y(CAPSULE_HALF_HEIGHT, CAPSULE_RADIUS))
                 .build(&mut scene.graph)
             ])
             
@@ -116,172 +253,561 @@ impl Player {
         Player {
             camera,
             playermodel,
+            perspective,
             ..Default::default()
         }
 
     }
 
-}
+    /// Why the player can't work in `graph`, if it can't: `camera` must be a camera, and `playermodel` a rigid body.
+    fn validate(&self, graph: &Graph) -> Result<(), String> {
+        let Some(camera) = graph.try_get(self.camera) else {
+            return Err(format!("camera {:?} is missing", self.camera));
+        };
+        let Some(playermodel) = graph.try_get(self.playermodel) else {
+            return Err(format!("playermodel {:?} is missing", self.playermodel));
+        };
+
+        if camera.cast::<Camera>().is_none() {
+            Err(format!("camera {} isn't a camera", camera.name()))
+        }
+        else if playermodel.cast::<RigidBody>().is_none() {
+            Err(format!("playermodel {} isn't a rigid body", playermodel.name()))
+        }
+        else {
+            Ok(())
+        }
+    }
 
-impl ScriptTrait for Player {
+    /// The playermodel's capsule collider, if it has one.
+    fn capsule_collider(&self, graph: &Graph) -> Option<Handle<Node>> {
+        graph[self.playermodel].children().iter()
+            .copied()
+            .find(|child| graph[*child].cast::<Collider>()
+                .is_some_and(|collider| matches!(collider.shape(), ColliderShape::Capsule(_))))
+    }
 
-    #[instrument(skip(context))]
-    fn on_update(&mut self, context: &mut ScriptContext) {
+    /// Half height and radius of the playermodel's capsule collider, or those `Player::new()` builds with if it has
+    /// none.
+    fn capsule(&self, graph: &Graph) -> (f32, f32) {
+        self.capsule_collider(graph)
+            .and_then(|collider| match graph[collider].cast::<Collider>()?.shape() {
+                ColliderShape::Capsule(capsule) => Some(((capsule.end - capsule.begin).norm() / 2.0, capsule.radius)),
+                _                               => None,
+            })
+            .unwrap_or((CAPSULE_HALF_HEIGHT, CAPSULE_RADIUS))
+    }
 
-        // ? Let dev know this scene is missing Player script node attachments...
-        if self.camera.is_none() || self.playermodel.is_none() {
-            error!(
-                "This scene's Player script has missing nodes: {:?} - {:?}",
-                self.camera,
-                self.playermodel
-            );
+    /// Crouch, shrinking the capsule collider to `crouch_height` of its standing height. On the ground, the
+    /// playermodel lowers with it so its feet stay put.
+    fn crouch(&mut self, graph: &mut Graph, grounded: bool) {
+
+        let Some(collider) = self.capsule_collider(graph) else { return };
+        let (half_height, radius) = self.capsule(graph);
+        let crouched = ((half_height + radius) * self.movement.crouch_height.clamp(0.0, 1.0) - radius).max(0.0);
+
+        if let Some(collider) = graph[collider].cast_mut::<Collider>() {
+            collider.set_shape(ColliderShape::capsule_y(crouched, radius));
+        }
+        if grounded {
+            graph[self.playermodel].local_transform_mut().offset(Vector3::new(0.0, crouched - half_height, 0.0));
         }
+        self.crouched = Some(half_height);
 
-        // TODO: Document.
-        context.scene.graph[self.camera].local_transform_mut().set_rotation(
-            UnitQuaternion::from_axis_angle(&Vector3::x_axis(), self.perspective.pitch().to_radians()),
-        );
+    }
 
-        // TODO: Document.
-        let body = context.scene.graph[self.playermodel]
-            .as_rigid_body_mut();
+    /// Stand back up from crouching, unless there's a ceiling in the way. Returns `true` if standing.
+    fn uncrouch(&mut self, graph: &mut Graph, grounded: bool) -> bool {
+
+        let (Some(standing), Some(collider)) = (self.crouched, self.capsule_collider(graph)) else {
+            self.crouched = None;
+            return true;
+        };
+        let (half_height, radius) = self.capsule(graph);
+        let rise = (standing - half_height).max(0.0);
+
+        // Standing on the ground grows upwards by twice the rise; in the air, by the rise as the feet drop too.
+        let growth = if grounded { rise * 2.0 } else { rise };
+        let position = graph[self.playermodel].global_position();
+        if self.cast(graph, position, Vector3::y(), half_height + radius + growth).is_some() {
+            return false;
+        }
 
-        // TODO: Document.
-        let mut velocity = Vector3::new(0.0, body.lin_vel().y, 0.0);
- 
-        // Move playermodel camera gimbal across floorplane when activated controls call for it.
-        if self.movement_backward {
-            velocity -= body.look_vector();
-        } if self.movement_forward {
-            velocity += body.look_vector();
-        } if self.movement_left {
-            velocity += body.side_vector();
-        } if self.movement_right {
-            velocity -= body.side_vector();
+        if let Some(collider) = graph[collider].cast_mut::<Collider>() {
+            collider.set_shape(ColliderShape::capsule_y(standing, radius));
         }
+        if grounded {
+            graph[self.playermodel].local_transform_mut().offset(Vector3::new(0.0, rise, 0.0));
+        }
+        self.crouched = None;
+        true
 
-        body.set_lin_vel(velocity);
+    }
 
-        // TODO: Document.
-        body.local_transform_mut()
-            .set_rotation(UnitQuaternion::from_axis_angle(
-                &Vector3::y_axis(),
-                self.perspective.yaw().to_radians()
-            ));
+    /// Shake the camera by adding `trauma`, from `0.0` for nothing to `1.0` for as hard as it shakes. Trauma adds up
+    /// and wears off over time; see `camera::CameraEffects`.
+    pub fn shake(&mut self, trauma: f32) {
+        self.camera_effects.add_trauma(trauma);
+    }
 
+    /// Turn the camera to `pitch`, with the playermodel yawed to `yaw`, and move it into position: at the playermodel's
+    /// centre in first person, or orbiting behind the top of its capsule in third person, pulled in by the spring arm
+    /// wherever something's in the way. Camera effect `offsets` apply on top, bobbing in first person only, and the
+    /// vertical field of view is set to `fov` degrees.
+    fn place_camera(
+        &mut self,
+        graph: &mut Graph,
+        dt: f32,
+        pitch: f32,
+        yaw: f32,
+        offsets: camera::CameraOffsets,
+        fov: f32
+    ) {
+
+        let pitch = UnitQuaternion::from_axis_angle(&Vector3::x_axis(), pitch.to_radians());
+        let position = match self.perspective.orbit() {
+            None => {
+                self.spring_arm.reset();
+                offsets.position
+            },
+            Some((distance, shoulder)) => {
+
+                // The camera looks along +Z and the playermodel's left is +X, so the arm reaches back along -Z and
+                // right along -X.
+                let (half_height, _) = self.capsule(graph);
+                let pivot = Vector3::new(0.0, half_height, 0.0);
+                let arm = pitch * Vector3::new(-shoulder, 0.0, -distance);
+                let length = arm.norm();
+                if length <= f32::EPSILON {
+                    pivot
+                }
+                else {
+                    let yaw = UnitQuaternion::from_axis_angle(&Vector3::y_axis(), yaw.to_radians());
+                    let origin = graph[self.playermodel].global_position() + pivot;
+                    let hit = self.cast(graph, origin, yaw * arm / length, length).map(|hit| hit.toi);
+                    pivot + arm / length * self.spring_arm.update(dt, length, hit)
+                }
+
+            },
+        };
+
+        let rotation = UnitQuaternion::from_axis_angle(&Vector3::y_axis(), offsets.yaw.to_radians())
+            * UnitQuaternion::from_axis_angle(&Vector3::x_axis(), offsets.pitch.to_radians())
+            * pitch
+            * UnitQuaternion::from_axis_angle(&Vector3::z_axis(), offsets.roll.to_radians());
+        let transform = graph[self.camera].local_transform_mut();
+        transform.set_rotation(rotation);
+        transform.set_position(position);
+
+        if let Some(camera) = graph[self.camera].cast_mut::<Camera>() {
+            if let Projection::Perspective(projection) = camera.projection().clone() {
+                camera.set_projection(Projection::Perspective(PerspectiveProjection {
+                    fov: fov.to_radians(),
+                    ..projection
+                }));
+            }
+        }
 
     }
 
-    #[instrument(skip(context))]
-    fn on_os_event(&mut self, event: &Event<()>, context: &mut ScriptContext) {
-        
-        // Run this code when the graphics context is initialized.
-        with_igc(context.graphics_context, |igc| {
-
-            // Grab cursor on focus.
-            if self.input_focus {
-                igc.window.set_cursor_grab(fyrox::window::CursorGrabMode::Confined)
-                // Some platforms have no support for cursor grab.
-                .or_else(|_e| igc.window.set_cursor_grab(fyrox::window::CursorGrabMode::Locked));
-            }
+    // Tick status effects by `dt` seconds while alive, poisoning and healing, then wear off invulnerability and
+    // regenerate. While `paused`, nothing changes and the update stops here, returning `false`.
+    fn tick(&mut self, dt: f32, paused: bool) -> bool {
+
+        if paused {
+            return false;
+        }
 
-            // Release cursor when focus is lost.
-            else {
-                igc.window.set_cursor_grab(fyrox::window::CursorGrabMode::None);
+        if self.health.is_alive() {
+            let tick = self.effects.update(dt);
+            if tick.damage > 0 {
+                self.health.damage(Damage::new(tick.damage, DamageType::Poison));
             }
+            if tick.heal > 0 {
+                self.health.heal(tick.heal);
+            }
+        }
+        self.health.update(dt);
+        true
 
-            // Enable/disable cursor depending on whether we block input.
-            igc.window.set_cursor_visible(self.input_block);
+    }
 
-        });
+    /// Put the playermodel back on its feet at the last activated checkpoint, facing the way it faces, or where it
+    /// started if there's none.
+    fn respawn(&mut self, graph: &mut Graph) {
 
-        match event {
-            
-            Event::WindowEvent { window_id, event } => {
+        let (half_height, radius) = self.capsule(graph);
+        let position = match graph.try_get(self.checkpoint) {
+            Some(checkpoint) => {
+                let look = checkpoint.look_vector();
+                self.perspective.set_yaw(look.x.atan2(look.z).to_degrees());
+                checkpoint.global_position() + Vector3::new(0.0, half_height + radius, 0.0)
+            },
+            None => self.spawn.unwrap_or_default(),
+        };
+        self.perspective.set_pitch(0.0);
+        self.controller.reset();
+        self.look.reset();
+
+        let body = graph.try_get_mut(self.playermodel)
+            .and_then(|node| node.cast_mut::<RigidBody>());
+        if let Some(body) = body {
+            body.set_lin_vel(Vector3::zeros());
+            body.local_transform_mut().set_position(position);
+        }
 
-                // Enable/disable input if the game window is focused/unfocused.
-                if let WindowEvent::Focused(focus) = event {
+    }
 
-                    // Controls how *we* see window focus.
-                    self.input_focus = focus.clone();
-                    self.input_block = focus | true;
+    /// Cast a ray from `origin` along `direction` for up to `length`, returning the nearest hit on anything but the
+    /// playermodel's own colliders.
+    fn cast(&self, graph: &Graph, origin: Vector3<f32>, direction: Vector3<f32>, length: f32) -> Option<Intersection> {
+
+        let mut hits = Vec::new();
+        graph.physics.cast_ray(
+            RayCastOptions {
+                ray_origin:     Point3::from(origin),
+                ray_direction:  direction,
+                max_len:        length,
+                groups:         Default::default(),
+                sort_results:   true,
+            },
+            &mut hits
+        );
+        hits.into_iter().find(|hit| graph[hit.collider].parent() != self.playermodel)
 
-                }
+    }
 
-                // Cursor entering window means player wants to direct their pointer at game.
-                if let WindowEvent::CursorEntered { device_id } = event {
-                    self.input_block = false;
-                    self.input_focus = true; // TODO: Maybe remove, test and debug this pls.
-                }
+    /// The enabled interactable the camera looks at, if the playermodel is within its range of where the camera's
+    /// view meets it.
+    fn interactable_ahead(&self, graph: &Graph) -> Option<Handle<Node>> {
 
-                // Cursor leaving window means player wants to direct their focus elsewhere.
-                if let WindowEvent::CursorLeft { device_id } = event {
-                    self.input_block = true;
-                    self.input_focus = false;
-                }
+        let camera = graph.try_get(self.camera)?;
+        let hit = self.cast(graph, camera.global_position(), camera.look_vector().normalize(), INTERACT_REACH)?;
+        let node = interaction::interactable_of(graph, hit.collider)?;
+        let interactable = interaction::interactable(graph, node)?;
 
-                // Keyboard input.
-                if let WindowEvent::KeyboardInput { event, .. } = event {
-
-                    // Pressed keys are mapped to movement modifiers.
-                    if 
-                        event.state.is_pressed() && // Only *keypresses* are read.
-                        self.input_focus &&         // Window must have focus.
-                        !self.input_block           // Game must not block input.
-                    {
-                        if let PhysicalKey::Code(keycode) = event.physical_key {
-                            println!("Key {:?}", keycode);
-                            match keycode {
-                                
-                                KeyCode::Escape => {
-                                    self.input_focus = false;
-                                    self.input_block = true;
-                                },
-
-                                KeyCode::KeyW => self.movement_forward = true,
-                                KeyCode::KeyA => self.movement_left = true,
-                                KeyCode::KeyS => self.movement_backward = true,
-                                KeyCode::KeyD => self.movement_right = true,
-                                KeyCode::KeyE => self.do_interact = true,
-                                _ => {}
-                            };
-                        };
-                    };
+        let distance = (hit.position.coords - graph[self.playermodel].global_position()).norm();
+        (interactable.is_enabled() && distance <= interactable.range()).then_some(node)
 
-                }
+    }
 
-                // Mouse wheel input.
-                if let WindowEvent::MouseWheel { delta, .. } = event {
+    /// Ground beneath the playermodel, cast for under the middle and around the edge of its capsule.
+    ///
+    /// The nearest walkable ground is preferred, so standing on the edge of a ledge still counts.
+    fn ground(&self, graph: &Graph) -> Option<GroundHit> {
 
-                }
+        let position = graph[self.playermodel].global_position();
+        let (half_height, radius) = self.capsule(graph);
+        let bottom = half_height + radius;
+        let edge = radius * 0.7;
 
-            },
+        let hits = [(0.0, 0.0), (edge, 0.0), (-edge, 0.0), (0.0, edge), (0.0, -edge)].iter()
+            .filter_map(|(x, z)| {
+                let origin = position + Vector3::new(*x, 0.0, *z);
+                self.cast(graph, origin, -Vector3::y(), bottom + self.controller.ground_distance)
+            })
+            .map(|hit| GroundHit { distance: hit.toi - bottom, normal: hit.normal.normalize() });
+
+        let walkable = |hit: &GroundHit| self.controller.walkable(&hit.normal);
+        hits.min_by(|a, b| walkable(b).cmp(&walkable(a)).then(a.distance.total_cmp(&b.distance)))
+
+    }
+
+    /// What's ahead of the playermodel in horizontal `direction`, cast for at its ankles, at step height and at its
+    /// middle.
+    fn obstacle(&self, graph: &Graph, direction: Vector3<f32>, grounded: bool) -> Obstacle {
+
+        let position = graph[self.playermodel].global_position();
+        let (half_height, radius) = self.capsule(graph);
+        let bottom = position - Vector3::y() * (half_height + radius);
+        let length = radius + self.controller.wall_distance;
+        let normal_at = |height: f32| {
+            self.cast(graph, bottom + Vector3::y() * height, direction, length).map(|hit| hit.normal.normalize())
+        };
+
+        let feet = normal_at(ANKLE_HEIGHT);
+        let step = normal_at(self.controller.step_height + ANKLE_HEIGHT)
+            .or_else(|| normal_at(half_height + radius));
+        self.controller.obstacle(feet, step, grounded)
+
+    }
+
+    /// How far the playermodel rises to step up onto the ledge ahead in horizontal `direction`, or `0.0` if there's
+    /// nothing walkable to step onto.
+    fn step_rise(&self, graph: &Graph, direction: Vector3<f32>) -> f32 {
+
+        let position = graph[self.playermodel].global_position();
+        let (half_height, radius) = self.capsule(graph);
+        let above = self.controller.step_height + ANKLE_HEIGHT;
+        let origin = position
+            - Vector3::y() * (half_height + radius)
+            + direction * (radius + self.controller.wall_distance)
+            + Vector3::y() * above;
+
+        match self.cast(graph, origin, -Vector3::y(), above) {
+            Some(hit) if self.controller.walkable(&hit.normal.normalize()) => (above - hit.toi).max(0.0),
+            _ => 0.0,
+        }
+
+    }
+
+}
+
+impl ScriptTrait for Player {
+
+    #[instrument(skip(context))]
+    fn on_update(&mut self, context: &mut ScriptContext) {
+
+        // A player whose nodes are missing was reported on starting, and does nothing; nor does anyone while the
+        // game is paused.
+        if !self.valid || !self.tick(context.dt, context.plugins.get::<crate::Game>().is_paused()) {
+            return;
+        }
+
+        // React to what happened to health since the last tick. Taking damage rumbles the player's gamepad and shakes
+        // the camera, harder the bigger the share of health it took.
+        for event in self.health.events_take() {
+            match event {
+                HealthEvent::Damaged { amount, .. } => {
+                    let share = amount as f32 / (*self.health.capacity()).max(1) as f32;
+                    context.plugins.get_mut::<crate::Game>().rumble
+                        .play_scaled(Rumble::Damage.effect(), (0.5 + share).min(1.0));
+                    self.shake(share);
+                },
+                HealthEvent::Died { kind }          => {
+                    info!("player died to {:?} damage", kind);
+                    self.death.die();
+                },
+                HealthEvent::Healed { .. }          => {},
+            }
+        }
+
+        // Dying plays out the death state with input taken away, then asks the player to respawn. Respawning puts
+        // them back at the last activated checkpoint, with the penalties for dying. Players loaded out of lifepoints
+        // pick dying back up.
+        self.death.follow(&self.health);
+        if self.death.update(context.dt) {
+            context.plugins.get_mut::<crate::Game>().input.push(InputContext::new(InputContextKind::Death));
+        }
+        if self.death.phase() == DeathPhase::Dead
+            && context.plugins.get::<crate::Game>().input.is_active(&BindingActions::respawn())
+            && self.death.respawn()
+        {
+            context.plugins.get_mut::<crate::Game>().input.remove(InputContextKind::Death);
+            self.death.penalise(&mut self.health, &mut self.stamina);
+            self.effects.clear();
+            self.respawn(&mut context.scene.graph);
+        }
+
+        // Mouse motion and swipes on the touch screen since the last tick, and the virtual stick, as set up in the
+        // player's touch input settings; replayed instead while replaying input.
+        let gameplugin = context.plugins.get_mut::<crate::Game>();
+        let motion = gameplugin.input.take_motion(&gameplugin.settings.input.touch);
+        let (swipe, stick) = (motion.swipe, motion.stick);
+        self.look.accumulate(motion.mouse.0, motion.mouse.1);
+
+        // Movement and interaction follow actions resolved from the player's bindings, while gameplay has input.
+        let gameplugin = context.plugins.get::<crate::Game>();
+        let input = &gameplugin.input;
+        let accepts_input = input.gameplay_active() && self.health.is_alive();
+        let is_active = |action: BindingActions| accepts_input && input.is_active(&action);
+        self.movement_forward   = is_active(BindingActions::linear(LinearDirectionSetting::forward()));
+        self.movement_backward  = is_active(BindingActions::linear(LinearDirectionSetting::backward()));
+        self.movement_left      = is_active(BindingActions::linear(LinearDirectionSetting::left()));
+        self.movement_right     = is_active(BindingActions::linear(LinearDirectionSetting::right()));
+        let interact            = is_active(BindingActions::interact());
+        let interact_pressed    = interact && !self.do_interact;
+        self.do_interact        = interact;
+        self.do_jump            = is_active(BindingActions::jump());
+        self.do_sprint          = is_active(BindingActions::sprint());
+        self.do_crouch          = is_active(BindingActions::crouch());
+
+        // Switch between first and third person on pressing *Toggle Perspective*, still looking the same way.
+        let toggle_perspective = is_active(BindingActions::toggle_perspective());
+        if toggle_perspective && !self.do_toggle_perspective {
+            self.perspective.toggle(&mut self.perspective_alternate);
+            self.fov_setting = None;
+        }
+        self.do_toggle_perspective = toggle_perspective;
+
+        // Mouse motion and swipes since the last tick turn the camera, as set up in the player's input settings.
+        if accepts_input {
+            let (yaw, pitch) = self.look.update(&gameplugin.settings.input.desktop);
+            self.perspective.set_yaw(self.perspective.yaw() + yaw + swipe.0);
+            self.perspective.set_pitch((self.perspective.pitch() + pitch + swipe.1).clamp(-90.0, 90.0));
+        } else {
+            self.look.reset();
+        }
 
-            Event::DeviceEvent { event, .. } => {
+        // Look for ground beneath the playermodel and obstacles around it, blocking movement into walls.
+        let grounded = self.controller.is_grounded();
 
-                // Device events are only accepted if we have window focus from the player.
-                if self.input_focus && !self.input_block {
-                    // Raw Mouse movements move the camera.
-                    if let DeviceEvent::MouseMotion { delta } = event {
-                        self.perspective.set_yaw( self.perspective.yaw() - delta.0 as f32);
-                        self.perspective.set_pitch((self.perspective.pitch() + delta.1 as f32)
-                            .clamp(-90.0, 90.0));
-                    }
+        // Crouch while held, and stand back up once let go and there's room overhead.
+        if self.do_crouch && self.crouched.is_none() {
+            self.crouch(&mut context.scene.graph, grounded);
+        }
+        else if !self.do_crouch && self.crouched.is_some() {
+            self.uncrouch(&mut context.scene.graph, grounded);
+        }
+
+        let graph = &context.scene.graph;
+        let (half_height, radius) = self.capsule(graph);
+        let forward = graph[self.playermodel].look_vector().normalize();
+        let side = graph[self.playermodel].side_vector().normalize();
+        let ground = self.ground(graph);
+        let [forward_obstacle, backward_obstacle, left_obstacle, right_obstacle] =
+            [forward, -forward, side, -side].map(|direction| self.obstacle(graph, direction, grounded));
+        self.movement_forward_block     = forward_obstacle == Obstacle::Wall;
+        self.movement_backward_block    = backward_obstacle == Obstacle::Wall;
+        self.movement_left_block        = left_obstacle == Obstacle::Wall;
+        self.movement_right_block       = right_obstacle == Obstacle::Wall;
+
+        // Move playermodel camera gimbal across floorplane when activated controls call for it, unless blocked.
+        let mut wish = Vector3::zeros();
+        let mut step = 0.0f32;
+        for (moving, blocked, obstacle, direction) in [
+            (self.movement_forward, self.movement_forward_block, forward_obstacle, forward),
+            (self.movement_backward, self.movement_backward_block, backward_obstacle, -forward),
+            (self.movement_left, self.movement_left_block, left_obstacle, side),
+            (self.movement_right, self.movement_right_block, right_obstacle, -side),
+        ] {
+            if moving && !blocked {
+                wish += direction;
+                if obstacle == Obstacle::Step {
+                    step = step.max(self.step_rise(graph, direction));
                 }
+            }
+        }
 
-            },
+        // The virtual stick moves only as fast as it's pushed.
+        let deflection = Vector2::new(stick.0, stick.1).norm().min(1.0);
+        if deflection > 0.0 && wish.norm() > 0.0 {
+            wish = wish.normalize() * deflection;
+        }
+
+        // Sprint while moving, standing and not exhausted, draining stamina; otherwise it regenerates.
+        let crouching = self.crouched.is_some();
+        let sprinting = self.do_sprint && !crouching && !self.stamina.is_exhausted() && wish.norm() > 0.0;
+        self.stamina.update(context.dt, sprinting);
+
+        // Speed up and slow down as tuned, scaled by slow and haste, then fall, jump, or stay on the ground as the
+        // character controller says, stepping up onto low ledges ahead. Landing from too high a fall hurts.
+        let body = context.scene.graph[self.playermodel]
+            .as_rigid_body_mut();
+        let current = body.lin_vel();
+        let horizontal = self.movement.velocity_at(
+            Vector2::new(current.x, current.z),
+            Vector2::new(wish.x, wish.z),
+            self.movement.speed(sprinting, crouching) * self.effects.speed_multiplier(),
+            grounded,
+            context.dt
+        );
+        let vertical = self.controller.update(context.dt, ground, self.do_jump);
+        if let Some(speed) = self.controller.landing() {
+            let damage = self.fall_damage.damage(speed, *self.health.capacity());
+            if damage > 0 {
+                self.health.damage(Damage::new(damage, DamageType::Fall));
+            }
+        }
+        if step > 0.0 {
+            body.local_transform_mut().offset(Vector3::new(0.0, step, 0.0));
+        }
+        body.set_lin_vel(Vector3::new(horizontal.x, vertical, horizontal.y));
+
+        // Camera effects follow speed as a share of walking speed, unless the player asked for reduced motion.
+        let reduced_motion = context.plugins.get::<crate::Game>().settings.display.reduced_motion;
+        let mut offsets = self.camera_effects.update(
+            context.dt,
+            horizontal.norm() / self.movement.walk_speed.max(f32::EPSILON),
+            sprinting,
+            self.controller.is_grounded(),
+            reduced_motion
+        );
 
-            _   => {},
+        // The death state sinks the camera towards the ground, tipping it over unless motion is reduced.
+        if let Some(progress) = self.death.progress() {
+            offsets.position.y -= (half_height + radius * 0.5) * progress;
+            if !reduced_motion {
+                offsets.roll += 70.0 * progress;
+            }
+        }
+        let (pitch, yaw) = self.camera_effects.look(
+            context.dt,
+            self.perspective.pitch(),
+            self.perspective.yaw(),
+            reduced_motion
+        );
 
+        // Apply the player's FOV setting to the perspective whenever it changes. The camera eases towards the
+        // perspective's FOV, kicked out while sprinting, measured along the axis the player chose for this window.
+        let gameplugin = context.plugins.get::<crate::Game>();
+        let display = &gameplugin.settings.display;
+        if self.fov_setting != Some(display.fov()) {
+            self.perspective.set_fov(display.fov());
+            self.fov_setting = Some(display.fov());
         }
+        let fov = self.perspective.fov().unwrap_or(display.fov()) + offsets.fov;
+        let fov = self.fov_transition.update(
+            context.dt,
+            crate::settings::vertical_fov(fov, display.fov_axis, gameplugin.aspect_ratio())
+        );
+
+        // Turn the playermodel, and the camera with it, to face the way the player is looking.
+        body.local_transform_mut()
+            .set_rotation(UnitQuaternion::from_axis_angle(&Vector3::y_axis(), yaw.to_radians()));
+
+        // Pitch the camera up and down, and move it into place for the perspective.
+        self.place_camera(&mut context.scene.graph, context.dt, pitch, yaw, offsets, fov);
+
+        // Focus the interactable the camera looks at, highlighting it, and interact with it on pressing *Interact*.
+        let graph = &mut context.scene.graph;
+        let focus = if accepts_input { self.interactable_ahead(graph).unwrap_or_default() } else { Handle::NONE };
+        if focus != self.focus {
+            if let Some(interactable) = interaction::interactable_mut(graph, self.focus) {
+                interactable.focus(false);
+            }
+            if let Some(interactable) = interaction::interactable_mut(graph, focus) {
+                interactable.focus(true);
+            }
+            self.focus = focus;
+        }
+        if interact_pressed {
+            if let Some(interactable) = interaction::interactable_mut(graph, self.focus) {
+                interactable.interact();
+                context.plugins.get_mut::<crate::Game>().rumble.play(Rumble::Click.effect());
+            }
+        }
+
 
     }
 
     #[instrument(skip(context))]
     fn on_start(&mut self, #[allow(unused_variables)] context: &mut ScriptContext) {
         
+        // ? Let dev know, once, if this scene's Player script is missing its nodes.
+        match self.validate(&context.scene.graph) {
+            Ok(())      => self.valid = true,
+            Err(why)    => {
+                error!("This scene's Player script can't work: {}", why);
+                self.valid = false;
+                return;
+            },
+        }
+
+        // Let checkpoints find the player, and remember where it started to respawn there without one.
         let gameplugin = context.plugins.get_mut::<crate::Game>();
-        let scene = gameplugin.scene;
+        gameplugin.player = context.handle;
+        if self.spawn.is_none() {
+            self.spawn = context.scene.graph.try_get(self.playermodel)
+                .map(|playermodel| playermodel.global_position());
+        }
+
+        // Gravity is applied by the character controller, so the physics engine mustn't apply it again.
+        let body = context.scene.graph.try_get_mut(self.playermodel)
+            .and_then(|node| node.cast_mut::<RigidBody>());
+        if let Some(body) = body {
+            body.set_gravity_scale(0.0);
+        }
 
     }
 
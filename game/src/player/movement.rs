@@ -0,0 +1,211 @@
+//!
+//! Movement tuning.
+//!
+//! How quickly the player gets up to walking speed and comes to a stop, on the ground and in the air. Works on the
+//! horizontal part of the playermodel's velocity only, as `(x, z)`; vertical movement is up to the character
+//! controller.
+//!
+
+use fyrox:: {
+    core:: { algebra::Vector2, visitor::prelude::*, reflect::prelude::* },
+};
+
+
+
+#[cfg(test)]
+fn tuning() -> MovementTuning {
+    MovementTuning {
+        walk_speed:     4.0,
+        acceleration:   10.0,
+        deceleration:   20.0,
+        air_control:    0.25,
+        friction:       0.0,
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+fn ticks(
+    tuning: &MovementTuning,
+    mut velocity: Vector2<f32>,
+    wish: Vector2<f32>,
+    grounded: bool,
+    n: usize
+) -> Vector2<f32> {
+    for _ in 0..n {
+        velocity = tuning.velocity(velocity, wish, grounded, 0.1);
+    }
+    velocity
+}
+
+#[test]
+fn test_movementtuning_acceleration() {
+
+    let tuning = tuning();
+    let forward = Vector2::new(0.0, 1.0);
+
+    // Walking speeds up by `acceleration` each second until it reaches `walk_speed`.
+    assert!((ticks(&tuning, Vector2::zeros(), forward, true, 2).norm() - 2.0).abs() < 1e-4);
+    assert!((ticks(&tuning, Vector2::zeros(), forward, true, 4).norm() - 4.0).abs() < 1e-4);
+    assert!((ticks(&tuning, Vector2::zeros(), forward, true, 20).norm() - 4.0).abs() < 1e-4);
+
+    // Moving diagonally is no faster than straight ahead.
+    let diagonal = ticks(&tuning, Vector2::zeros(), Vector2::new(1.0, 1.0), true, 20);
+    assert!((diagonal.norm() - tuning.walk_speed).abs() < 1e-4);
+    assert!((diagonal.x - diagonal.y).abs() < 1e-4);
+
+    // Turning around brakes before speeding up the other way.
+    let turned = ticks(&tuning, Vector2::new(0.0, 4.0), -forward, true, 4);
+    assert!((turned.y - 0.0).abs() < 1e-4);
+
+    // Sprinting and crouching change top speed.
+    let sprint = tuning.speed(true, false);
+    let sprinted = tuning.velocity_at(Vector2::zeros(), Vector2::new(0.0, 1.0), sprint, true, 10.0);
+    assert!((sprinted.y - tuning.walk_speed * tuning.sprint_speed).abs() < 1e-4);
+    assert_eq!(tuning.speed(true, true), tuning.walk_speed * tuning.crouch_speed);
+
+}
+
+#[test]
+fn test_movementtuning_stopping() {
+
+    let tuning = tuning();
+    let walking = Vector2::new(0.0, 4.0);
+
+    // Letting go slows down by `deceleration` each second, and stops without backing up.
+    assert!((ticks(&tuning, walking, Vector2::zeros(), true, 1).y - 2.0).abs() < 1e-4);
+    assert_eq!(ticks(&tuning, walking, Vector2::zeros(), true, 2), Vector2::zeros());
+    assert_eq!(ticks(&tuning, walking, Vector2::zeros(), true, 10), Vector2::zeros());
+
+    // Friction drags in proportion to speed: it adds to deceleration, and slows anything faster than walking...
+    let slippery = MovementTuning { friction: 5.0, ..tuning };
+    assert!((ticks(&slippery, Vector2::new(0.0, 8.0), Vector2::zeros(), true, 1).y - 2.0).abs() < 1e-4);
+    assert!((ticks(&slippery, Vector2::new(0.0, 8.0), Vector2::new(0.0, 1.0), true, 1).y - 4.0).abs() < 1e-4);
+
+    // ...while walking still keeps up its speed.
+    let grippy = MovementTuning { friction: 2.0, ..tuning };
+    assert!((ticks(&grippy, Vector2::zeros(), Vector2::new(0.0, 1.0), true, 20).y - 4.0).abs() < 1e-4);
+
+}
+
+#[test]
+fn test_movementtuning_air_control() {
+
+    let tuning = tuning();
+
+    // In the air, speeding up and slowing down take `air_control` of the effort, and there's no friction.
+    assert!((ticks(&tuning, Vector2::zeros(), Vector2::new(0.0, 1.0), false, 2).y - 0.5).abs() < 1e-4);
+    assert!((ticks(&tuning, Vector2::new(0.0, 4.0), Vector2::zeros(), false, 1).y - 3.5).abs() < 1e-4);
+
+    let slippery = MovementTuning { friction: 5.0, ..tuning };
+    assert!((ticks(&slippery, Vector2::new(0.0, 4.0), Vector2::zeros(), false, 1).y - 3.5).abs() < 1e-4);
+
+    // Without air control, momentum carries on unchanged.
+    let committed = MovementTuning { air_control: 0.0, ..tuning };
+    assert_eq!(ticks(&committed, Vector2::new(1.0, 2.0), Vector2::new(0.0, -1.0), false, 5), Vector2::new(1.0, 2.0));
+
+}
+
+
+/// Horizontal movement parameters of the player.
+#[derive(Visit, Reflect, Debug, Clone, Copy, PartialEq)]
+#[visit(optional)]
+pub struct MovementTuning {
+
+    /// Top speed walking, in metres per second.
+    pub walk_speed:     f32,
+
+    /// Speeding up towards walking speed, in metres per second squared.
+    pub acceleration:   f32,
+
+    /// Slowing down once movement controls are let go, in metres per second squared.
+    pub deceleration:   f32,
+
+    /// Share of `acceleration` and `deceleration` available in the air, from `0.0` (none) to `1.0` (as on the ground).
+    pub air_control:    f32,
+
+    /// Drag on the ground in proportion to speed, as the share of speed lost per second.
+    pub friction:       f32,
+
+    /// Top speed sprinting, as a multiple of `walk_speed`.
+    pub sprint_speed:   f32,
+
+    /// Top speed crouching, as a multiple of `walk_speed`.
+    pub crouch_speed:   f32,
+
+    /// Height of the crouched body, as a share of its standing height.
+    pub crouch_height:  f32,
+
+}
+
+impl MovementTuning {
+
+    /// Horizontal velocity after `dt` seconds, starting at `velocity` with movement controls asking for `wish`.
+    ///
+    /// `wish` is a direction scaled by how far the controls are pushed; it's normalised if longer than one, so pressing
+    /// two directions at once is no faster than one. On the ground, friction drags first, then velocity changes
+    /// towards `wish` at walking speed by up to `acceleration`, or towards a stop by up to `deceleration` when there
+    /// is no `wish`. In the air there's no friction, and both are scaled by `air_control`.
+    pub fn velocity(&self, velocity: Vector2<f32>, wish: Vector2<f32>, grounded: bool, dt: f32) -> Vector2<f32> {
+        self.velocity_at(velocity, wish, self.walk_speed, grounded, dt)
+    }
+
+    /// Top speed while `sprinting` or `crouching`; crouching wins if both.
+    pub fn speed(&self, sprinting: bool, crouching: bool) -> f32 {
+        if crouching { self.walk_speed * self.crouch_speed }
+        else if sprinting { self.walk_speed * self.sprint_speed }
+        else { self.walk_speed }
+    }
+
+    /// Horizontal velocity after `dt` seconds, as in `velocity()`, with a top speed of `speed` instead of walking
+    /// speed.
+    pub fn velocity_at(
+        &self,
+        velocity: Vector2<f32>,
+        wish: Vector2<f32>,
+        speed: f32,
+        grounded: bool,
+        dt: f32
+    ) -> Vector2<f32> {
+
+        let wish = if wish.norm() > 1.0 { wish.normalize() } else { wish };
+        let control = if grounded { 1.0 } else { self.air_control.clamp(0.0, 1.0) };
+
+        let velocity = if grounded {
+            velocity * (1.0 - self.friction * dt).max(0.0)
+        } else {
+            velocity
+        };
+
+        let (target, rate) = if wish.norm() > 0.0 {
+            (wish * speed, self.acceleration)
+        } else {
+            (Vector2::zeros(), self.deceleration)
+        };
+
+        let change = target - velocity;
+        let most = (rate * control * dt).max(0.0);
+        if change.norm() <= most {
+            target
+        } else {
+            velocity + change.normalize() * most
+        }
+
+    }
+
+}
+
+impl Default for MovementTuning {
+    fn default() -> Self {
+        MovementTuning {
+            walk_speed:     4.0,
+            acceleration:   40.0,
+            deceleration:   30.0,
+            air_control:    0.3,
+            friction:       6.0,
+            sprint_speed:   1.6,
+            crouch_speed:   0.5,
+            crouch_height:  0.6,
+        }
+    }
+}
@@ -28,15 +28,65 @@ fn test_playerperspective_defaults() {
 
     // PlayerPerspective defaults to `FirstPerson`.
     let firstperson_pp = PlayerPerspective::default();
-    // TODO: Replace with new_thirdperson() functionality.
-    let thirdperson_pp = PlayerPerspective::ThirdPerson { visualfield_pitch: 0.0, visualfield_yaw: 0.0 };
+    let thirdperson_pp = PlayerPerspective::new_thirdperson();
 
     assert_eq!(firstperson_pp.fov(), Some(75.0));
     assert_eq!(firstperson_pp.pitch(), 0.0);
     assert_eq!(firstperson_pp.yaw(), 0.0);
+    assert_eq!(firstperson_pp.orbit(), None);
 
+    // `ThirdPerson` orbits behind the player, over their right shoulder.
+    assert_eq!(thirdperson_pp.fov(), None);
+    assert_eq!(thirdperson_pp.pitch(), 0.0);
+    assert_eq!(thirdperson_pp.yaw(), 0.0);
+    assert_eq!(thirdperson_pp.orbit(), Some((3.0, 0.5)));
 
-    // TODO: Test for thirdperson defaults below.
+}
+
+#[test]
+fn test_playerperspective_toggle() {
+
+    let mut perspective = PlayerPerspective::default();
+    let mut alternate = None;
+    perspective.set_pitch(-20.0);
+    perspective.set_yaw(135.0);
+
+    // Toggling swaps to third person with the default orbit, keeping where the player was looking.
+    perspective.toggle(&mut alternate);
+    assert_eq!(perspective.orbit(), PlayerPerspective::new_thirdperson().orbit());
+    assert_eq!(perspective.pitch(), -20.0);
+    assert_eq!(perspective.yaw(), 135.0);
+
+    // Toggling back returns to the first person perspective as it was left, still looking the same way.
+    perspective.set_yaw(90.0);
+    perspective.toggle(&mut alternate);
+    assert_eq!(perspective.fov(), Some(75.0));
+    assert_eq!(perspective.pitch(), -20.0);
+    assert_eq!(perspective.yaw(), 90.0);
+    assert_eq!(alternate.as_ref().and_then(PlayerPerspective::orbit), Some((3.0, 0.5)));
+
+}
+
+#[test]
+fn test_springarm_collision() {
+
+    let mut arm = SpringArm::default();
+
+    // Without anything in the way, the arm extends at `extend_speed` until it reaches the orbit distance.
+    assert!((arm.update(0.1, 3.0, None) - arm.extend_speed * 0.1).abs() < 1e-4);
+    for _ in 0..100 {
+        arm.update(0.1, 3.0, None);
+    }
+    assert_eq!(arm.update(0.1, 3.0, None), 3.0);
+
+    // A wall behind the player pulls the camera in at once, short of the wall by `margin`...
+    assert!((arm.update(0.1, 3.0, Some(1.0)) - (1.0 - arm.margin)).abs() < 1e-4);
+
+    // ...but never through the player.
+    assert_eq!(arm.update(0.1, 3.0, Some(0.0)), 0.0);
+
+    // Once clear, it eases back out.
+    assert!(arm.update(0.1, 3.0, None) < 3.0);
 
 }
 
@@ -44,9 +94,39 @@ fn test_playerperspective_defaults() {
 fn test_playerperspective_fov() {
 
     let mut firstperson = PlayerPerspective::default();
+    let mut thirdperson = PlayerPerspective::new_thirdperson();
 
     assert_eq!(firstperson.fov(), Some(75.0));
 
+    // Only `FirstPerson` has an FOV to set.
+    firstperson.set_fov(90.0);
+    thirdperson.set_fov(90.0);
+    assert_eq!(firstperson.fov(), Some(90.0));
+    assert_eq!(thirdperson.fov(), None);
+
+}
+
+#[test]
+fn test_fovtransition() {
+
+    let mut transition = FovTransition::default();
+
+    // The first FOV is taken at once...
+    assert_eq!(transition.update(0.05, 75.0), 75.0);
+
+    // ...then changes ease in, without overshooting.
+    let eased = transition.update(0.05, 90.0);
+    assert!(eased > 75.0 && eased < 90.0);
+    for _ in 0..100 {
+        assert!(transition.update(0.05, 90.0) <= 90.0);
+    }
+    assert!((transition.update(0.05, 90.0) - 90.0).abs() < 1e-3);
+
+    // Without a speed, changes snap.
+    let mut snapping = FovTransition { speed: 0.0, ..Default::default() };
+    snapping.update(0.05, 75.0);
+    assert_eq!(snapping.update(0.05, 90.0), 90.0);
+
 }
 
 /// *PlayerPerspective* tells the game how to position the camera and holds your `pitch` + `yaw`.
@@ -64,6 +144,9 @@ pub enum PlayerPerspective {
     ThirdPerson {
         visualfield_pitch:          f32,
         visualfield_yaw:            f32,
+        // ? Distance the camera orbits at behind the player, and how far right of them it sits.
+        orbit_distance:             f32,
+        shoulder_offset:            f32,
     }
 
 }
@@ -75,6 +158,16 @@ impl PlayerPerspective {
         Self::default()
     }
 
+    /// Creates a `ThirdPerson` perspective, orbiting 3 metres behind and half a metre right of the player.
+    pub fn new_thirdperson() -> Self {
+        PlayerPerspective::ThirdPerson {
+            visualfield_pitch: 0.0,
+            visualfield_yaw: 0.0,
+            orbit_distance: 3.0,
+            shoulder_offset: 0.5
+        }
+    }
+
     /// Creates clone of `self.fov` *f32* if it's available.
     /// 
     /// If an FOV is `Some(...)` then Self is `FirstPerson` mode.
@@ -86,6 +179,18 @@ impl PlayerPerspective {
         }
     }
 
+    /// Creates clone of `(self.orbit_distance, self.shoulder_offset)` if it's available.
+    ///
+    /// Only `ThirdPerson` orbits, so `FirstPerson` is `None`.
+    pub fn orbit(&self) -> Option<(f32, f32)> {
+        match self {
+            PlayerPerspective::FirstPerson { .. } => None,
+            PlayerPerspective::ThirdPerson { orbit_distance, shoulder_offset, .. } => {
+                Some((orbit_distance.clone(), shoulder_offset.clone()))
+            }
+        }
+    }
+
     /// Creates clone of `self.visualfield_pitch` *f32*.
     pub fn pitch(&self) -> f32 {
         match self {
@@ -112,6 +217,15 @@ impl PlayerPerspective {
         self
     }
 
+    /// Sets FOV of `Self` to the new value given through `new`, if it's `FirstPerson`.
+    /// Returns a borrowed, new version of `Self`, if you desire.
+    pub fn set_fov(&mut self, new: f32) -> &Self {
+        if let PlayerPerspective::FirstPerson { fov, .. } = self {
+            *fov = new;
+        }
+        self
+    }
+
     /// Sets yaw of `Self` to the new value given through `new`.
     /// Returns a borrowed, new version of `Self`, if you desire.
     pub fn set_yaw(&mut self, new: f32) -> &Self {
@@ -122,6 +236,27 @@ impl PlayerPerspective {
         self
     }
 
+    /// Switches between `FirstPerson` and `ThirdPerson`, keeping pitch and yaw so the view doesn't jump.
+    ///
+    /// `alternate` holds the perspective switched away from, to be switched back to as it was left. If it's `None` or
+    /// the same mode as `self`, the other mode's defaults are switched to instead.
+    pub fn toggle(&mut self, alternate: &mut Option<PlayerPerspective>) {
+
+        let (pitch, yaw) = (self.pitch(), self.yaw());
+        let mut next = match alternate.take() {
+            Some(next) if next.fov().is_some() != self.fov().is_some() => next,
+            _ => match self {
+                PlayerPerspective::FirstPerson { .. } => Self::new_thirdperson(),
+                PlayerPerspective::ThirdPerson { .. } => Self::default(),
+            }
+        };
+        next.set_pitch(pitch);
+        next.set_yaw(yaw);
+
+        *alternate = Some(std::mem::replace(self, next));
+
+    }
+
 }
 
 impl Display for PlayerPerspective {
@@ -144,3 +279,98 @@ impl Default for PlayerPerspective {
     }
 }
 
+
+/// *SpringArm* holds the `ThirdPerson` camera back from walls between it and the player.
+///
+/// Each tick, something cast for from the player towards the camera says how far it's clear; the arm pulls in short of
+/// any hit at once, so the camera never clips through, and eases back out once clear.
+#[derive(Visit, Reflect, Debug, Clone)]
+#[visit(optional)]
+pub struct SpringArm {
+
+    /// Distance kept from whatever the camera would otherwise be behind, in metres.
+    pub margin:                     f32,
+
+    /// Speed the arm extends back out at, in metres per second.
+    pub extend_speed:               f32,
+
+    /// Current length of the arm, in metres.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    length:                         f32,
+
+}
+
+impl SpringArm {
+
+    /// Advance by `dt` seconds, reaching for `distance` with the nearest hit along the arm at `hit` metres, if any.
+    /// Returns the arm's new length.
+    pub fn update(&mut self, dt: f32, distance: f32, hit: Option<f32>) -> f32 {
+
+        let clear = hit.map_or(distance, |hit| (hit - self.margin).clamp(0.0, distance));
+        self.length = if clear < self.length {
+            clear
+        } else {
+            (self.length + self.extend_speed * dt).min(clear)
+        };
+        self.length
+
+    }
+
+    /// Fold the arm all the way in, so it extends out from the player the next time it's used.
+    pub fn reset(&mut self) {
+        self.length = 0.0;
+    }
+
+}
+
+impl Default for SpringArm {
+    fn default() -> Self {
+        SpringArm {
+            margin: 0.2,
+            extend_speed: 6.0,
+            length: 0.0
+        }
+    }
+}
+
+
+/// *FovTransition* eases the camera's field of view towards the one it's meant to have, so changes to it don't snap.
+#[derive(Visit, Reflect, Debug, Clone)]
+#[visit(optional)]
+pub struct FovTransition {
+
+    /// How quickly the field of view changes, as the share of the remaining change made per second. Zero snaps.
+    pub speed:                      f32,
+
+    /// Current field of view, in degrees, once there is one.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    current:                        Option<f32>,
+
+}
+
+impl FovTransition {
+
+    /// Advance by `dt` seconds towards a field of view of `target` degrees. Returns the field of view to use now.
+    pub fn update(&mut self, dt: f32, target: f32) -> f32 {
+
+        let fov = match self.current {
+            Some(current) if self.speed > 0.0 => current + (target - current) * (1.0 - (-self.speed * dt).exp()),
+            _ => target,
+        };
+        self.current = Some(fov);
+        fov
+
+    }
+
+}
+
+impl Default for FovTransition {
+    fn default() -> Self {
+        FovTransition {
+            speed: 6.0,
+            current: None
+        }
+    }
+}
@@ -1,6 +1,7 @@
 
 use std::path::Path;
 use std::sync::Arc;
+use tracing::warn;
 use fyrox::{
     core::pool::Handle,
     asset::manager::ResourceManager,
@@ -11,7 +12,8 @@ use fyrox::{
 
 // The majority of this code was shamefully stolen from 
 
-pub async fn request(resource_manager: &ResourceManager) -> SkyBox {
+/// Load the skybox textures and build the skybox, or `None` if it can't be built from what loaded.
+pub async fn request(resource_manager: &ResourceManager) -> Option<SkyBox> {
 
     let (front, back, left, right, top, bottom) = fyrox::core::futures::join!(
         resource_manager.request("data/textures/skybox-front.png"),
@@ -29,13 +31,16 @@ pub async fn request(resource_manager: &ResourceManager) -> SkyBox {
         right: right.ok(),
         top: top.ok(),
         bottom: bottom.ok()
-    }.build().unwrap();
+    }.build()
+        .inspect_err(|why| warn!("Unable to build skybox: {:?}", why))
+        .ok()?;
 
-    let skybox_texture = skybox.cubemap().unwrap();
-    let mut data = skybox_texture.data_ref();
-    data.set_s_wrap_mode(TextureWrapMode::ClampToEdge);
-    data.set_t_wrap_mode(TextureWrapMode::ClampToEdge);
+    if let Some(skybox_texture) = skybox.cubemap() {
+        let mut data = skybox_texture.data_ref();
+        data.set_s_wrap_mode(TextureWrapMode::ClampToEdge);
+        data.set_t_wrap_mode(TextureWrapMode::ClampToEdge);
+    }
 
-    skybox
+    Some(skybox)
 
 }
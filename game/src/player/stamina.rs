@@ -0,0 +1,227 @@
+
+use std::fmt::Display;
+use fyrox:: {
+    core:: { visitor::prelude::*, reflect::prelude::*, type_traits::prelude::* },
+
+};
+
+
+
+#[test]
+fn test_playerstamina_safety() {
+
+    let mut playerstamina = PlayerStamina::default();
+
+    // SP and CAP start at 100.
+    assert_eq!(playerstamina.staminapoints, 100);
+    assert_eq!(playerstamina.capacity, 100);
+
+    // You can't regenerate more SP than your CAP, and draining never overflows.
+    assert_eq!(playerstamina.staminapoints_regen(10), &100);
+    assert_eq!(playerstamina.staminapoints_drain(u16::MAX), &0);
+    assert_eq!(playerstamina.staminapoints_drain(1), &0);
+
+    // Capacity is modified through `.capacity_change()`, but never to zero.
+    assert_eq!(playerstamina.capacity_change(0), Err(()));
+    assert_eq!(playerstamina.capacity_change(120), Ok(120));
+    assert_eq!(playerstamina.staminapoints_regen(u16::MAX), &120);
+
+    // The exhaustion threshold can't exceed capacity.
+    assert_eq!(playerstamina.exhaustion_change(121), Err(()));
+    assert_eq!(playerstamina.exhaustion_change(30), Ok(30));
+
+}
+
+#[test]
+fn test_playerstamina_exhaustion() {
+
+    let mut playerstamina = PlayerStamina::default();
+    assert!(!playerstamina.is_exhausted());
+
+    // Running out exhausts...
+    playerstamina.staminapoints_drain(100);
+    assert!(playerstamina.is_exhausted());
+
+    // ...until stamina has regenerated up to the threshold.
+    playerstamina.staminapoints_regen(playerstamina.exhaustion() - 1);
+    assert!(playerstamina.is_exhausted());
+    playerstamina.staminapoints_regen(1);
+    assert!(!playerstamina.is_exhausted());
+
+    // Draining without running out doesn't exhaust.
+    playerstamina.staminapoints_drain(1);
+    assert!(!playerstamina.is_exhausted());
+
+}
+
+#[test]
+fn test_playerstamina_rates() {
+
+    let mut playerstamina = PlayerStamina::default();
+
+    // Sprinting drains at `drain_rate` per second, carrying fractions of points between ticks.
+    for _ in 0..10 {
+        playerstamina.update(0.05, true);
+    }
+    assert_eq!(playerstamina.staminapoints, 100 - (playerstamina.drain_rate * 0.5) as u16);
+
+    // Resting regenerates at `regen_rate` per second, without going past capacity.
+    for _ in 0..1000 {
+        playerstamina.update(0.05, false);
+    }
+    assert_eq!(playerstamina.staminapoints, 100);
+
+}
+
+
+#[derive(Visit, Reflect, Debug, Clone, TypeUuidProvider, ComponentProvider)]
+#[type_uuid(id = "5a0c3a9e-3b1f-4f7e-9d6b-2c4e8f1a7b53")]
+#[visit(optional)]
+pub struct PlayerStamina {
+
+    /// Current stamina points.
+    pub staminapoints:  u16,
+
+    /// Capacity of stamina points, as in, the maximum amount of points.
+    capacity:           u16,
+
+    /// Stamina points needed to recover from exhaustion.
+    exhaustion:         u16,
+
+    /// Stamina points drained per second of sprinting.
+    pub drain_rate:     f32,
+
+    /// Stamina points regenerated per second while not sprinting.
+    pub regen_rate:     f32,
+
+    /// Ran out of stamina, and hasn't regenerated up to `exhaustion` since.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    exhausted:          bool,
+
+    /// Fraction of a point drained (negative) or regenerated (positive), carried between update ticks.
+    #[visit(skip)]
+    #[reflect(hidden)]
+    carry:              f32,
+
+}
+
+impl PlayerStamina {
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return stamina capacity.
+    pub fn capacity(&self) -> &u16 {
+        &self.capacity
+    }
+
+    /// Return stamina points at the current frame.
+    pub fn staminapoints(&self) -> &u16 {
+        &self.staminapoints
+    }
+
+    /// Return the exhaustion threshold.
+    pub fn exhaustion(&self) -> &u16 {
+        &self.exhaustion
+    }
+
+    /// Change stamina capacity to the `new` value provided.
+    /// Returns `Err` if `new` is zero, as a capacity of zero is illogical. The exhaustion threshold is lowered to the
+    /// new capacity if it's above it.
+    pub fn capacity_change(&mut self, new: u16) -> Result<u16, ()> {
+        if new >= 1 {
+            self.capacity = new;
+            self.exhaustion = self.exhaustion.min(new);
+            Ok(self.capacity)
+        }
+        else {
+            Err(())
+        }
+    }
+
+    /// Change the exhaustion threshold to the `new` value provided.
+    /// Returns `Err` if `new` exceeds capacity, as exhaustion could never be recovered from.
+    pub fn exhaustion_change(&mut self, new: u16) -> Result<u16, ()> {
+        if new <= self.capacity {
+            self.exhaustion = new;
+            Ok(self.exhaustion)
+        }
+        else {
+            Err(())
+        }
+    }
+
+    /// Regenerate the amount of stamina points in `add`. Returns the new value.
+    /// This function saturates at capacity instead of overflowing.
+    ///
+    /// ```staminapoints = staminapoints + add```
+    pub fn staminapoints_regen(&mut self, add: u16) -> &u16 {
+        self.staminapoints = self.staminapoints.saturating_add(add).min(self.capacity);
+        if self.staminapoints >= self.exhaustion {
+            self.exhausted = false;
+        }
+        &self.staminapoints
+    }
+
+    /// Drain the amount of stamina points in `subtract`. Returns the new value.
+    /// This function saturates at zero instead of overflowing. Running out exhausts.
+    ///
+    /// ```staminapoints = staminapoints - subtract```
+    pub fn staminapoints_drain(&mut self, subtract: u16) -> &u16 {
+        self.staminapoints = self.staminapoints.saturating_sub(subtract);
+        if self.staminapoints == 0 {
+            self.exhausted = true;
+        }
+        &self.staminapoints
+    }
+
+    /// Drain for `dt` seconds of `sprinting`, or regenerate for `dt` seconds of rest.
+    pub fn update(&mut self, dt: f32, sprinting: bool) {
+
+        // Fractions carry over only while the direction is the same.
+        let rate = if sprinting { -self.drain_rate } else { self.regen_rate };
+        if self.carry * rate < 0.0 {
+            self.carry = 0.0;
+        }
+        self.carry += rate * dt;
+
+        let whole = self.carry.trunc();
+        self.carry -= whole;
+        let points = whole.abs().min(u16::MAX as f32) as u16;
+        if whole < 0.0 {
+            self.staminapoints_drain(points);
+        }
+        else if whole > 0.0 {
+            self.staminapoints_regen(points);
+        }
+
+    }
+
+    /// Returns `true` after running out of stamina, until it has regenerated up to the exhaustion threshold.
+    pub fn is_exhausted(&self) -> bool {
+        self.exhausted
+    }
+
+}
+
+impl Default for PlayerStamina {
+    fn default() -> Self {
+        PlayerStamina {
+            staminapoints:  100,
+            capacity:       100,
+            exhaustion:     25,
+            drain_rate:     20.0,
+            regen_rate:     15.0,
+            exhausted:      false,
+            carry:          0.0,
+        }
+    }
+}
+
+impl Display for PlayerStamina {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("{}/{}", self.staminapoints, self.capacity))
+    }
+}
@@ -0,0 +1,788 @@
+
+use ron;
+use std::{
+    any::Any,
+    hash::Hash,
+    fmt::Debug,
+    marker::Send,
+    collections::HashMap,
+};
+use serde:: { Serialize, Deserialize };
+use fyrox::{
+    core:: { pool::Handle, reflect::prelude::*, visitor::prelude::* }, event::*, gui::{ screen::Screen, widget::WidgetBuilder, wrap_panel::Line, UiNode, UserInterface }, keyboard::*, plugin::PluginContext
+};
+// use super::SettingsComponent;
+
+mod names;
+mod presets;
+
+pub use names::BindingNameError;
+pub use presets::BindingPreset;
+
+
+
+mod tests {
+
+    use super::{ InputSettingsComponent };
+
+    #[test]
+    fn test_inputsettingscomponent_defaults_safety() {
+
+        let isc = InputSettingsComponent::default();
+        // TODO: Write this test to completion.
+
+    }
+
+}
+
+// * Input Settings Component as a subsystem of `super::Settings`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InputSettingsComponent {
+
+    /// All bindings.
+    /// 
+    /// Associates an input source (like key press) with an action meaningful to the engine (like movement across the
+    /// playing field.) Sources may be chorded with modifier keys, and each binding chooses how it's triggered.
+    pub bindings:           Bindings,
+
+    /// Desktop input settings not otherwise applicable as a binding.
+    pub desktop:            desktop_input::DesktopInput,
+
+    /// Gamepad input settings not otherwise applicable as a binding.
+    pub gamepad:            gamepad_input::GamepadInput,
+
+    /// Touch input settings not otherwise applicable as a binding, including the on-screen controls' layout.
+    #[serde(default)]
+    pub touch:              touch_input::TouchInput,
+
+    /// Binding preset last selected. Bindings may have been changed since.
+    #[serde(default)]
+    pub preset:             BindingPreset,
+
+}
+
+impl Default for InputSettingsComponent {
+
+    fn default() -> Self {
+        InputSettingsComponent {
+            bindings:       BindingPreset::default().bindings().unwrap_or_default()
+                                .into_iter()
+                                .chain(touch_input::default_bindings())
+                                .collect(),
+            desktop:        desktop_input::DesktopInput::default(),
+            gamepad:        gamepad_input::GamepadInput::default(),
+            touch:          touch_input::TouchInput::default(),
+            preset:         BindingPreset::default()
+        }
+    }
+
+}
+
+// /// A collection of binds and their associated actions.
+// /// 
+// /// Also contains a *debounce buffer* to filter out repeated messages (like keypress repeat.)
+// #[derive(Debug, Serialize, Deserialize)]
+// pub struct Bindings<I>
+//     where I: Hash + Eq + BindingLayer {
+
+//     pub bindings:   HashMap<I, BindingActions>,
+//     pub debounce:   ()
+
+// }
+
+// impl <I> Default for Bindings<I>
+//     where I: Hash + Eq + BindingLayer {
+//     fn default() -> Self {
+//         Self {
+//             bindings: HashMap::default(),
+//             debounce: ()
+//         }
+//     }
+// }
+
+pub trait BindingLayer<T>
+    where T: Hash {
+
+    // Find actions registered to the given input `source`.
+    // TODO: Document.
+    fn binding_action(&self, source: BindingSources) -> Option<&BindingActions>;
+    // Find input sources registered to the given `action`.
+    // TODO: Document.
+    fn binding_sources(&self, action: BindingActions) -> Option<&[BindingSources]>;
+
+    // Bind an input source to its action.
+    // TODO: Document.
+    fn bind(&mut self, source: BindingSources, action: BindingActions);
+
+    // Unbind an input source from its action.
+    // TODO: Document.
+    fn unbind(&mut self, source: BindingSources);
+
+    // Return 'debounce count' for the provided input `source`.
+    // Sources of inputs where debounce functionality is necessary will always return `Some()`, while inputs that are
+    // not applicable return `None`.
+    // TODO: Document better.
+    fn debounce(&self, source: BindingSources) -> Option<&u32>;
+
+}
+
+/// Input sources which may be bound to a `BindingActions`.
+/// 
+/// Serialized by portable name, such as `"Key:W"`, `"Mouse:Left"` or `"Pad:LeftStickX+"`. See `portable_name()`.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum BindingSources {
+
+    Desktop(desktop_input::DesktopInputSources),
+
+    Gamepad(gamepad_input::GamepadInputSources),
+
+    Touch(touch_input::TouchInputSources)
+
+}
+
+/// All bindings, keyed by the chord which triggers them.
+pub type Bindings = HashMap<BindingChord, Binding>;
+
+/// An input source, optionally chorded with modifier keys that must be held when the source is pressed.
+/// 
+/// Serialized by portable name with modifiers as a prefix, such as `"Key:W"` or `"Shift+Key:W"`.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct BindingChord {
+
+    /// Modifier keys held down for this chord.
+    pub modifiers:  ChordModifiers,
+
+    /// Input source which completes the chord.
+    pub source:     BindingSources,
+
+}
+
+impl BindingChord {
+
+    pub fn new(modifiers: ChordModifiers, source: BindingSources) -> Self {
+        BindingChord { modifiers, source }
+    }
+
+}
+
+impl From<BindingSources> for BindingChord {
+    fn from(value: BindingSources) -> Self {
+        BindingChord::new(ChordModifiers::default(), value)
+    }
+}
+
+/// Modifier keys of a `BindingChord`.
+/// 
+/// Either the left or right key satisfies a modifier, so `shift` is held while `ShiftLeft` *or* `ShiftRight` is.
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq)]
+pub struct ChordModifiers {
+    pub shift:  bool,
+    pub ctrl:   bool,
+    pub alt:    bool,
+    pub logo:   bool,
+}
+
+impl ChordModifiers {
+
+    /// Only `Shift` is held.
+    pub fn shift() -> Self {
+        ChordModifiers { shift: true, ..Default::default() }
+    }
+
+    /// Only `Ctrl` is held.
+    pub fn ctrl() -> Self {
+        ChordModifiers { ctrl: true, ..Default::default() }
+    }
+
+    /// Only `Alt` is held.
+    pub fn alt() -> Self {
+        ChordModifiers { alt: true, ..Default::default() }
+    }
+
+    /// Only `Super` (the *Windows* or *Command* key) is held.
+    pub fn logo() -> Self {
+        ChordModifiers { logo: true, ..Default::default() }
+    }
+
+    /// Modifiers held while the given `sources` are held down.
+    pub fn from_held<'a>(sources: impl IntoIterator<Item = &'a BindingSources>) -> Self {
+
+        let mut modifiers = ChordModifiers::default();
+        for source in sources {
+            if let BindingSources::Desktop(desktop_input::DesktopInputSources::Key(PhysicalKey::Code(code))) = source {
+                match code {
+                    KeyCode::ShiftLeft      | KeyCode::ShiftRight   => modifiers.shift = true,
+                    KeyCode::ControlLeft    | KeyCode::ControlRight => modifiers.ctrl = true,
+                    KeyCode::AltLeft        | KeyCode::AltRight     => modifiers.alt = true,
+                    KeyCode::SuperLeft      | KeyCode::SuperRight   => modifiers.logo = true,
+                    _                                               => {}
+                }
+            }
+        }
+        modifiers
+
+    }
+
+    /// Returns `true` if every modifier held in `other` is also held in `self`.
+    pub fn contains(&self, other: &ChordModifiers) -> bool {
+        (self.shift || !other.shift) &&
+        (self.ctrl  || !other.ctrl) &&
+        (self.alt   || !other.alt) &&
+        (self.logo  || !other.logo)
+    }
+
+    /// Number of modifiers held.
+    pub fn count(&self) -> u8 {
+        self.shift as u8 + self.ctrl as u8 + self.alt as u8 + self.logo as u8
+    }
+
+}
+
+/// A bound action together with the way its chord triggers it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Binding {
+
+    /// Action performed.
+    pub action:     BindingActions,
+
+    /// How pressing and releasing the chord starts and ends the action.
+    #[serde(default)]
+    pub trigger:    BindingTrigger,
+
+}
+
+impl Binding {
+
+    pub fn new(action: BindingActions, trigger: BindingTrigger) -> Self {
+        Binding { action, trigger }
+    }
+
+    /// Action is active for as long as the chord is held.
+    pub fn held(action: BindingActions) -> Self {
+        Binding::new(action, BindingTrigger::Held)
+    }
+
+    /// Each press of the chord switches the action on or off.
+    pub fn toggle(action: BindingActions) -> Self {
+        Binding::new(action, BindingTrigger::Toggle)
+    }
+
+    /// Action starts once the chord has been held for `threshold` seconds.
+    pub fn long_press(action: BindingActions, threshold: f32) -> Self {
+        Binding::new(action, BindingTrigger::LongPress { threshold })
+    }
+
+    /// Action starts when the chord is pressed twice within `window` seconds.
+    pub fn double_tap(action: BindingActions, window: f32) -> Self {
+        Binding::new(action, BindingTrigger::DoubleTap { window })
+    }
+
+}
+
+/// Ways in which a `BindingChord` starts and ends its action.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum BindingTrigger {
+
+    /// Active while held; ends on release.
+    #[default]
+    Held,
+
+    /// Press starts the action, the next press ends it. Releases are ignored.
+    Toggle,
+
+    /// Starts after being held for `threshold` seconds; ends on release. Shorter presses do nothing.
+    LongPress { threshold: f32 },
+
+    /// Starts on the second press within `window` seconds of the first; ends on release.
+    DoubleTap { window: f32 },
+
+}
+
+/// Game actions associated with input.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum BindingActions {
+
+    /// Nonlinear movement on joystick actuation.
+    MovementNonlinear { x: f32, y: f32, z: f32 },
+
+    /// Linear movement on key/button press.
+    MovementLinear (LinearDirectionSetting),
+
+    /// Interact button.
+    Interact,
+
+    /// Jump, while standing on the ground or just after leaving it.
+    Jump,
+
+    /// Move faster while stamina lasts.
+    Sprint,
+
+    /// Move slower and lower, fitting under obstacles.
+    Crouch,
+
+    /// Switch between first- and third-person camera.
+    TogglePerspective,
+
+    /// Come back to life at the last checkpoint, once dead.
+    Respawn,
+
+    /// Open or close the pause menu.
+    Pause,
+
+    /// Back out of the current menu, text field or capture.
+    Cancel,
+
+    /// Open or close the developer console.
+    DeveloperConsole,
+
+}
+
+impl BindingActions {
+
+    pub fn nonlinear(x: f32, y: f32, z: f32) -> Self {
+        BindingActions::MovementNonlinear { x, y, z }
+    }
+
+    pub fn linear(direction: LinearDirectionSetting) -> Self {
+        BindingActions::MovementLinear (direction)
+    }
+
+    pub fn interact() -> Self {
+        BindingActions::Interact
+    }
+
+    pub fn jump() -> Self {
+        BindingActions::Jump
+    }
+
+    pub fn sprint() -> Self {
+        BindingActions::Sprint
+    }
+
+    pub fn crouch() -> Self {
+        BindingActions::Crouch
+    }
+
+    pub fn toggle_perspective() -> Self {
+        BindingActions::TogglePerspective
+    }
+
+    pub fn respawn() -> Self {
+        BindingActions::Respawn
+    }
+
+    pub fn pause() -> Self {
+        BindingActions::Pause
+    }
+
+    pub fn cancel() -> Self {
+        BindingActions::Cancel
+    }
+
+    pub fn developer_console() -> Self {
+        BindingActions::DeveloperConsole
+    }
+
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LinearDirectionSetting {
+    Forward,
+    Backward,
+    Left,
+    Right
+}
+
+impl LinearDirectionSetting {
+
+    pub fn forward() -> Self {
+        Self::Forward
+    }
+
+    pub fn backward() -> Self {
+        Self::Backward
+    }
+
+    pub fn left() -> Self {
+        Self::Left
+    }
+
+    pub fn right() -> Self {
+        Self::Right
+    }
+
+}
+
+pub mod desktop_input {
+
+    use std::{
+        any::Any,
+        hash::Hash,
+        collections::HashMap,
+        default::Default
+    };
+    use serde:: { Serialize, Deserialize };
+    use fyrox::{
+        event::*,
+        core:: { pool::Handle, reflect::prelude::*, visitor::prelude::* },
+        dpi::PhysicalPosition,
+        keyboard::*
+    };
+
+
+    #[derive(Debug, Serialize, Deserialize)]
+    #[serde(default)]
+    pub struct DesktopInput {
+
+        /// Mouse sensitivity multiplier, in degrees of look per mouse count.
+        pub mouse_sensitivity:  f32,
+
+        /// Horizontal mouse look multiplier, on top of `mouse_sensitivity`.
+        pub mouse_scale_x:      f32,
+
+        /// Vertical mouse look multiplier, on top of `mouse_sensitivity`.
+        pub mouse_scale_y:      f32,
+
+        /// Moving the mouse forward looks down instead of up.
+        pub mouse_invert_y:     bool,
+
+        /// Mouse look smoothing, from `0.0` (off) towards `1.0` (heaviest).
+        /// 
+        /// The share of the previous update tick's look carried into the next one.
+        pub mouse_smoothing:    f32,
+
+        /// Mouse acceleration curve.
+        pub mouse_acceleration: MouseAcceleration,
+
+    }
+
+    impl Default for DesktopInput {
+        fn default() -> Self {
+            DesktopInput {
+                mouse_sensitivity:  1.0,
+                mouse_scale_x:      1.0,
+                mouse_scale_y:      1.0,
+                mouse_invert_y:     false,
+                mouse_smoothing:    0.0,
+                mouse_acceleration: MouseAcceleration::default(),
+            }
+        }
+    }
+
+    /// Mouse acceleration curve.
+    /// 
+    /// Faster mouse movement looks further per count: mouse deltas are multiplied by a gain of
+    /// `1 + factor * speed ^ exponent`, capped at `max_gain`, where `speed` is the distance in counts moved over an
+    /// update tick. A `factor` of `0.0` turns acceleration off.
+    #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    #[serde(default)]
+    pub struct MouseAcceleration {
+        pub factor:     f32,
+        pub exponent:   f32,
+        pub max_gain:   f32,
+    }
+
+    impl MouseAcceleration {
+
+        /// Gain applied to a mouse movement of `speed` counts per update tick.
+        pub fn gain(&self, speed: f32) -> f32 {
+            if self.factor <= 0.0 {
+                return 1.0;
+            }
+            (1.0 + self.factor * speed.powf(self.exponent)).min(self.max_gain.max(1.0))
+        }
+
+    }
+
+    impl Default for MouseAcceleration {
+        fn default() -> Self {
+            MouseAcceleration {
+                factor:     0.0,
+                exponent:   1.0,
+                max_gain:   4.0,
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum DesktopInputSources {
+
+        Key(PhysicalKey),
+
+        MouseButton(MouseButton),
+
+        MouseScroll(abstractions::MouseScrollDirection)
+
+    }
+
+    impl From<PhysicalKey> for DesktopInputSources {
+        fn from(value: PhysicalKey) -> Self {
+            DesktopInputSources::Key(value)
+        }
+    }
+
+    impl From<MouseButton> for DesktopInputSources {
+        fn from(value: MouseButton) -> Self {
+            DesktopInputSources::MouseButton(value)
+        }
+    }
+
+    impl From<abstractions::MouseScrollDirection> for DesktopInputSources {
+        fn from(value: abstractions::MouseScrollDirection) -> Self {
+            DesktopInputSources::MouseScroll(value)
+        }
+    }
+
+    pub mod abstractions {
+
+        use serde:: { Serialize, Deserialize };
+        use fyrox::event::MouseScrollDelta;
+
+
+        /// Pixels of a precise (touchpad) scroll counted as one line, like a notch of a mouse wheel.
+        pub const SCROLL_PIXELS_PER_LINE: f32 = 20.0;
+
+        /// Direction of mouse wheel scroll, vertical or horizontal.
+        #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+        pub enum MouseScrollDirection {
+            Up,
+            Down,
+            Left,
+            Right
+        }
+
+        impl MouseScrollDirection {
+
+            /// Direction along the same axis, the other way.
+            pub fn opposite(&self) -> Self {
+                match self {
+                    MouseScrollDirection::Up    => MouseScrollDirection::Down,
+                    MouseScrollDirection::Down  => MouseScrollDirection::Up,
+                    MouseScrollDirection::Left  => MouseScrollDirection::Right,
+                    MouseScrollDirection::Right => MouseScrollDirection::Left,
+                }
+            }
+
+        }
+
+        /// Direction of the larger of the horizontal and vertical parts of a scroll; vertical on a tie.
+        impl From<MouseScrollDelta> for MouseScrollDirection {
+            fn from(other: MouseScrollDelta) -> Self {
+                let (x, y) = scroll_lines(other);
+                if x.abs() > y.abs() { horizontal(x) }
+                else { vertical(y) }
+            }
+        }
+
+        /// Horizontal and vertical scroll of `delta`, in lines. Pixel deltas are normalised by
+        /// `SCROLL_PIXELS_PER_LINE`, so wheels and touchpads scroll alike.
+        pub fn scroll_lines(delta: MouseScrollDelta) -> (f32, f32) {
+            match delta {
+                MouseScrollDelta::LineDelta(x, y)   => (x, y),
+                MouseScrollDelta::PixelDelta(pp)    => (
+                    pp.x as f32 / SCROLL_PIXELS_PER_LINE,
+                    pp.y as f32 / SCROLL_PIXELS_PER_LINE
+                ),
+            }
+        }
+
+        /// Directions `delta` scrolls in, each with how far in lines.
+        pub fn scroll_amounts(delta: MouseScrollDelta) -> impl Iterator<Item = (MouseScrollDirection, f32)> {
+            let (x, y) = scroll_lines(delta);
+            [(horizontal(x), x.abs()), (vertical(y), y.abs())]
+                .into_iter()
+                .filter(|(_, lines)| *lines > 0.0)
+        }
+
+        fn vertical(axis: f32) -> MouseScrollDirection {
+            if axis.is_sign_positive() { MouseScrollDirection::Up }
+            else { MouseScrollDirection::Down }
+        }
+
+        fn horizontal(axis: f32) -> MouseScrollDirection {
+            if axis.is_sign_positive() { MouseScrollDirection::Right }
+            else { MouseScrollDirection::Left }
+        }
+
+    }
+    
+}
+
+pub mod gamepad_input {
+
+    use serde:: { Serialize, Deserialize };
+    use gilrs;
+
+
+    #[derive(Debug, Serialize, Deserialize)]
+    #[serde(default)]
+    pub struct GamepadInput {
+
+        /// Play force feedback (rumble) effects.
+        pub rumble_enabled:     bool,
+
+        /// Force feedback strength multiplier, from `0.0` to `1.0`.
+        pub rumble_intensity:   f32,
+
+    }
+
+    impl Default for GamepadInput {
+        fn default() -> Self {
+            GamepadInput {
+                rumble_enabled:     true,
+                rumble_intensity:   1.0,
+            }
+        }
+    }
+
+    pub type GamepadId = gilrs::GamepadId;
+
+    /// Gamepad elements which may be bound to an action.
+    /// 
+    /// These are the *mapped* buttons and axes of a controller (as in `gilrs::Button` and `gilrs::Axis`) rather than
+    /// raw event codes, which differ between platforms and drivers.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum GamepadInputSources {
+
+        /// Digital or pressure-sensitive button.
+        Button(gilrs::Button),
+
+        /// One half of an analog axis, actuated in `AxisDirection`.
+        Axis(gilrs::Axis, AxisDirection),
+
+    }
+
+    impl From<gilrs::Button> for GamepadInputSources {
+        fn from(value: gilrs::Button) -> Self {
+            GamepadInputSources::Button(value)
+        }
+    }
+
+    /// Direction an analog axis is pushed in.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+    pub enum AxisDirection {
+        Positive,
+        Negative
+    }
+
+}
+
+pub mod touch_input {
+
+    use serde:: { Serialize, Deserialize };
+    use super:: { Binding, BindingActions, BindingChord, Bindings, BindingSources, LinearDirectionSetting };
+
+
+    /// Touch input settings, and the layout of on-screen controls.
+    /// 
+    /// The left half of the screen is a floating virtual stick, centred wherever it's touched down; the right half
+    /// looks around as it's swiped. On-screen buttons take precedence over both.
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    #[serde(default)]
+    pub struct TouchInput {
+
+        /// Distance from where the stick was touched down to full deflection, as a share of screen height.
+        pub stick_radius:       f32,
+
+        /// Stick deflection ignored around its centre, from `0.0` to `1.0`.
+        pub stick_deadzone:     f32,
+
+        /// Degrees of look per screen height swiped.
+        pub look_sensitivity:   f32,
+
+        /// Swiping up looks down instead of up.
+        pub look_invert_y:      bool,
+
+        /// On-screen buttons. Each is bound as `TouchInputSources::Button` with its index here.
+        pub buttons:            Vec<TouchButtonLayout>,
+
+    }
+
+    impl Default for TouchInput {
+        fn default() -> Self {
+            TouchInput {
+                stick_radius:       0.12,
+                stick_deadzone:     0.15,
+                look_sensitivity:   180.0,
+                look_invert_y:      false,
+                buttons:            vec![
+                    TouchButtonLayout { x: 0.85, y: 0.75, radius: 0.08 },
+                    TouchButtonLayout { x: 0.95, y: 0.08, radius: 0.05 },
+                    TouchButtonLayout { x: 0.7, y: 0.85, radius: 0.08 },
+                ],
+            }
+        }
+    }
+
+    /// Position and size of an on-screen button.
+    /// 
+    /// `x` and `y` place its centre as shares of screen width and height from the top left; `radius` is a share of
+    /// screen height, so buttons stay round.
+    #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    pub struct TouchButtonLayout {
+        pub x:          f32,
+        pub y:          f32,
+        pub radius:     f32,
+    }
+
+    impl TouchButtonLayout {
+
+        /// Returns `true` if `position`, in pixels, is on this button on a screen of `screen` pixels.
+        pub fn contains(&self, position: (f32, f32), screen: (f32, f32)) -> bool {
+            let dx = position.0 - self.x * screen.0;
+            let dy = position.1 - self.y * screen.1;
+            (dx * dx + dy * dy).sqrt() <= self.radius * screen.1
+        }
+
+    }
+
+    /// Touch controls which may be bound to an action.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum TouchInputSources {
+
+        /// Virtual stick pushed in `StickDirection`.
+        Stick(StickDirection),
+
+        /// On-screen button, by its index in `TouchInput::buttons`.
+        Button(u8),
+
+    }
+
+    /// Direction the virtual stick is pushed in.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum StickDirection {
+        Up,
+        Down,
+        Left,
+        Right
+    }
+
+    /// Bindings for the default touch layout: the stick moves, and the buttons interact, pause and jump.
+    pub fn default_bindings() -> Bindings {
+
+        let touch = |source| BindingChord::from(BindingSources::Touch(source));
+        let mut bindings = Bindings::new();
+        bindings.insert(
+            touch(TouchInputSources::Stick(StickDirection::Up)),
+            Binding::held(BindingActions::linear(LinearDirectionSetting::forward()))
+        );
+        bindings.insert(
+            touch(TouchInputSources::Stick(StickDirection::Down)),
+            Binding::held(BindingActions::linear(LinearDirectionSetting::backward()))
+        );
+        bindings.insert(
+            touch(TouchInputSources::Stick(StickDirection::Left)),
+            Binding::held(BindingActions::linear(LinearDirectionSetting::left()))
+        );
+        bindings.insert(
+            touch(TouchInputSources::Stick(StickDirection::Right)),
+            Binding::held(BindingActions::linear(LinearDirectionSetting::right()))
+        );
+        bindings.insert(touch(TouchInputSources::Button(0)), Binding::held(BindingActions::interact()));
+        bindings.insert(touch(TouchInputSources::Button(1)), Binding::held(BindingActions::pause()));
+        bindings.insert(touch(TouchInputSources::Button(2)), Binding::held(BindingActions::jump()));
+        bindings
+
+    }
+
+}
@@ -0,0 +1,705 @@
+//! 
+//! Portable, human-readable names for binding sources.
+//! 
+//! Binding sources are written to `gamesettings.ron` as `"<Family>:<Name>"` strings instead of platform-specific codes,
+//! so the settings file can be read, diffed and carried over to another machine. Examples include `"Key:W"`,
+//! `"Mouse:Left"`, `"Mouse:ScrollUp"`, `"Pad:South"`, `"Pad:LeftStickX+"`, `"Touch:StickUp"` and `"Touch:Button0"`.
+//! 
+//! Chords prefix their modifiers in a fixed order, like `"Shift+Key:W"` or `"Ctrl+Alt+Key:Delete"`.
+//! 
+
+use std::{ fmt::Display, str::FromStr };
+use serde:: { Serialize, Serializer, Deserialize, Deserializer };
+use fyrox::{ event::MouseButton, keyboard::{ KeyCode, PhysicalKey } };
+use gilrs:: { Axis, Button };
+use super:: {
+    BindingChord,
+    BindingSources,
+    ChordModifiers,
+    desktop_input:: { DesktopInputSources, abstractions::MouseScrollDirection },
+    gamepad_input:: { GamepadInputSources, AxisDirection },
+    touch_input:: { StickDirection, TouchInputSources },
+};
+
+
+#[test]
+fn test_bindingnames_keys_roundtrip() {
+
+    // Every named key survives a trip through its portable name.
+    for code in KEY_CODES {
+        let source = BindingSources::Desktop(DesktopInputSources::Key(PhysicalKey::Code(*code)));
+        let name = source.portable_name().unwrap();
+        assert_eq!(name.parse::<BindingSources>(), Ok(source));
+    }
+
+    // Letters and digits use their short names.
+    let w = BindingSources::Desktop(DesktopInputSources::Key(PhysicalKey::Code(KeyCode::KeyW)));
+    let one = BindingSources::Desktop(DesktopInputSources::Key(PhysicalKey::Code(KeyCode::Digit1)));
+    assert_eq!(w.to_string(), "Key:W");
+    assert_eq!(one.to_string(), "Key:1");
+
+}
+
+#[test]
+fn test_bindingnames_mouse_roundtrip() {
+
+    let sources = [
+        DesktopInputSources::MouseButton(MouseButton::Left),
+        DesktopInputSources::MouseButton(MouseButton::Right),
+        DesktopInputSources::MouseButton(MouseButton::Middle),
+        DesktopInputSources::MouseButton(MouseButton::Back),
+        DesktopInputSources::MouseButton(MouseButton::Forward),
+        DesktopInputSources::MouseButton(MouseButton::Other(8)),
+        DesktopInputSources::MouseScroll(MouseScrollDirection::Up),
+        DesktopInputSources::MouseScroll(MouseScrollDirection::Down),
+        DesktopInputSources::MouseScroll(MouseScrollDirection::Left),
+        DesktopInputSources::MouseScroll(MouseScrollDirection::Right),
+    ];
+
+    for each in sources {
+        let source = BindingSources::Desktop(each);
+        assert_eq!(source.to_string().parse::<BindingSources>(), Ok(source));
+    }
+
+    assert_eq!("Mouse:Left".parse::<BindingSources>(), Ok(BindingSources::Desktop(DesktopInputSources::MouseButton(MouseButton::Left))));
+    assert_eq!("Mouse:Button8".parse::<BindingSources>(), Ok(BindingSources::Desktop(DesktopInputSources::MouseButton(MouseButton::Other(8)))));
+
+}
+
+#[test]
+fn test_bindingnames_gamepad_roundtrip() {
+
+    for button in PAD_BUTTONS {
+        let source = BindingSources::Gamepad(GamepadInputSources::Button(*button));
+        assert_eq!(source.to_string().parse::<BindingSources>(), Ok(source));
+    }
+
+    for axis in PAD_AXES {
+        for direction in [AxisDirection::Positive, AxisDirection::Negative] {
+            let source = BindingSources::Gamepad(GamepadInputSources::Axis(*axis, direction));
+            assert_eq!(source.to_string().parse::<BindingSources>(), Ok(source));
+        }
+    }
+
+    assert_eq!("Pad:South".parse::<BindingSources>(), Ok(BindingSources::Gamepad(GamepadInputSources::Button(Button::South))));
+    assert_eq!(
+        "Pad:LeftStickX+".parse::<BindingSources>(),
+        Ok(BindingSources::Gamepad(GamepadInputSources::Axis(Axis::LeftStickX, AxisDirection::Positive)))
+    );
+
+}
+
+#[test]
+fn test_bindingnames_touch_roundtrip() {
+
+    let mut sources: Vec<TouchInputSources> = [
+        StickDirection::Up,
+        StickDirection::Down,
+        StickDirection::Left,
+        StickDirection::Right,
+    ].into_iter().map(TouchInputSources::Stick).collect();
+    sources.extend((0..=u8::MAX).map(TouchInputSources::Button));
+
+    for each in sources {
+        let source = BindingSources::Touch(each);
+        assert_eq!(source.portable_name().unwrap().parse::<BindingSources>(), Ok(source));
+    }
+
+    assert_eq!("Touch:StickUp".parse::<BindingSources>(), Ok(BindingSources::Touch(TouchInputSources::Stick(StickDirection::Up))));
+    assert_eq!("Touch:Button1".parse::<BindingSources>(), Ok(BindingSources::Touch(TouchInputSources::Button(1))));
+    assert!("Touch:Button256".parse::<BindingSources>().is_err());
+
+}
+
+#[test]
+fn test_bindingnames_errors() {
+
+    // Unknown families, names and malformed strings are rejected with a precise error.
+    assert_eq!("W".parse::<BindingSources>(), Err(BindingNameError::MissingFamily(String::from("W"))));
+    assert_eq!("Joy:W".parse::<BindingSources>(), Err(BindingNameError::UnknownFamily(String::from("Joy"))));
+    assert_eq!("Key:Nope".parse::<BindingSources>(), Err(BindingNameError::UnknownName(String::from("Key:Nope"))));
+    assert_eq!("Pad:LeftStickX".parse::<BindingSources>(), Err(BindingNameError::UnknownName(String::from("Pad:LeftStickX"))));
+    assert_eq!("Mouse:Button".parse::<BindingSources>(), Err(BindingNameError::UnknownName(String::from("Mouse:Button"))));
+
+    // Sources without a portable name can't be written out.
+    let unknown = BindingSources::Gamepad(GamepadInputSources::Button(Button::Unknown));
+    assert!(unknown.portable_name().is_err());
+
+}
+
+#[test]
+fn test_bindingnames_chords() {
+
+    let w = BindingSources::Desktop(DesktopInputSources::Key(PhysicalKey::Code(KeyCode::KeyW)));
+
+    // Chords without modifiers are written just like their source.
+    assert_eq!(BindingChord::from(w).to_string(), "Key:W");
+    assert_eq!(BindingChord::new(ChordModifiers::shift(), w).to_string(), "Shift+Key:W");
+
+    // Modifiers are always written in the same order, but may be read in any order.
+    let modifiers = ChordModifiers { ctrl: true, alt: true, ..Default::default() };
+    assert_eq!(BindingChord::new(modifiers, w).to_string(), "Ctrl+Alt+Key:W");
+    assert_eq!("Alt+Ctrl+Key:W".parse::<BindingChord>(), Ok(BindingChord::new(modifiers, w)));
+
+    // Axis directions aren't confused with chord separators.
+    let chord = BindingChord::new(
+        ChordModifiers::shift(),
+        BindingSources::Gamepad(GamepadInputSources::Axis(Axis::LeftStickX, AxisDirection::Positive))
+    );
+    assert_eq!(chord.to_string().parse::<BindingChord>(), Ok(chord));
+
+    assert_eq!("Hyper+Key:W".parse::<BindingChord>(), Err(BindingNameError::UnknownFamily(String::from("Hyper+Key"))));
+
+}
+
+#[test]
+fn test_bindingnames_ron_roundtrip() {
+
+    let isc = super::InputSettingsComponent::default();
+    let buffer = ron::ser::to_string_pretty(&isc, ron::ser::PrettyConfig::default()).unwrap();
+
+    // Bindings are written as readable names.
+    assert!(buffer.contains("\"Key:W\""));
+    assert!(buffer.contains("Held"));
+
+    let isc_loaded: super::InputSettingsComponent = ron::de::from_str(&buffer).unwrap();
+    assert_eq!(isc_loaded.bindings.len(), isc.bindings.len());
+    for (chord, binding) in &isc.bindings {
+        assert_eq!(isc_loaded.bindings.get(chord), Some(binding));
+    }
+
+}
+
+
+/// Error returned when a binding source has no portable name, or a name can't be parsed back into a binding source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BindingNameError {
+
+    /// Name is missing its `"<Family>:"` prefix.
+    MissingFamily(String),
+
+    /// Family prefix is not one of `Key`, `Mouse`, `Pad` or `Touch`.
+    UnknownFamily(String),
+
+    /// Family is known but the name after the prefix is not.
+    UnknownName(String),
+
+    /// Binding source is platform-specific or unknown and can't be given a portable name.
+    Unnameable(String),
+
+}
+
+impl Display for BindingNameError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingFamily(name)   => write!(f, "binding name '{name}' is missing a family prefix like 'Key:'"),
+            Self::UnknownFamily(family) => write!(f, "binding family '{family}' is unknown"),
+            Self::UnknownName(name)     => write!(f, "binding name '{name}' is unknown"),
+            Self::Unnameable(source)    => write!(f, "binding source {source} has no portable name"),
+        }
+    }
+}
+
+impl std::error::Error for BindingNameError {}
+
+impl BindingSources {
+
+    /// Portable name of this binding source, such as `"Key:W"` or `"Pad:LeftStickX+"`.
+    /// 
+    /// Returns `Err` for sources that have no portable name: keys the platform couldn't identify and gamepad
+    /// elements unknown to the controller mapping.
+    pub fn portable_name(&self) -> Result<String, BindingNameError> {
+
+        let unnameable = || BindingNameError::Unnameable(format!("{self:?}"));
+
+        match self {
+
+            BindingSources::Desktop(DesktopInputSources::Key(PhysicalKey::Code(code))) => {
+                key_name(*code).map(|name| format!("Key:{name}")).ok_or_else(unnameable)
+            },
+
+            BindingSources::Desktop(DesktopInputSources::Key(PhysicalKey::Unidentified(_))) => Err(unnameable()),
+
+            BindingSources::Desktop(DesktopInputSources::MouseButton(button)) => {
+                Ok(match button {
+                    MouseButton::Left       => String::from("Mouse:Left"),
+                    MouseButton::Right      => String::from("Mouse:Right"),
+                    MouseButton::Middle     => String::from("Mouse:Middle"),
+                    MouseButton::Back       => String::from("Mouse:Back"),
+                    MouseButton::Forward    => String::from("Mouse:Forward"),
+                    MouseButton::Other(id)  => format!("Mouse:Button{id}"),
+                })
+            },
+
+            BindingSources::Desktop(DesktopInputSources::MouseScroll(direction)) => {
+                Ok(match direction {
+                    MouseScrollDirection::Up    => String::from("Mouse:ScrollUp"),
+                    MouseScrollDirection::Down  => String::from("Mouse:ScrollDown"),
+                    MouseScrollDirection::Left  => String::from("Mouse:ScrollLeft"),
+                    MouseScrollDirection::Right => String::from("Mouse:ScrollRight"),
+                })
+            },
+
+            BindingSources::Gamepad(GamepadInputSources::Button(button)) => {
+                pad_button_name(*button).map(|name| format!("Pad:{name}")).ok_or_else(unnameable)
+            },
+
+            BindingSources::Gamepad(GamepadInputSources::Axis(axis, direction)) => {
+                let sign = match direction {
+                    AxisDirection::Positive => '+',
+                    AxisDirection::Negative => '-',
+                };
+                pad_axis_name(*axis).map(|name| format!("Pad:{name}{sign}")).ok_or_else(unnameable)
+            },
+
+            BindingSources::Touch(TouchInputSources::Stick(direction)) => {
+                Ok(match direction {
+                    StickDirection::Up      => String::from("Touch:StickUp"),
+                    StickDirection::Down    => String::from("Touch:StickDown"),
+                    StickDirection::Left    => String::from("Touch:StickLeft"),
+                    StickDirection::Right   => String::from("Touch:StickRight"),
+                })
+            },
+
+            BindingSources::Touch(TouchInputSources::Button(index)) => Ok(format!("Touch:Button{index}")),
+
+        }
+
+    }
+
+}
+
+impl Display for BindingSources {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.portable_name() {
+            Ok(name)    => f.write_str(&name),
+            Err(_)      => write!(f, "{self:?}"),
+        }
+    }
+}
+
+impl FromStr for BindingSources {
+
+    type Err = BindingNameError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+
+        let (family, name) = s.split_once(':')
+            .ok_or_else(|| BindingNameError::MissingFamily(String::from(s)))?;
+        let unknown = || BindingNameError::UnknownName(String::from(s));
+
+        match family {
+
+            "Key"   => {
+                key_from_name(name)
+                    .map(|code| BindingSources::Desktop(DesktopInputSources::Key(PhysicalKey::Code(code))))
+                    .ok_or_else(unknown)
+            },
+
+            "Mouse" => {
+                let source = match name {
+                    "Left"          => DesktopInputSources::MouseButton(MouseButton::Left),
+                    "Right"         => DesktopInputSources::MouseButton(MouseButton::Right),
+                    "Middle"        => DesktopInputSources::MouseButton(MouseButton::Middle),
+                    "Back"          => DesktopInputSources::MouseButton(MouseButton::Back),
+                    "Forward"       => DesktopInputSources::MouseButton(MouseButton::Forward),
+                    "ScrollUp"      => DesktopInputSources::MouseScroll(MouseScrollDirection::Up),
+                    "ScrollDown"    => DesktopInputSources::MouseScroll(MouseScrollDirection::Down),
+                    "ScrollLeft"    => DesktopInputSources::MouseScroll(MouseScrollDirection::Left),
+                    "ScrollRight"   => DesktopInputSources::MouseScroll(MouseScrollDirection::Right),
+                    other           => {
+                        let id = other.strip_prefix("Button")
+                            .and_then(|id| id.parse::<u16>().ok())
+                            .ok_or_else(unknown)?;
+                        DesktopInputSources::MouseButton(MouseButton::Other(id))
+                    }
+                };
+                Ok(BindingSources::Desktop(source))
+            },
+
+            "Pad"   => {
+                // Axes carry a trailing `+` or `-` for the direction of actuation; buttons carry none.
+                let source = if let Some(axis) = name.strip_suffix('+') {
+                    pad_axis_from_name(axis).map(|axis| GamepadInputSources::Axis(axis, AxisDirection::Positive))
+                } else if let Some(axis) = name.strip_suffix('-') {
+                    pad_axis_from_name(axis).map(|axis| GamepadInputSources::Axis(axis, AxisDirection::Negative))
+                } else {
+                    pad_button_from_name(name).map(GamepadInputSources::Button)
+                };
+                source.map(BindingSources::Gamepad).ok_or_else(unknown)
+            },
+
+            "Touch" => {
+                let source = match name {
+                    "StickUp"       => TouchInputSources::Stick(StickDirection::Up),
+                    "StickDown"     => TouchInputSources::Stick(StickDirection::Down),
+                    "StickLeft"     => TouchInputSources::Stick(StickDirection::Left),
+                    "StickRight"    => TouchInputSources::Stick(StickDirection::Right),
+                    other           => {
+                        let index = other.strip_prefix("Button")
+                            .and_then(|index| index.parse::<u8>().ok())
+                            .ok_or_else(unknown)?;
+                        TouchInputSources::Button(index)
+                    }
+                };
+                Ok(BindingSources::Touch(source))
+            },
+
+            other   => Err(BindingNameError::UnknownFamily(String::from(other))),
+
+        }
+
+    }
+
+}
+
+impl Display for BindingChord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (held, name) in modifier_names(&self.modifiers) {
+            if held {
+                write!(f, "{name}+")?;
+            }
+        }
+        write!(f, "{}", self.source)
+    }
+}
+
+impl FromStr for BindingChord {
+
+    type Err = BindingNameError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+
+        let mut modifiers = ChordModifiers::default();
+        let mut rest = s;
+
+        // Strip modifier prefixes until only the source remains.
+        'prefixes: loop {
+            for name in MODIFIER_NAMES {
+                if let Some(stripped) = rest.strip_prefix(name).and_then(|r| r.strip_prefix('+')) {
+                    match name {
+                        "Shift" => modifiers.shift = true,
+                        "Ctrl"  => modifiers.ctrl = true,
+                        "Alt"   => modifiers.alt = true,
+                        _       => modifiers.logo = true,
+                    }
+                    rest = stripped;
+                    continue 'prefixes;
+                }
+            }
+            break;
+        }
+
+        Ok(BindingChord::new(modifiers, rest.parse()?))
+
+    }
+
+}
+
+impl Serialize for BindingChord {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer {
+        // Check the source is nameable first, as `Display` can't report errors.
+        self.source.portable_name().map_err(serde::ser::Error::custom)?;
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for BindingChord {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de> {
+        let name = String::deserialize(deserializer)?;
+        name.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+// Names of chord modifiers, in the order they're written.
+const MODIFIER_NAMES: [&str; 4] = ["Shift", "Ctrl", "Alt", "Super"];
+
+fn modifier_names(modifiers: &ChordModifiers) -> [(bool, &'static str); 4] {
+    [
+        (modifiers.shift,   MODIFIER_NAMES[0]),
+        (modifiers.ctrl,    MODIFIER_NAMES[1]),
+        (modifiers.alt,     MODIFIER_NAMES[2]),
+        (modifiers.logo,    MODIFIER_NAMES[3]),
+    ]
+}
+
+impl Serialize for BindingSources {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer {
+        let name = self.portable_name().map_err(serde::ser::Error::custom)?;
+        serializer.serialize_str(&name)
+    }
+}
+
+impl<'de> Deserialize<'de> for BindingSources {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de> {
+        let name = String::deserialize(deserializer)?;
+        name.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+// Generates the two-way lookup between an enum's variants and their portable names, plus a list of every named
+// variant (used by tests to check each name round-trips).
+macro_rules! portable_names {
+    (
+        $ty:ident, $all:ident, $to_name:ident, $from_name:ident;
+        $( $variant:ident => $name:literal ),* $(,)?
+    ) => {
+
+        #[allow(dead_code)]
+        const $all: &[$ty] = &[ $( $ty::$variant ),* ];
+
+        #[allow(unreachable_patterns)]
+        fn $to_name(value: $ty) -> Option<&'static str> {
+            match value {
+                $( $ty::$variant => Some($name), )*
+                _ => None,
+            }
+        }
+
+        fn $from_name(name: &str) -> Option<$ty> {
+            match name {
+                $( $name => Some($ty::$variant), )*
+                _ => None,
+            }
+        }
+
+    };
+}
+
+portable_names! {
+    Button, PAD_BUTTONS, pad_button_name, pad_button_from_name;
+    South           => "South",
+    East            => "East",
+    North           => "North",
+    West            => "West",
+    C               => "C",
+    Z               => "Z",
+    LeftTrigger     => "LeftTrigger",
+    LeftTrigger2    => "LeftTrigger2",
+    RightTrigger    => "RightTrigger",
+    RightTrigger2   => "RightTrigger2",
+    Select          => "Select",
+    Start           => "Start",
+    Mode            => "Mode",
+    LeftThumb       => "LeftThumb",
+    RightThumb      => "RightThumb",
+    DPadUp          => "DPadUp",
+    DPadDown        => "DPadDown",
+    DPadLeft        => "DPadLeft",
+    DPadRight       => "DPadRight",
+}
+
+portable_names! {
+    Axis, PAD_AXES, pad_axis_name, pad_axis_from_name;
+    LeftStickX      => "LeftStickX",
+    LeftStickY      => "LeftStickY",
+    LeftZ           => "LeftZ",
+    RightStickX     => "RightStickX",
+    RightStickY     => "RightStickY",
+    RightZ          => "RightZ",
+    DPadX           => "DPadX",
+    DPadY           => "DPadY",
+}
+
+// Letter and digit keys are written by their legend (`W`, `1`); every other key keeps its `KeyCode` name.
+portable_names! {
+    KeyCode, KEY_CODES, key_name, key_from_name;
+    Backquote               => "Backquote",
+    Backslash               => "Backslash",
+    BracketLeft             => "BracketLeft",
+    BracketRight            => "BracketRight",
+    Comma                   => "Comma",
+    Digit0                  => "0",
+    Digit1                  => "1",
+    Digit2                  => "2",
+    Digit3                  => "3",
+    Digit4                  => "4",
+    Digit5                  => "5",
+    Digit6                  => "6",
+    Digit7                  => "7",
+    Digit8                  => "8",
+    Digit9                  => "9",
+    Equal                   => "Equal",
+    IntlBackslash           => "IntlBackslash",
+    IntlRo                  => "IntlRo",
+    IntlYen                 => "IntlYen",
+    KeyA                    => "A",
+    KeyB                    => "B",
+    KeyC                    => "C",
+    KeyD                    => "D",
+    KeyE                    => "E",
+    KeyF                    => "F",
+    KeyG                    => "G",
+    KeyH                    => "H",
+    KeyI                    => "I",
+    KeyJ                    => "J",
+    KeyK                    => "K",
+    KeyL                    => "L",
+    KeyM                    => "M",
+    KeyN                    => "N",
+    KeyO                    => "O",
+    KeyP                    => "P",
+    KeyQ                    => "Q",
+    KeyR                    => "R",
+    KeyS                    => "S",
+    KeyT                    => "T",
+    KeyU                    => "U",
+    KeyV                    => "V",
+    KeyW                    => "W",
+    KeyX                    => "X",
+    KeyY                    => "Y",
+    KeyZ                    => "Z",
+    Minus                   => "Minus",
+    Period                  => "Period",
+    Quote                   => "Quote",
+    Semicolon               => "Semicolon",
+    Slash                   => "Slash",
+    AltLeft                 => "AltLeft",
+    AltRight                => "AltRight",
+    Backspace               => "Backspace",
+    CapsLock                => "CapsLock",
+    ContextMenu             => "ContextMenu",
+    ControlLeft             => "ControlLeft",
+    ControlRight            => "ControlRight",
+    Enter                   => "Enter",
+    SuperLeft               => "SuperLeft",
+    SuperRight              => "SuperRight",
+    ShiftLeft               => "ShiftLeft",
+    ShiftRight              => "ShiftRight",
+    Space                   => "Space",
+    Tab                     => "Tab",
+    Convert                 => "Convert",
+    KanaMode                => "KanaMode",
+    Lang1                   => "Lang1",
+    Lang2                   => "Lang2",
+    Lang3                   => "Lang3",
+    Lang4                   => "Lang4",
+    Lang5                   => "Lang5",
+    NonConvert              => "NonConvert",
+    Delete                  => "Delete",
+    End                     => "End",
+    Help                    => "Help",
+    Home                    => "Home",
+    Insert                  => "Insert",
+    PageDown                => "PageDown",
+    PageUp                  => "PageUp",
+    ArrowDown               => "ArrowDown",
+    ArrowLeft               => "ArrowLeft",
+    ArrowRight              => "ArrowRight",
+    ArrowUp                 => "ArrowUp",
+    NumLock                 => "NumLock",
+    Numpad0                 => "Numpad0",
+    Numpad1                 => "Numpad1",
+    Numpad2                 => "Numpad2",
+    Numpad3                 => "Numpad3",
+    Numpad4                 => "Numpad4",
+    Numpad5                 => "Numpad5",
+    Numpad6                 => "Numpad6",
+    Numpad7                 => "Numpad7",
+    Numpad8                 => "Numpad8",
+    Numpad9                 => "Numpad9",
+    NumpadAdd               => "NumpadAdd",
+    NumpadBackspace         => "NumpadBackspace",
+    NumpadClear             => "NumpadClear",
+    NumpadClearEntry        => "NumpadClearEntry",
+    NumpadComma             => "NumpadComma",
+    NumpadDecimal           => "NumpadDecimal",
+    NumpadDivide            => "NumpadDivide",
+    NumpadEnter             => "NumpadEnter",
+    NumpadEqual             => "NumpadEqual",
+    NumpadHash              => "NumpadHash",
+    NumpadMemoryAdd         => "NumpadMemoryAdd",
+    NumpadMemoryClear       => "NumpadMemoryClear",
+    NumpadMemoryRecall      => "NumpadMemoryRecall",
+    NumpadMemoryStore       => "NumpadMemoryStore",
+    NumpadMemorySubtract    => "NumpadMemorySubtract",
+    NumpadMultiply          => "NumpadMultiply",
+    NumpadParenLeft         => "NumpadParenLeft",
+    NumpadParenRight        => "NumpadParenRight",
+    NumpadStar              => "NumpadStar",
+    NumpadSubtract          => "NumpadSubtract",
+    Escape                  => "Escape",
+    Fn                      => "Fn",
+    FnLock                  => "FnLock",
+    PrintScreen             => "PrintScreen",
+    ScrollLock              => "ScrollLock",
+    Pause                   => "Pause",
+    BrowserBack             => "BrowserBack",
+    BrowserFavorites        => "BrowserFavorites",
+    BrowserForward          => "BrowserForward",
+    BrowserHome             => "BrowserHome",
+    BrowserRefresh          => "BrowserRefresh",
+    BrowserSearch           => "BrowserSearch",
+    BrowserStop             => "BrowserStop",
+    Eject                   => "Eject",
+    LaunchApp1              => "LaunchApp1",
+    LaunchApp2              => "LaunchApp2",
+    LaunchMail              => "LaunchMail",
+    MediaPlayPause          => "MediaPlayPause",
+    MediaSelect             => "MediaSelect",
+    MediaStop               => "MediaStop",
+    MediaTrackNext          => "MediaTrackNext",
+    MediaTrackPrevious      => "MediaTrackPrevious",
+    Power                   => "Power",
+    Sleep                   => "Sleep",
+    AudioVolumeDown         => "AudioVolumeDown",
+    AudioVolumeMute         => "AudioVolumeMute",
+    AudioVolumeUp           => "AudioVolumeUp",
+    WakeUp                  => "WakeUp",
+    Meta                    => "Meta",
+    Hyper                   => "Hyper",
+    Turbo                   => "Turbo",
+    Abort                   => "Abort",
+    Resume                  => "Resume",
+    Suspend                 => "Suspend",
+    Again                   => "Again",
+    Copy                    => "Copy",
+    Cut                     => "Cut",
+    Find                    => "Find",
+    Open                    => "Open",
+    Paste                   => "Paste",
+    Props                   => "Props",
+    Select                  => "Select",
+    Undo                    => "Undo",
+    Hiragana                => "Hiragana",
+    Katakana                => "Katakana",
+    F1                      => "F1",
+    F2                      => "F2",
+    F3                      => "F3",
+    F4                      => "F4",
+    F5                      => "F5",
+    F6                      => "F6",
+    F7                      => "F7",
+    F8                      => "F8",
+    F9                      => "F9",
+    F10                     => "F10",
+    F11                     => "F11",
+    F12                     => "F12",
+    F13                     => "F13",
+    F14                     => "F14",
+    F15                     => "F15",
+    F16                     => "F16",
+    F17                     => "F17",
+    F18                     => "F18",
+    F19                     => "F19",
+    F20                     => "F20",
+    F21                     => "F21",
+    F22                     => "F22",
+    F23                     => "F23",
+    F24                     => "F24",
+    F25                     => "F25",
+    F26                     => "F26",
+    F27                     => "F27",
+    F28                     => "F28",
+    F29                     => "F29",
+    F30                     => "F30",
+    F31                     => "F31",
+    F32                     => "F32",
+    F33                     => "F33",
+    F34                     => "F34",
+    F35                     => "F35",
+}
@@ -0,0 +1,242 @@
+//!
+//! Binding presets.
+//!
+//! Named sets of keyboard and mouse bindings the player can pick from settings, plus custom presets saved to and loaded
+//! from files.
+//!
+//! Keys are bound by their position on the keyboard rather than the letter printed on them (see `PhysicalKey`), so one
+//! preset puts movement under the same fingers whatever the layout prints there: `WASD` on QWERTY, `ZQSD` on AZERTY
+//! and `,AOE` on Dvorak. `Azerty` and `Dvorak` are aliases of that `Qwerty` preset, so players looking for their
+//! layout by name find it; settings offer it once, labelled with all three.
+//!
+
+use std::{ fs, io, io::Write, path::{ Path, PathBuf } };
+use serde:: { Serialize, Deserialize };
+use fyrox::keyboard:: { KeyCode, PhysicalKey };
+use super:: {
+    Binding,
+    BindingActions,
+    BindingChord,
+    Bindings,
+    BindingSources,
+    InputSettingsComponent,
+    LinearDirectionSetting,
+    desktop_input::DesktopInputSources,
+};
+
+
+#[test]
+fn test_bindingpresets_complete() {
+
+    for preset in BindingPreset::BUILTIN.iter().chain(&BindingPreset::ALIASES) {
+
+        let bindings = preset.bindings().unwrap();
+        let bound = |action: BindingActions| bindings.values().any(|binding| binding.action == action);
+
+        // Every built-in preset moves, interacts and pauses.
+        assert!(bound(BindingActions::linear(LinearDirectionSetting::forward())), "{preset:?}");
+        assert!(bound(BindingActions::linear(LinearDirectionSetting::backward())), "{preset:?}");
+        assert!(bound(BindingActions::linear(LinearDirectionSetting::left())), "{preset:?}");
+        assert!(bound(BindingActions::linear(LinearDirectionSetting::right())), "{preset:?}");
+        assert!(bound(BindingActions::interact()), "{preset:?}");
+        assert!(bound(BindingActions::jump()), "{preset:?}");
+        assert!(bound(BindingActions::sprint()), "{preset:?}");
+        assert!(bound(BindingActions::crouch()), "{preset:?}");
+        assert!(bound(BindingActions::toggle_perspective()), "{preset:?}");
+        assert!(bound(BindingActions::pause()), "{preset:?}");
+
+    }
+
+    // Layout presets are one and the same, bound by key position...
+    let qwerty = BindingPreset::Qwerty.bindings().unwrap();
+    for alias in BindingPreset::ALIASES {
+        assert_eq!(alias.bindings().unwrap(), qwerty, "{alias:?}");
+        assert!(alias.label().contains("AZERTY") && alias.label().contains("Dvorak"), "{alias:?}");
+    }
+    assert!(!BindingPreset::BUILTIN.iter().any(|preset| BindingPreset::ALIASES.contains(preset)));
+
+    // ...while presets for other hands actually move the keys.
+    let key = |code| BindingChord::from(BindingSources::Desktop(DesktopInputSources::Key(PhysicalKey::Code(code))));
+    assert!(BindingPreset::Qwerty.bindings().unwrap().contains_key(&key(KeyCode::KeyW)));
+    assert!(BindingPreset::LeftHanded.bindings().unwrap().contains_key(&key(KeyCode::KeyI)));
+    assert!(BindingPreset::ArrowKeys.bindings().unwrap().contains_key(&key(KeyCode::ArrowUp)));
+    assert!(!BindingPreset::ArrowKeys.bindings().unwrap().contains_key(&key(KeyCode::KeyW)));
+
+}
+
+#[test]
+fn test_bindingpresets_apply_and_custom() {
+
+    let pad = BindingChord::from(BindingSources::Gamepad(gilrs::Button::South.into()));
+    let mut isc = InputSettingsComponent::default();
+    isc.bindings.insert(pad, Binding::held(BindingActions::interact()));
+
+    // Applying a built-in preset replaces keyboard and mouse bindings only.
+    isc.apply_preset(BindingPreset::ArrowKeys).unwrap();
+    assert_eq!(isc.preset, BindingPreset::ArrowKeys);
+    assert!(isc.bindings.contains_key(&pad));
+    assert_eq!(
+        isc.bindings.len(),
+        BindingPreset::ArrowKeys.bindings().unwrap().len() + super::touch_input::default_bindings().len() + 1
+    );
+
+    // Custom presets save the whole binding set, and load it back.
+    let path = std::env::temp_dir().join("test_bindingpresets_custom.ron");
+    isc.save_preset(&path).unwrap();
+    assert_eq!(isc.preset, BindingPreset::Custom(path.clone()));
+
+    let mut loaded = InputSettingsComponent::default();
+    loaded.apply_preset(BindingPreset::Custom(path.clone())).unwrap();
+    assert_eq!(loaded.bindings, isc.bindings);
+
+    // Missing files leave bindings as they were.
+    let before = loaded.bindings.clone();
+    assert!(loaded.apply_preset(BindingPreset::Custom(path.with_extension("missing"))).is_err());
+    assert_eq!(loaded.bindings, before);
+    assert_eq!(loaded.preset, BindingPreset::Custom(path.clone()));
+
+    let _ = fs::remove_file(path);
+
+}
+
+
+/// A named set of bindings.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum BindingPreset {
+
+    /// `WASD` to move, or wherever those keys sit on other layouts, `E` to interact, left `Shift` to sprint and left
+    /// `Ctrl` to crouch. Every built-in preset jumps with `Space` and switches between first and third person
+    /// with `V`.
+    #[default]
+    Qwerty,
+
+    /// Alias of `Qwerty`, moving with `ZQSD` on AZERTY keyboards.
+    Azerty,
+
+    /// Alias of `Qwerty`, moving with `,AOE` on Dvorak keyboards.
+    Dvorak,
+
+    /// For the mouse in the left hand: `IJKL` to move, `U` to interact, right `Shift` to sprint and right `Ctrl` to
+    /// crouch.
+    LeftHanded,
+
+    /// Arrow keys to move, right `Ctrl` to interact, right `Shift` to sprint and `/` to crouch.
+    ArrowKeys,
+
+    /// Bindings saved to a file with `InputSettingsComponent::save_preset()`.
+    Custom(PathBuf),
+
+}
+
+impl BindingPreset {
+
+    /// Presets built into the game, as offered in settings.
+    pub const BUILTIN: [BindingPreset; 3] = [
+        BindingPreset::Qwerty,
+        BindingPreset::LeftHanded,
+        BindingPreset::ArrowKeys,
+    ];
+
+    /// Built-in presets that are aliases of `Qwerty`, kept so players can find it by the name of their layout.
+    pub const ALIASES: [BindingPreset; 2] = [
+        BindingPreset::Azerty,
+        BindingPreset::Dvorak,
+    ];
+
+    /// Name to show for the preset in settings. `Qwerty` and its aliases share one, naming every layout they cover.
+    pub fn label(&self) -> String {
+        match self {
+            BindingPreset::Qwerty
+            | BindingPreset::Azerty
+            | BindingPreset::Dvorak     => String::from("QWERTY / AZERTY / Dvorak (WASD / ZQSD / ,AOE)"),
+            BindingPreset::LeftHanded   => String::from("Left-handed (IJKL)"),
+            BindingPreset::ArrowKeys    => String::from("Arrow keys"),
+            BindingPreset::Custom(path) => format!("Custom ({})", path.display()),
+        }
+    }
+
+    /// Bindings of this preset. Custom presets are read from their file.
+    pub fn bindings(&self) -> io::Result<Bindings> {
+
+        // Positions of forward, backward, left, right, interact, sprint and crouch.
+        let cluster = match self {
+            BindingPreset::Qwerty
+            | BindingPreset::Azerty
+            | BindingPreset::Dvorak     => [
+                KeyCode::KeyW, KeyCode::KeyS, KeyCode::KeyA, KeyCode::KeyD, KeyCode::KeyE,
+                KeyCode::ShiftLeft, KeyCode::ControlLeft
+            ],
+            BindingPreset::LeftHanded   => [
+                KeyCode::KeyI, KeyCode::KeyK, KeyCode::KeyJ, KeyCode::KeyL, KeyCode::KeyU,
+                KeyCode::ShiftRight, KeyCode::ControlRight
+            ],
+            BindingPreset::ArrowKeys    => [
+                KeyCode::ArrowUp, KeyCode::ArrowDown, KeyCode::ArrowLeft, KeyCode::ArrowRight, KeyCode::ControlRight,
+                KeyCode::ShiftRight, KeyCode::Slash
+            ],
+            BindingPreset::Custom(path) => return Self::read(path),
+        };
+
+        let key = |code| BindingChord::from(BindingSources::Desktop(DesktopInputSources::Key(PhysicalKey::Code(code))));
+        let mut bindings = Bindings::new();
+        bindings.insert(key(cluster[0]), Binding::held(BindingActions::linear(LinearDirectionSetting::forward())));
+        bindings.insert(key(cluster[1]), Binding::held(BindingActions::linear(LinearDirectionSetting::backward())));
+        bindings.insert(key(cluster[2]), Binding::held(BindingActions::linear(LinearDirectionSetting::left())));
+        bindings.insert(key(cluster[3]), Binding::held(BindingActions::linear(LinearDirectionSetting::right())));
+        bindings.insert(key(cluster[4]), Binding::held(BindingActions::interact()));
+        bindings.insert(key(cluster[5]), Binding::held(BindingActions::sprint()));
+        bindings.insert(key(cluster[6]), Binding::held(BindingActions::crouch()));
+        bindings.insert(key(KeyCode::Space), Binding::held(BindingActions::jump()));
+        bindings.insert(key(KeyCode::KeyV), Binding::held(BindingActions::toggle_perspective()));
+        bindings.insert(key(KeyCode::Escape), Binding::held(BindingActions::pause()));
+        bindings.insert(key(KeyCode::Backquote), Binding::held(BindingActions::developer_console()));
+        Ok(bindings)
+
+    }
+
+    // Read a custom preset file.
+    fn read(path: &Path) -> io::Result<Bindings> {
+        ron::de::from_str(&fs::read_to_string(path)?)
+            .map_err(|why| io::Error::new(io::ErrorKind::InvalidData, why))
+    }
+
+}
+
+impl InputSettingsComponent {
+
+    /// Switch to the bindings of `preset`, remembering it as the selected preset.
+    ///
+    /// Built-in presets replace keyboard and mouse bindings, leaving gamepad and touch bindings as they are; custom presets
+    /// replace every binding. If a custom preset can't be read, bindings are left unchanged and the error returned.
+    pub fn apply_preset(&mut self, preset: BindingPreset) -> io::Result<()> {
+
+        let bindings = preset.bindings()?;
+        self.preset = preset;
+
+        if let BindingPreset::Custom(_) = self.preset {
+            self.bindings = bindings;
+        }
+        else {
+            self.bindings.retain(|chord, _| !matches!(chord.source, BindingSources::Desktop(_)));
+            self.bindings.extend(bindings);
+        }
+        Ok(())
+
+    }
+
+    /// Save the current bindings as a custom preset file at `path` in [Rusty Object Notation][1], and select it.
+    ///
+    /// [1]: https://docs.rs/ron/0.8.1/ron/index.html
+    pub fn save_preset(&mut self, path: &Path) -> io::Result<()> {
+
+        let buffer = ron::ser::to_string_pretty(&self.bindings, ron::ser::PrettyConfig::default())
+            .map_err(|why| io::Error::new(io::ErrorKind::InvalidData, why))?;
+
+        let mut file = fs::File::create(path)?;
+        file.write_all(buffer.as_bytes())?;
+        self.preset = BindingPreset::Custom(path.to_path_buf());
+        Ok(())
+
+    }
+
+}
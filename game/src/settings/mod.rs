@@ -21,7 +21,7 @@ use fyrox:: {
 use tracing:: { trace, trace_span, info, info_span, warn, warn_span, error, error_span };
 use serde::{Deserialize, Serialize};
 
-mod input;
+pub mod input;
 
 const SETTINGS_SAVENAME: &'static str = "gamesettings.ron";
 const SETTINGS_SAVENAME_OLD: &'static str = "gamesettings.ron.old";
@@ -60,7 +60,32 @@ mod tests {
     #[test]
     fn test_settings_display() {
 
-        // TODO
+        let mut display = DisplaySettings::default();
+
+        // Field of view is kept within the bounds of its axis...
+        assert_eq!(display.fov(), 75.0);
+        assert_eq!(display.fov_set(10.0), FovAxis::Vertical.bounds().0);
+        assert_eq!(display.fov_set(500.0), FovAxis::Vertical.bounds().1);
+
+        // ...including when it's edited in the file, or the axis changes.
+        display.fov = 500.0;
+        assert_eq!(display.fov(), FovAxis::Vertical.bounds().1);
+        display.fov_axis = FovAxis::Horizontal;
+        assert_eq!(display.fov(), FovAxis::Horizontal.bounds().1);
+
+        // Vertical field of view is the same on any screen, so wider screens see more to the sides...
+        assert_eq!(vertical_fov(75.0, FovAxis::Vertical, 16.0 / 9.0), 75.0);
+        assert_eq!(vertical_fov(75.0, FovAxis::Vertical, 21.0 / 9.0), 75.0);
+
+        // ...while horizontal field of view is the same on any screen, so wider screens see less above and below.
+        assert!((vertical_fov(90.0, FovAxis::Horizontal, 1.0) - 90.0).abs() < 1e-3);
+        let widescreen = vertical_fov(90.0, FovAxis::Horizontal, 16.0 / 9.0);
+        let ultrawide = vertical_fov(90.0, FovAxis::Horizontal, 21.0 / 9.0);
+        assert!((widescreen - 58.715).abs() < 1e-2);
+        assert!(ultrawide < widescreen);
+
+        // Screens of unknown size are treated as 16:9.
+        assert_eq!(vertical_fov(90.0, FovAxis::Horizontal, 0.0), widescreen);
 
     }
 
@@ -105,17 +130,18 @@ impl Settings {
     // }
 
     /// Write a file with the content of `Settings` serialized in [Rusty Object Notation][1].
-    /// This file is meant to persist game settings across runtime sessions for *this machine*; Settings on one machine
-    /// should not be transfered to a different machine, as doing so may cause [strange misconfigurations][2].
+    /// This file persists game settings across runtime sessions. Input bindings are written by their [portable
+    /// names][2], so the file is readable, diffable and may be carried over to another machine.
     /// 
     /// **Important details:**
     /// 1. The location of this file is dependent on the Operating System and where it stores application files.
     //    TODO: Implement this functionality ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
     /// 2. A backup of the last settings configuration is kept along with the [version we're saving][3].
+    /// 3. Bindings without a portable name (keys the platform couldn't identify) fail serialization with an
+    ///    `io::ErrorKind::InvalidData` error.
     /// 
     /// [1]: https://docs.rs/ron/0.8.1/ron/index.html
-    /// [2]: Serialization of system-specific event codes prevents such transfer from being a *comfortable experience*
-    ///  to foreign systems.
+    /// [2]: For example `"Key:W"`, `"Mouse:Left"` or `"Pad:South"`.
     /// [3]: Backup copy is available for restoring previous settings.
     /// 
     pub fn save(&self) -> io::Result<()> {
@@ -125,7 +151,7 @@ impl Settings {
         }
 
         let buffer = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
-            .expect("Serialization error!"); // TODO: Replace with something less crashy.
+            .map_err(|why| io::Error::new(io::ErrorKind::InvalidData, why))?;
 
         let mut new_save = fs::File::create(SETTINGS_SAVENAME)?;
         new_save.write_all(buffer.as_bytes())
@@ -133,20 +159,19 @@ impl Settings {
     }
 
     /// Load the content of the settings save file previously serialized in [Rusty Object Notation][1].
-    /// This file is meant to persist game settings across runtime sessions for *this machine*; Settings on one machine
-    /// should not be transfered to a different machine, as doing so may cause [strange misconfigurations][2]. 
+    /// Settings files are portable between machines, as input bindings are stored by name rather than by
+    /// platform-specific event codes.
     /// 
     /// You may override the `path` of the file to read from by passing `Some(PathBuf)` as an argument, otherwise the 
     /// default location is read from instead.
     /// 
     /// [1]: https://docs.rs/ron/0.8.1/ron/index.html
-    /// [2]: Serialization of system-specific event codes prevents such transfer from being a *comfortable experience*
-    ///  to foreign systems.
     /// 
     pub fn load(&mut self, path: Option<std::path::PathBuf>) -> io::Result<()> {
 
         let read_save = fs::File::open(path.unwrap_or(SETTINGS_SAVENAME.into()))?;
-        *self = ron::de::from_reader(read_save).unwrap(); // TODO: Replace .unwrap()
+        *self = ron::de::from_reader(read_save)
+            .map_err(|why| io::Error::new(io::ErrorKind::InvalidData, why))?;
         Ok(())
 
     }
@@ -170,6 +195,43 @@ impl Default for Settings {
     }
 }
 
+/// Which way across the screen the field of view setting is measured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum FovAxis {
+
+    /// Top to bottom. Wider screens see more to the sides ("Hor+"), which suits ultrawide screens.
+    #[default]
+    Vertical,
+
+    /// Side to side. Every screen sees as far to the sides, so wider screens see less above and below.
+    Horizontal,
+
+}
+
+impl FovAxis {
+
+    /// Narrowest and widest field of view that may be set along this axis, in degrees.
+    pub fn bounds(&self) -> (f32, f32) {
+        match self {
+            FovAxis::Vertical   => (50.0, 100.0),
+            FovAxis::Horizontal => (70.0, 130.0),
+        }
+    }
+
+}
+
+/// Vertical field of view, in degrees, for a field of view of `fov` degrees measured along `axis` on a screen of
+/// `aspect` ratio (width over height). Screens of unknown size, with an `aspect` of zero, are treated as 16:9.
+pub fn vertical_fov(fov: f32, axis: FovAxis, aspect: f32) -> f32 {
+    match axis {
+        FovAxis::Vertical   => fov,
+        FovAxis::Horizontal => {
+            let aspect = if aspect > 0.0 { aspect } else { 16.0 / 9.0 };
+            2.0 * ((fov.to_radians() / 2.0).tan() / aspect).atan().to_degrees()
+        },
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DisplaySettings {
 
@@ -183,7 +245,41 @@ pub struct DisplaySettings {
     /// Monitor preference.
     //? Represents the index # in the collection of monitors provided by Fyrox and points to the desired one.
     //? Default is 0 which equates to the primary monitor.
-    pub monitor:            u8
+    pub monitor:            u8,
+
+    /// Field of view preference, in degrees along `fov_axis`. Read through `fov()`, which keeps it within bounds.
+    #[serde(default = "DisplaySettings::default_fov")]
+    pub fov:                f32,
+
+    /// Whether `fov` is measured top to bottom or side to side.
+    #[serde(default)]
+    pub fov_axis:           FovAxis,
+
+    /// Reduced motion preference: no head bob, field of view kick, camera shake or look smoothing.
+    #[serde(default)]
+    pub reduced_motion:     bool
+
+}
+
+impl DisplaySettings {
+
+    /// Field of view preference, in degrees along `fov_axis`, within the bounds of that axis.
+    pub fn fov(&self) -> f32 {
+        let (min, max) = self.fov_axis.bounds();
+        self.fov.clamp(min, max)
+    }
+
+    /// Change the field of view preference to `new` degrees, kept within the bounds of `fov_axis`. Returns the value
+    /// set.
+    pub fn fov_set(&mut self, new: f32) -> f32 {
+        let (min, max) = self.fov_axis.bounds();
+        self.fov = new.clamp(min, max);
+        self.fov
+    }
+
+    fn default_fov() -> f32 {
+        75.0
+    }
 
 }
 
@@ -194,7 +290,10 @@ impl Default for DisplaySettings {
             resolution_width:   960,
             resolution_height:  540,
             scalefactor:        1.0,
-            monitor:            0
+            monitor:            0,
+            fov:                Self::default_fov(),
+            fov_axis:           FovAxis::default(),
+            reduced_motion:     false
         }
     }
 }
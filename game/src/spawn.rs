@@ -0,0 +1,54 @@
+//!
+//! Spawn points.
+//!
+//! A `SpawnPoint` script on a node marks where the player may be spawned into a scene, facing the way the node faces.
+//! When a scene without a player of its own loads, `Game` builds the player at the spawn point it was asked for by
+//! name, or else at the scene's default one. Saved scenes bring their player along, so aren't spawned into.
+//!
+
+use fyrox:: {
+    core:: { visitor::prelude::*, reflect::prelude::*, type_traits::prelude::* },
+    script::ScriptTrait,
+};
+
+
+
+#[test]
+fn test_spawnpoint_choose() {
+
+    let points = [("Cellar", false), ("Entrance", true), ("Roof", true)];
+
+    // Spawn points asked for by name are chosen first...
+    assert_eq!(choose(&points, Some("Roof")), Some(2));
+
+    // ...then the first default one, if the name isn't found or there's none...
+    assert_eq!(choose(&points, Some("Attic")), Some(1));
+    assert_eq!(choose(&points, None), Some(1));
+
+    // ...then the first one there is.
+    assert_eq!(choose(&[("Cellar", false), ("Roof", false)], None), Some(0));
+    assert_eq!(choose(&[], Some("Roof")), None);
+
+}
+
+
+#[derive(Visit, Reflect, Debug, Clone, Default, TypeUuidProvider, ComponentProvider)]
+#[type_uuid(id = "9d4e6b21-3f8a-4c57-a0d2-6e1b7c9f3a48")]
+#[visit(optional)]
+pub struct SpawnPoint {
+
+    /// Spawn the player here unless another spawn point is asked for by name. If there are several, the first found
+    /// is used.
+    pub default:    bool,
+
+}
+
+impl ScriptTrait for SpawnPoint {}
+
+/// Choose among spawn `points`, given by node name and whether each is a default, the one named `wanted`, else the
+/// first default one, else the first one. Returns its index, or `None` if there are no spawn points.
+pub fn choose(points: &[(&str, bool)], wanted: Option<&str>) -> Option<usize> {
+    wanted.and_then(|wanted| points.iter().position(|(name, _)| *name == wanted))
+        .or_else(|| points.iter().position(|(_, default)| *default))
+        .or_else(|| (!points.is_empty()).then_some(0))
+}
@@ -17,16 +17,23 @@ use fyrox::{
     plugin::{ Plugin, PluginContext },
 };
 use tracing::instrument;
+use crate::input:: { InputContext, InputContextKind, InputSystem };
 
 
 /// Centralized menu system.
 #[derive(Debug, Visit, Reflect)]
 pub struct CoreMenuUI {
 
-    /// Menu visibility with lockable state system.
+    /// Menu visibility with lockable state system. Changed through `.visibility_set()` and `.toggle()`, so the menu
+    /// claims input whenever it's shown.
     #[reflect(hidden)]
     #[visit(skip)]
-    pub visibility:         CoreMenuVisibility,
+    visibility:             CoreMenuVisibility,
+
+    /// Screen widget holding the menu, shown and hidden with it.
+    #[reflect(hidden)]
+    #[visit(skip)]
+    pub root:               Handle<gui::UiNode>,
 
     // /// Menu mode
     // /// 
@@ -69,9 +76,9 @@ impl CoreMenuUI {
         let ui = context.user_interfaces.first_mut();
         let mut context = ui.build_ctx();
 
-        // Screen size.
-        gui::screen::ScreenBuilder::new(
-            gui::widget::WidgetBuilder::new().with_child(
+        // Screen size, hidden like the menu starts out.
+        let root = gui::screen::ScreenBuilder::new(
+            gui::widget::WidgetBuilder::new().with_visibility(visibility.is_show()).with_child(
                 // CoreMenu pause/settings layout grid.
                 gui::grid::GridBuilder::new(
                     gui::widget::WidgetBuilder::new()
@@ -123,6 +130,7 @@ impl CoreMenuUI {
 
         CoreMenuUI {
             visibility,
+            root,
             // mode,
             button_quit,
             button_settings,
@@ -132,6 +140,61 @@ impl CoreMenuUI {
 
     }
 
+    /// Menu visibility.
+    pub fn visibility(&self) -> &CoreMenuVisibility {
+        &self.visibility
+    }
+
+    /// Returns `true` while the menu is on screen, whether or not it's locked there.
+    pub fn is_shown(&self) -> bool {
+        self.visibility.is_show() || self.visibility.is_locked_with(true)
+    }
+
+    /// Change the menu's visibility to `visibility`, showing or hiding it on screen and claiming input to match.
+    pub fn visibility_set(
+        &mut self,
+        visibility: CoreMenuVisibility,
+        input: &mut InputSystem,
+        plugin: &mut PluginContext
+    ) {
+        self.visibility = visibility;
+        self.refresh(input, plugin);
+    }
+
+    /// Show the menu if it's hidden and hide it if it's shown, unless it's locked, claiming input to match.
+    pub fn toggle(&mut self, input: &mut InputSystem, plugin: &mut PluginContext) {
+        self.visibility.toggle();
+        self.refresh(input, plugin);
+    }
+
+    /// Claim input while the menu is shown, and hand it back to gameplay while it's hidden.
+    /// 
+    /// Pushes or removes the `PauseMenu` input context to match `visibility`. Changing visibility through
+    /// `.visibility_set()` or `.toggle()` calls this.
+    pub fn claim_input(&self, input: &mut InputSystem) {
+
+        let shown = self.is_shown();
+        let claimed = input.contains(InputContextKind::PauseMenu);
+
+        if shown && !claimed {
+            input.push(InputContext::new(InputContextKind::PauseMenu));
+        }
+        else if !shown && claimed {
+            input.remove(InputContextKind::PauseMenu);
+        }
+
+    }
+
+    // Show or hide the menu on screen to match `visibility`, and claim input to match.
+    fn refresh(&self, input: &mut InputSystem, plugin: &mut PluginContext) {
+        plugin.user_interfaces.first().send_message(gui::widget::WidgetMessage::visibility(
+            self.root,
+            gui::message::MessageDirection::ToWidget,
+            self.is_shown()
+        ));
+        self.claim_input(input);
+    }
+
 }
 
 #[test]
@@ -14,22 +14,52 @@ use fyrox::{
     plugin::{ Plugin, PluginContext },
 };
 use tracing::instrument;
+use crate::{ input:: { InputSystem, PromptLabel }, settings::input:: { BindingActions, Bindings } };
 
 
 pub mod developer;
 pub mod coremenu;
+pub mod prompt;
 
 
 #[derive(Debug, Reflect, Visit)]
 pub struct UiSubset {
 
-    // TODO: Put Core Menu UI module here.
+    /// Core Menu, shown as the pause menu.
+    #[reflect(hidden)]
+    #[visit(skip)]
+    pub core_menu:          Option<coremenu::CoreMenuUI>,
 
     /// Developer Overlay
     #[reflect(hidden)]
     #[visit(skip)]
     pub developer_overlay:  Option<developer::DeveloperOverlay>,
 
+    /// Prompt to reconnect the player's gamepad, while it's disconnected.
+    #[reflect(hidden)]
+    #[visit(skip)]
+    pub reconnect_prompt:   Option<prompt::PromptOverlay>,
+
+    /// Prompt to respawn, while the player is dead.
+    #[reflect(hidden)]
+    #[visit(skip)]
+    pub respawn_prompt:     Option<prompt::PromptOverlay>,
+
+    /// Prompt to interact with what the player focuses.
+    #[reflect(hidden)]
+    #[visit(skip)]
+    pub interact_prompt:    Option<prompt::PromptOverlay>,
+
+    /// Label of the control to respawn with.
+    #[reflect(hidden)]
+    #[visit(skip)]
+    respawn_label:          PromptLabel,
+
+    /// Label of the control to interact with.
+    #[reflect(hidden)]
+    #[visit(skip)]
+    interact_label:         PromptLabel,
+
     // #[reflect(hidden)]
     // #[visit(skip)]
     // _tracy_framebuffer:     Option<renderer::framework::framebuffer::FrameBuffer>,
@@ -41,7 +71,10 @@ impl UiSubset {
     pub fn new(plugin: &mut PluginContext) -> Self {
 
         // TODO: Perform UI setup routine here.
-        UiSubset::default()
+        UiSubset {
+            core_menu: Some(coremenu::CoreMenuUI::new(plugin)),
+            ..Default::default()
+        }
 
     }
 
@@ -54,6 +87,94 @@ impl UiSubset {
         }
     }
 
+    /// Show or hide the core menu, pausing the game while it's shown by claiming input; see
+    /// `coremenu::CoreMenuUI::claim_input()`.
+    pub fn coremenu_set(&mut self, show: bool, input: &mut InputSystem, plugin: &mut PluginContext) {
+        if let Some(core_menu) = &mut self.core_menu {
+            let visibility = if show { coremenu::CoreMenuVisibility::Show } else { coremenu::CoreMenuVisibility::Hide };
+            core_menu.visibility_set(visibility, input, plugin);
+        }
+    }
+
+    /// Show the core menu if it's hidden and hide it if it's shown, unless it's locked.
+    pub fn coremenu_toggle(&mut self, input: &mut InputSystem, plugin: &mut PluginContext) {
+        if let Some(core_menu) = &mut self.core_menu {
+            core_menu.toggle(input, plugin);
+        }
+    }
+
+    pub fn reconnectprompt_set(&mut self, show: bool, plugin: &mut PluginContext) {
+        if show && self.reconnect_prompt.is_none() {
+            self.reconnect_prompt = Some(prompt::PromptOverlay::new(plugin, "Reconnect your controller"));
+        }
+        else if !show {
+            if let Some(reconnect_prompt) = self.reconnect_prompt.take() {
+                reconnect_prompt.remove(plugin);
+            }
+        }
+    }
+
+    /// Show the respawn prompt while `respawn` is `Some`, with the bindings to respawn by, naming the control to
+    /// respawn with on the active device of `input` if it has a label. The label follows bindings and the active
+    /// device while it's shown.
+    pub fn respawnprompt_set(&mut self, respawn: Option<&Bindings>, input: &InputSystem, plugin: &mut PluginContext) {
+        match respawn {
+            Some(bindings) => {
+                self.respawn_label.refresh(input, bindings);
+                let message = match self.respawn_label.label() {
+                    Some(label) => format!("You died. Press {} to respawn", label),
+                    None        => String::from("You died"),
+                };
+                if let Some(respawn_prompt) = &mut self.respawn_prompt {
+                    respawn_prompt.message_set(plugin, &message);
+                }
+                else {
+                    self.respawn_prompt = Some(prompt::PromptOverlay::new(plugin, &message));
+                }
+            },
+            None => {
+                if let Some(respawn_prompt) = self.respawn_prompt.take() {
+                    respawn_prompt.remove(plugin);
+                }
+            },
+        }
+    }
+
+    /// Show the interaction prompt along the bottom of the screen while `interact` is `Some`, with the interactable's
+    /// prompt and the label of the control to interact with in `bindings`, on the active device of `input`.
+    pub fn interactprompt_set(
+        &mut self,
+        interact: Option<&str>,
+        input: &InputSystem,
+        bindings: &Bindings,
+        plugin: &mut PluginContext
+    ) {
+        match interact {
+            Some(interact) => {
+                self.interact_label.refresh(input, bindings);
+                let message = match self.interact_label.label() {
+                    Some(label) => format!("{} [{}]", interact, label),
+                    None        => interact.to_owned(),
+                };
+                if let Some(interact_prompt) = &mut self.interact_prompt {
+                    interact_prompt.message_set(plugin, &message);
+                }
+                else {
+                    self.interact_prompt = Some(prompt::PromptOverlay::new_aligned(
+                        plugin,
+                        &message,
+                        gui::VerticalAlignment::Bottom
+                    ));
+                }
+            },
+            None => {
+                if let Some(interact_prompt) = self.interact_prompt.take() {
+                    interact_prompt.remove(plugin);
+                }
+            },
+        }
+    }
+
     #[instrument(name = "UI Update", skip(context))]
     pub fn update(&mut self, context: &mut PluginContext) {
         
@@ -87,7 +208,13 @@ impl UiSubset {
 impl Default for UiSubset {
     fn default() -> Self {
         UiSubset {
+            core_menu:              None,
             developer_overlay:      None,
+            reconnect_prompt:       None,
+            respawn_prompt:         None,
+            interact_prompt:        None,
+            respawn_label:          PromptLabel::new(BindingActions::respawn()),
+            interact_label:         PromptLabel::new(BindingActions::interact()),
         }
     }
 }
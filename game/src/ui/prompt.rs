@@ -0,0 +1,97 @@
+
+use fyrox::{
+    core::{
+        pool::Handle,
+        reflect::prelude::*,
+        visitor::prelude::*
+    },
+    plugin:: { PluginContext },
+    gui,
+    gui::UiNode
+};
+
+
+/// Prompt Overlay
+///
+/// A line of text centred on screen, or along its bottom, asking the player to do something.
+#[derive(Debug, Reflect, Visit)]
+pub struct PromptOverlay {
+
+    /// Screen widget holding the prompt, removed along with it.
+    pub root:       Handle<UiNode>,
+
+    /// Prompt text.
+    pub text:       Handle<UiNode>,
+
+    /// Message the prompt text shows.
+    pub message:    String,
+
+}
+
+impl PromptOverlay {
+
+    pub fn new(plugin: &mut PluginContext, message: &str) -> Self {
+        Self::new_aligned(plugin, message, gui::VerticalAlignment::Center)
+    }
+
+    /// Show a prompt centred horizontally, and aligned vertically as `alignment` says.
+    pub fn new_aligned(plugin: &mut PluginContext, message: &str, alignment: gui::VerticalAlignment) -> Self {
+
+        let ui = plugin.user_interfaces.first_mut();
+        let context = &mut ui.build_ctx();
+
+        let text;
+        let root = gui::screen::ScreenBuilder::new(
+            gui::widget::WidgetBuilder::new().with_child({
+                text = gui::text::TextBuilder::new(
+                    gui::widget::WidgetBuilder::new()
+                        .with_horizontal_alignment(gui::HorizontalAlignment::Center)
+                        .with_vertical_alignment(alignment)
+                        .with_margin(gui::Thickness::uniform(32.0))
+                )
+                    .with_text(message)
+                    .with_font_size(32.0)
+                    .build(context);
+                text
+            })
+        ).build(context);
+
+        PromptOverlay {
+            root,
+            text,
+            message: message.to_owned(),
+        }
+
+    }
+
+    /// Change the prompt's message, if it's different.
+    pub fn message_set(&mut self, plugin: &mut PluginContext, message: &str) {
+        if self.message != message {
+            self.message = message.to_owned();
+            plugin.user_interfaces.first().send_message(gui::text::TextMessage::text(
+                self.text,
+                gui::message::MessageDirection::ToWidget,
+                self.message.clone()
+            ));
+        }
+    }
+
+    /// Remove the prompt from the screen.
+    pub fn remove(self, plugin: &mut PluginContext) {
+        plugin.user_interfaces.first().send_message(gui::widget::WidgetMessage::remove(
+            self.root,
+            gui::message::MessageDirection::ToWidget
+        ));
+    }
+
+}
+
+impl Default for PromptOverlay {
+    fn default() -> Self {
+        PromptOverlay {
+            root:       Handle::NONE,
+            text:       Handle::NONE,
+            message:    String::new(),
+        }
+    }
+}